@@ -41,7 +41,7 @@ pub const CREATOR_WALLET_BYTES: [u8; 32] = [
 
 // CLMM constants
 pub const MIN_SQRT_PRICE_X64: u128 = 4295048016; // sqrt(1.0001^-443636) * 2^64
-pub const MAX_SQRT_PRICE_X64: u128 = 79226673515401279992447579055; // sqrt(1.0001^443636) * 2^64
+pub const MAX_SQRT_PRICE_X64: u128 = 79226673521066979257578248091; // sqrt(1.0001^443636) * 2^64
 pub const MIN_TICK: i32 = -443636;
 pub const MAX_TICK: i32 = 443636;
 pub const TICK_ARRAY_SIZE: i32 = 88;
@@ -49,6 +49,13 @@ pub const TICK_SPACING_10: u16 = 10;
 pub const TICK_SPACING_60: u16 = 60;
 pub const TICK_SPACING_200: u16 = 200;
 
+// Routing constants
+pub const MAX_SWAP_HOPS: usize = 3; // Maximum pools in a single batch_swap route
+pub const SWAP_HOP_ACCOUNTS: usize = 6; // pool, input vault, output vault, tick array, platform wallet, creator wallet
+pub const MAX_FEE_SWEEP_POOLS: usize = 8; // Maximum pools in a single collect_protocol_fees_batch
+pub const FEE_SWEEP_POOL_ACCOUNTS: usize = 5; // pool, vault A, vault B, platform token A, platform token B
+pub const FLASH_SWAP_CALLBACK_PREIMAGE: &[u8] = b"global:flash_swap_callback"; // Anchor sighash preimage of the borrower callback
+
 // Fee constants
 pub const FEE_RATE_DENOMINATOR_VALUE: u64 = 1000000;
 pub const PROTOCOL_FEE_RATE_MUL_VALUE: u64 = 12000;
@@ -56,8 +63,18 @@ pub const FUND_FEE_RATE_MUL_VALUE: u64 = 25000;
 pub const DEFAULT_PROTOCOL_FEE_RATE: u32 = 120; // 1.2%
 pub const DEFAULT_TRADE_FEE_RATE: u32 = 2500; // 0.25%
 pub const DEFAULT_FUND_FEE_RATE: u32 = 40000; // 4%
-
-// Platform fee constants (consistent with bonding curve)
+pub const POOL_FEE_TIER_COUNT: usize = 3; // Volume-based trade fee tiers per pool
+pub const MAX_CREATE_POOL_FEE: u64 = 100_000_000_000; // 100 SOL
+pub const DEFAULT_CREATE_POOL_FEE: u64 = 1_000_000_000; // 1 SOL
+pub const MAX_TRADE_FEE_RATE: u32 = 100000; // 10%
+pub const MAX_PROTOCOL_FEE_RATE: u32 = 200000; // 20%
+pub const MAX_FUND_FEE_RATE: u32 = 200000; // 20%
+pub const DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS: u16 = 10000; // Full trade fee on every pool
+pub const DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS: u32 = 3600; // At most one FeeGrowthSnapshotEvent per pool per hour
+pub const AMM_GLOBAL_VERSION: u8 = 2; // Current AmmGlobal layout version
+pub const POOL_STATE_VIEW_VERSION: u8 = 1; // Layout version of get_pool_state return data
+
+// Platform fee constants (consistent with bonding curve); per-pool defaults
 pub const PLATFORM_FEE_BASIS_POINTS: u16 = 300; // 3%
 pub const CREATOR_FEE_BASIS_POINTS: u16 = 100; // 1%
 pub const BASIS_POINTS_DENOMINATOR: u64 = 10000;
@@ -75,6 +92,8 @@ pub const GLOBAL_SEED: &[u8] = b"amm_global";
 pub const POOL_VAULT_SEED: &[u8] = b"pool_vault";
 pub const POOL_REWARD_VAULT_SEED: &[u8] = b"pool_reward_vault";
 pub const PERSONAL_POSITION_SEED: &[u8] = b"personal_position";
+pub const USER_VOLUME_SEED: &[u8] = b"user_volume";
+pub const SWAP_COMMITMENT_SEED: &[u8] = b"swap_commitment";
 
 // Observation constants
 pub const OBSERVATION_SEED: &[u8] = b"observation";
@@ -83,8 +102,8 @@ pub const OBSERVATION_STATE_SEED: &[u8] = b"observation_state";
 // Pool state constants
 pub const POOL_STATUS_INITIALIZED: u8 = 1;
 pub const POOL_STATUS_DISABLED: u8 = 2;
-pub const POOL_STATUS_WITHDRAW_ONLY: u8 = 3;
-pub const POOL_STATUS_SWAP_ONLY: u8 = 4;
+pub const POOL_STATUS_WITHDRAW_ONLY: u8 = 3; // Withdrawals only; swaps and deposits blocked
+pub const POOL_STATUS_SWAP_ONLY: u8 = 4; // Swaps and withdrawals; new deposits blocked
 
 // Multi-sig constants
 pub const REQUIRED_SIGNATURES: u8 = 2; // Require both admin and multisig
@@ -92,6 +111,9 @@ pub const REQUIRED_SIGNATURES: u8 = 2; // Require both admin and multisig
 // Reward constants
 pub const REWARD_NUM: usize = 3;
 pub const REWARD_SEED: &[u8] = b"reward";
+pub const MAX_REWARD_SLOTS: usize = 8; // REWARD_NUM in-struct slots plus overflow PoolRewardSlot accounts
+pub const MIN_REWARD_DURATION: u64 = 604800; // 7 days of emissions must be funded
+pub const MAX_REWARD_ACCRUAL_SECONDS: u64 = 31536000; // Longest idle gap (1 year) credited in one update
 
 // Oracle constants
 pub const OBSERVATION_UPDATE_DURATION_DEFAULT: u32 = 15; // 15 seconds
\ No newline at end of file
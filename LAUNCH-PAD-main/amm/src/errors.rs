@@ -92,7 +92,7 @@ pub enum AmmError {
     #[msg("Invalid tick array")]
     InvalidTickArray,
     
-    #[msg("Tick array not initialized")]
+    #[msg("Tick array not initialized - call initialize_tick_array for this tick range first")]
     TickArrayNotInitialized,
     
     #[msg("Invalid reward index")]
@@ -110,6 +110,9 @@ pub enum AmmError {
     #[msg("Invalid reward amount")]
     InvalidRewardAmount,
     
+    #[msg("Reward emissions exceed funded amount for the minimum duration")]
+    EmissionsExceedFunding,
+    
     #[msg("Oracle not updated")]
     OracleNotUpdated,
     
@@ -175,4 +178,79 @@ pub enum AmmError {
     
     #[msg("Fee calculation failed")]
     FeeCalculationFailed,
+    
+    #[msg("Invalid swap route")]
+    InvalidSwapRoute,
+    
+    #[msg("Too many hops in swap route")]
+    TooManySwapHops,
+    
+    #[msg("Operations are not paused")]
+    OperationsNotPaused,
+    
+    #[msg("Invalid volume fee tier configuration")]
+    InvalidVolumeFeeTier,
+    
+    #[msg("Tick range is narrower than the configured minimum")]
+    TickRangeTooNarrow,
+    
+    #[msg("Reward emissions exceed the slot's maximum rate")]
+    EmissionsExceedCap,
+    
+    #[msg("Fee split exceeds the gross trade fee")]
+    InvalidFeeSplit,
+    
+    #[msg("Swap would produce zero output")]
+    ZeroOutputAmount,
+    
+    #[msg("Net tick liquidity across the pool's tick arrays does not sum to zero")]
+    TickLiquidityImbalance,
+    
+    #[msg("Reward mint already initialized at another index")]
+    DuplicateRewardMint,
+    
+    #[msg("Invalid pool status")]
+    InvalidPoolStatus,
+    
+    #[msg("Account is not at the version this migration upgrades from")]
+    InvalidAccountVersion,
+    
+    #[msg("Native SOL wrapping requires a wSOL token account")]
+    InvalidNativeMint,
+    
+    #[msg("Flash swap callback program is not allowed")]
+    InvalidFlashSwapCallback,
+    
+    #[msg("Flash swap was not repaid with the input amount and trade fee")]
+    FlashSwapNotRepaid,
+    
+    #[msg("Swap is too small: trade fee rounds to zero")]
+    SwapTooSmall,
+    
+    #[msg("Transaction deadline has passed")]
+    TransactionExpired,
+    
+    #[msg("Overflow reward slot account is missing or does not match the reward index")]
+    InvalidRewardSlot,
+    
+    #[msg("Revealed swap parameters do not match the commitment")]
+    SwapCommitmentMismatch,
+    
+    #[msg("Swap commitment must be revealed in a later slot")]
+    SwapRevealTooEarly,
+    
+    #[msg("Invalid protocol fee sweep accounts")]
+    InvalidFeeSweepAccounts,
+    
+    #[msg("Too many pools in protocol fee sweep")]
+    TooManyFeeSweepPools,
+    
+    #[msg("Fee growth snapshot interval has not elapsed")]
+    FeeSnapshotTooSoon,
+    
+    #[msg("Tick array still has initialized ticks")]
+    TickArrayNotEmpty,
+    
+    #[msg("Tick array covers the pool's current tick")]
+    ActiveTickArray,
 }
\ No newline at end of file
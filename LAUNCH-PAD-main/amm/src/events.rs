@@ -26,6 +26,9 @@ pub struct PoolCreatedEvent {
     pub trade_fee_rate: u32,
     pub protocol_fee_rate: u32,
     pub fund_fee_rate: u32,
+    pub platform_fee_basis_points: u16,
+    pub creator_fee_basis_points: u16,
+    pub token_creator: Option<Pubkey>,
     pub created_by: Pubkey,
     pub timestamp: i64,
 }
@@ -60,6 +63,42 @@ pub struct LiquidityDecreasedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PositionRebalancedEvent {
+    pub position_mint: Pubkey,
+    pub pool_id: Pubkey,
+    pub previous_tick_lower: i32,
+    pub previous_tick_upper: i32,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity_removed: u128,
+    pub liquidity_added: u128,
+    pub amount0_withdrawn: u64,
+    pub amount1_withdrawn: u64,
+    pub amount0_deposited: u64,
+    pub amount1_deposited: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeeGrowthSnapshotEvent {
+    pub pool_id: Pubkey,
+    pub fee_growth_global_a_x64: u128,
+    pub fee_growth_global_b_x64: u128,
+    pub liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolLiquiditySnapshotEvent {
+    pub pool_id: Pubkey,
+    pub liquidity: u128,
+    pub protocol_liquidity: u128,
+    pub liquidity_reserve_a: u64,
+    pub liquidity_reserve_b: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct SwapEvent {
     pub pool_id: Pubkey,
@@ -74,6 +113,105 @@ pub struct SwapEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct SwapQuoteEvent {
+    pub pool_id: Pubkey,
+    pub zero_for_one: bool,
+    pub is_base_input: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub new_sqrt_price: u128,
+    pub new_tick: i32,
+    pub fee_amount: u64,
+    pub is_partial_fill: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserVolumeAccumulatorInitializedEvent {
+    pub user: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapCommittedEvent {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub commitment: [u8; 32],
+    pub committed_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SwapRevealedEvent {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub committed_slot: u64,
+    pub revealed_slot: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct UserVolumeAccumulatorClosedEvent {
+    pub user: Pubkey,
+    pub volume_a: u64,
+    pub volume_b: u64,
+    pub swaps_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PositionAmountsEvent {
+    pub position_mint: Pubkey,
+    pub pool_id: Pubkey,
+    pub liquidity: u128,
+    pub amount0: u64,
+    pub amount1: u64,
+    pub fees_owed_a: u64,
+    pub fees_owed_b: u64,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BatchSwapEvent {
+    pub user: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub input_amount: u64,
+    pub output_amount: u64,
+    pub hops: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FlashSwapEvent {
+    pub pool_id: Pubkey,
+    pub user: Pubkey,
+    pub callback_program: Pubkey,
+    pub input_mint: Pubkey,
+    pub output_mint: Pubkey,
+    pub amount_owed: u64,
+    pub amount_repaid: u64,
+    pub output_amount: u64,
+    pub fee_amount: u64,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct EmergencyLiquidityWithdrawnEvent {
+    pub position_mint: Pubkey,
+    pub pool_id: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity_delta: u128,
+    pub amount0: u64,
+    pub amount1: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct FeesCollectedEvent {
     pub position_mint: Pubkey,
@@ -94,17 +232,71 @@ pub struct ProtocolFeesCollectedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TokensRescuedEvent {
+    pub pool_id: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub accounted_amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DustSweptEvent {
+    pub pool_id: Pubkey,
+    pub amount0: u64,
+    pub amount1: u64,
+    pub accounted_amount0: u64,
+    pub accounted_amount1: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PoolFeesUpdatedEvent {
     pub pool_id: Pubkey,
     pub trade_fee_rate: u32,
     pub protocol_fee_rate: u32,
     pub fund_fee_rate: u32,
+    pub platform_fee_basis_points: u16,
+    pub creator_fee_basis_points: u16,
+    pub volume_thresholds: Vec<u64>,
+    pub tier_trade_fee_rates: Vec<u32>,
+    pub admin_authority: Pubkey,
+    pub multisig_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolStatusUpdatedEvent {
+    pub pool_id: Pubkey,
+    pub previous_status: u8,
+    pub new_status: u8,
     pub admin_authority: Pubkey,
     pub multisig_authority: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AmmGlobalMigratedEvent {
+    pub amm_global: Pubkey,
+    pub previous_version: u8,
+    pub new_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMigratedEvent {
+    pub pool_id: Pubkey,
+    pub previous_len: u64,
+    pub new_len: u64,
+    pub liquidity_reserve_a: u64,
+    pub liquidity_reserve_b: u64,
+    pub protocol_liquidity: u128,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct TickArrayInitializedEvent {
     pub pool_id: Pubkey,
@@ -113,6 +305,26 @@ pub struct TickArrayInitializedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct TickArrayRecomputedEvent {
+    pub pool_id: Pubkey,
+    pub tick_array: Pubkey,
+    pub previous_initialized_tick_count: u32,
+    pub initialized_tick_count: u32,
+    pub ticks_cleared: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TickArrayClosedEvent {
+    pub pool_id: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+    pub rent_recipient: Pubkey,
+    pub rent_reclaimed: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RewardInitializedEvent {
     pub pool_id: Pubkey,
@@ -132,6 +344,26 @@ pub struct RewardEmissionUpdatedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct RewardFundedEvent {
+    pub pool_id: Pubkey,
+    pub reward_index: u8,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub funded_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardAuthorityUpdatedEvent {
+    pub pool_id: Pubkey,
+    pub reward_index: u8,
+    pub previous_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct EmergencyPauseAmmEvent {
     pub admin_authority: Pubkey,
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::{state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*};
+use anchor_spl::token::TokenAccount;
+use crate::{constants::{BASIS_POINTS_DENOMINATOR, MAX_CREATE_POOL_FEE, POOL_FEE_TIER_COUNT, POOL_STATUS_DISABLED, POOL_STATUS_INITIALIZED, POOL_STATUS_SWAP_ONLY, POOL_STATUS_WITHDRAW_ONLY, REWARD_NUM}, state::{AmmGlobal, Pool, PoolFeeTier, PoolV1}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct UpdatePoolFees<'info> {
@@ -66,11 +67,161 @@ pub struct ResumeAmmOperations<'info> {
     pub multisig_authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetCreatePoolFee<'info> {
+    #[account(mut)]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for fee updates)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinTickRangeMultiple<'info> {
+    #[account(mut)]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for position settings)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSnapshotInterval<'info> {
+    #[account(mut)]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for global settings)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGlobalFeeMultiplier<'info> {
+    #[account(mut)]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for fee updates)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolStatus<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for pool status changes)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalV2<'info> {
+    #[account(mut)]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for account migrations)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigratePoolV2<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Pool still in the v1 layout, which no longer deserializes as Pool
+    /// CHECK: Owner checked here; discriminator and size checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ AmmError::InvalidAccountOwner
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// Pool vault for token A; checked against the v1 pool in the handler
+    pub vault_a: Account<'info, TokenAccount>,
+
+    /// Pool vault for token B; checked against the v1 pool in the handler
+    pub vault_b: Account<'info, TokenAccount>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for account migrations)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 pub fn update_pool_fees(
     ctx: Context<UpdatePoolFees>,
     trade_fee_rate: u32,
     protocol_fee_rate: u32,
     fund_fee_rate: u32,
+    volume_fee_tiers: Option<[PoolFeeTier; POOL_FEE_TIER_COUNT]>,
+    platform_fee_basis_points: Option<u16>,
+    creator_fee_basis_points: Option<u16>,
 ) -> Result<()> {
     let amm_global = &ctx.accounts.amm_global;
     let pool = &mut ctx.accounts.pool;
@@ -88,6 +239,35 @@ pub fn update_pool_fees(
     pool.trade_fee_rate = trade_fee_rate;
     pool.protocol_fee_rate = protocol_fee_rate;
     pool.fund_fee_rate = fund_fee_rate;
+
+    // Update platform/creator fee split if provided
+    if let Some(platform_fee) = platform_fee_basis_points {
+        pool.platform_fee_basis_points = platform_fee;
+    }
+    if let Some(creator_fee) = creator_fee_basis_points {
+        pool.creator_fee_basis_points = creator_fee;
+    }
+    pool.validate_fee_split()?;
+
+    // Register volume tiers if provided
+    if let Some(volume_fee_tiers) = volume_fee_tiers {
+        pool.volume_fee_tiers = volume_fee_tiers;
+    }
+
+    // Enabled tiers must be ordered by threshold and only ever discount the trade fee
+    let mut previous_threshold = 0u64;
+    for tier in pool.volume_fee_tiers.iter().filter(|tier| tier.is_enabled()) {
+        require!(
+            tier.volume_threshold > previous_threshold,
+            AmmError::InvalidVolumeFeeTier
+        );
+        require!(
+            tier.trade_fee_rate <= pool.trade_fee_rate,
+            AmmError::FeeTooHigh
+        );
+        previous_threshold = tier.volume_threshold;
+    }
+
     pool.updated_at = clock.unix_timestamp;
 
     // Emit pool fees updated event
@@ -96,6 +276,10 @@ pub fn update_pool_fees(
         trade_fee_rate,
         protocol_fee_rate,
         fund_fee_rate,
+        platform_fee_basis_points: pool.platform_fee_basis_points,
+        creator_fee_basis_points: pool.creator_fee_basis_points,
+        volume_thresholds: pool.volume_fee_tiers.iter().map(|tier| tier.volume_threshold).collect(),
+        tier_trade_fee_rates: pool.volume_fee_tiers.iter().map(|tier| tier.trade_fee_rate).collect(),
         admin_authority: ctx.accounts.admin_authority.key(),
         multisig_authority: ctx.accounts.multisig_authority.key(),
         timestamp: clock.unix_timestamp,
@@ -115,6 +299,8 @@ pub fn update_pool_fees(
     msg!("Trade Fee: {}%", trade_fee_rate as f64 / 10000.0);
     msg!("Protocol Fee: {}%", protocol_fee_rate as f64 / 10000.0);
     msg!("Fund Fee: {}%", fund_fee_rate as f64 / 10000.0);
+    msg!("Platform Fee Share: {} bps", pool.platform_fee_basis_points);
+    msg!("Creator Fee Share: {} bps", pool.creator_fee_basis_points);
 
     Ok(())
 }
@@ -195,4 +381,308 @@ pub fn resume_amm_operations(ctx: Context<ResumeAmmOperations>) -> Result<()> {
     msg!("✅ AMM Operations resumed - Platform is operational");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn set_create_pool_fee(ctx: Context<SetCreatePoolFee>, new_fee: u64) -> Result<()> {
+    let amm_global = &mut ctx.accounts.amm_global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for global fee updates
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Cap the fee so a mistyped value cannot lock out pool creation
+    require!(new_fee <= MAX_CREATE_POOL_FEE, AmmError::FeeTooHigh);
+
+    let previous_fee = amm_global.create_pool_fee;
+    amm_global.create_pool_fee = new_fee;
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "CREATE_POOL_FEE_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💰 Pool creation fee updated");
+    msg!("Previous Fee: {} SOL", previous_fee as f64 / 1_000_000_000.0);
+    msg!("New Fee: {} SOL", new_fee as f64 / 1_000_000_000.0);
+
+    Ok(())
+}
+
+pub fn set_min_tick_range_multiple(
+    ctx: Context<SetMinTickRangeMultiple>,
+    min_tick_range_multiple: u32,
+) -> Result<()> {
+    let amm_global = &mut ctx.accounts.amm_global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for position settings
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    let previous_multiple = amm_global.min_tick_range_multiple;
+    amm_global.min_tick_range_multiple = min_tick_range_multiple;
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "MIN_TICK_RANGE_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📏 Minimum position tick range updated");
+    msg!("Previous Multiple: {} tick spacings", previous_multiple);
+    msg!("New Multiple: {} tick spacings", min_tick_range_multiple);
+
+    Ok(())
+}
+
+pub fn set_fee_snapshot_interval(
+    ctx: Context<SetFeeSnapshotInterval>,
+    fee_snapshot_interval_seconds: u32,
+) -> Result<()> {
+    let amm_global = &mut ctx.accounts.amm_global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for global settings
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    let previous_interval = amm_global.fee_snapshot_interval_seconds;
+    amm_global.fee_snapshot_interval_seconds = fee_snapshot_interval_seconds;
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "FEE_SNAPSHOT_INTERVAL_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📈 Fee growth snapshot interval updated");
+    msg!("Previous Interval: {} seconds", previous_interval);
+    msg!("New Interval: {} seconds", fee_snapshot_interval_seconds);
+
+    Ok(())
+}
+
+pub fn set_global_fee_multiplier(
+    ctx: Context<SetGlobalFeeMultiplier>,
+    global_fee_multiplier_bps: u16,
+) -> Result<()> {
+    let amm_global = &mut ctx.accounts.amm_global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for global fee updates
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Promotions may only discount trade fees, never raise them
+    require!(
+        global_fee_multiplier_bps as u64 <= BASIS_POINTS_DENOMINATOR,
+        AmmError::FeeTooHigh
+    );
+
+    let previous_multiplier = amm_global.global_fee_multiplier_bps;
+    amm_global.global_fee_multiplier_bps = global_fee_multiplier_bps;
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "GLOBAL_FEE_MULTIPLIER_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💰 Global trade fee multiplier updated");
+    msg!("Previous Multiplier: {} bps", previous_multiplier);
+    msg!("New Multiplier: {} bps", global_fee_multiplier_bps);
+
+    Ok(())
+}
+
+pub fn set_pool_status(ctx: Context<SetPoolStatus>, status: u8) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for pool status changes
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    require!(
+        status == POOL_STATUS_INITIALIZED
+            || status == POOL_STATUS_DISABLED
+            || status == POOL_STATUS_WITHDRAW_ONLY
+            || status == POOL_STATUS_SWAP_ONLY,
+        AmmError::InvalidPoolStatus
+    );
+
+    // Only this pool changes; every other pool stays live
+    let previous_status = pool.status;
+    pool.status = status;
+    pool.updated_at = clock.unix_timestamp;
+
+    emit!(PoolStatusUpdatedEvent {
+        pool_id: pool.key(),
+        previous_status,
+        new_status: status,
+        admin_authority: ctx.accounts.admin_authority.key(),
+        multisig_authority: ctx.accounts.multisig_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: if status == POOL_STATUS_DISABLED { "POOL_PAUSED" } else { "POOL_STATUS_UPDATED" }.to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: pool.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔧 Pool status updated with multi-sig authorization");
+    msg!("Pool: {}", pool.key());
+    msg!("Status: {} -> {}", previous_status, status);
+
+    Ok(())
+}
+
+pub fn migrate_global_v2(ctx: Context<MigrateGlobalV2>) -> Result<()> {
+    let amm_global = &mut ctx.accounts.amm_global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for account migrations
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // v2 fields were carved out of reserved, so a v1 account already loads at this size
+    let previous_version = amm_global.version;
+    amm_global.migrate_to_v2()?;
+
+    emit!(AmmGlobalMigratedEvent {
+        amm_global: amm_global.key(),
+        previous_version,
+        new_version: amm_global.version,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "AMM_GLOBAL_MIGRATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔄 AMM global account migrated");
+    msg!("Version: {} -> {}", previous_version, amm_global.version);
+    msg!("Global Fee Multiplier: {} bps", amm_global.global_fee_multiplier_bps);
+
+    Ok(())
+}
+
+/// Reallocate a v1 pool to Pool::LEN and rewrite it in the current layout.
+/// `protocol_liquidity` is the in-pool liquidity of positions owned by the multi-sig
+/// treasury, which v1 did not track. The reward vault of every initialized reward slot
+/// follows in `remaining_accounts`, in slot order, to seed its funded amount.
+pub fn migrate_pool_v2<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MigratePoolV2<'info>>,
+    protocol_liquidity: u128,
+) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool_info = ctx.accounts.pool.to_account_info();
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for account migrations
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Only a pool still at the v1 size is migrated, so a second run is rejected
+    let pool_v1 = {
+        let data = pool_info.try_borrow_data()?;
+        require!(data.len() == PoolV1::LEN, AmmError::InvalidAccountVersion);
+        require!(
+            &data[..8] == Pool::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        PoolV1::deserialize(&mut &data[8..])?
+    };
+    require!(
+        ctx.accounts.vault_a.key() == pool_v1.vault_a && ctx.accounts.vault_b.key() == pool_v1.vault_b,
+        AmmError::InvalidTokenAccount
+    );
+    require!(protocol_liquidity <= pool_v1.liquidity, AmmError::InvalidLiquidityAmount);
+
+    let mut reward_vault_amounts = [0u64; REWARD_NUM];
+    let mut reward_vaults = ctx.remaining_accounts.iter();
+    for (slot, reward_info) in pool_v1.reward_infos.iter().enumerate() {
+        if reward_info.mint == Pubkey::default() {
+            continue;
+        }
+        let reward_vault = Account::<TokenAccount>::try_from(
+            reward_vaults.next().ok_or(AmmError::InvalidTokenAccount)?,
+        )?;
+        require!(reward_vault.key() == reward_info.vault, AmmError::InvalidTokenAccount);
+        reward_vault_amounts[slot] = reward_vault.amount;
+    }
+
+    let pool = pool_v1.into_current(
+        ctx.accounts.vault_a.amount,
+        ctx.accounts.vault_b.amount,
+        reward_vault_amounts,
+        protocol_liquidity,
+    );
+
+    // Top up rent for the larger account, then grow and rewrite it
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(Pool::LEN)
+        .saturating_sub(pool_info.lamports());
+    if rent_shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    pool_info.resize(Pool::LEN)?;
+    {
+        let mut data = pool_info.try_borrow_mut_data()?;
+        let mut writer: &mut [u8] = &mut data;
+        pool.try_serialize(&mut writer)?;
+    }
+
+    emit!(PoolMigratedEvent {
+        pool_id: pool_info.key(),
+        previous_len: PoolV1::LEN as u64,
+        new_len: Pool::LEN as u64,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        protocol_liquidity,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "POOL_MIGRATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: pool_info.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔄 Pool account migrated");
+    msg!("Pool: {}", pool_info.key());
+    msg!("Size: {} -> {} bytes", PoolV1::LEN, Pool::LEN);
+    msg!("Liquidity Reserve A: {} tokens", pool.liquidity_reserve_a);
+    msg!("Liquidity Reserve B: {} tokens", pool.liquidity_reserve_b);
+
+    Ok(())
+}
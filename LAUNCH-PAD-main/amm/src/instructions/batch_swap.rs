@@ -0,0 +1,290 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, TokenAccount, Transfer},
+};
+use crate::{constants::*, state::{AmmGlobal, Pool, TickArray}, events::*, errors::*};
+use super::swap::{apply_swap_to_pool, calculate_swap, calculate_swap_fees, cross_ticks, pay_out_swap_fees};
+
+/// Route accounts are passed in `remaining_accounts`, SWAP_HOP_ACCOUNTS per hop:
+/// pool, input vault, output vault, tick array, platform wallet, creator wallet.
+/// The wallets receive that hop's fee shares in the hop's input token, exactly as in `swap`.
+#[derive(Accounts)]
+pub struct BatchSwap<'info> {
+    #[account(
+        mut,
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// User's input token account for the first hop
+    #[account(
+        mut,
+        constraint = input_token_account.owner == user.key()
+            @ AmmError::InvalidAccountOwner
+    )]
+    pub input_token_account: Account<'info, TokenAccount>,
+
+    /// User's output token account for the final hop
+    #[account(
+        mut,
+        constraint = output_token_account.owner == user.key()
+            @ AmmError::InvalidAccountOwner
+    )]
+    pub output_token_account: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn batch_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchSwap<'info>>,
+    amount_in: u64,
+    other_amount_threshold: u64,
+    hop_minimum_amounts_out: Vec<u64>,
+) -> Result<()> {
+    let route = ctx.remaining_accounts;
+    let fee_multiplier_bps = ctx.accounts.amm_global.fee_multiplier_bps();
+    let clock = Clock::get()?;
+
+    // Validate input amount
+    require!(amount_in > 0, AmmError::InvalidTokenAmount);
+
+    // Validate route shape; `is_multiple_of` needs a newer rustc than the SBF toolchain ships
+    #[allow(clippy::manual_is_multiple_of)]
+    let whole_hops = route.len() % SWAP_HOP_ACCOUNTS == 0;
+    require!(
+        !route.is_empty() && whole_hops,
+        AmmError::InvalidSwapRoute
+    );
+    let hop_count = route.len() / SWAP_HOP_ACCOUNTS;
+    require!(hop_count <= MAX_SWAP_HOPS, AmmError::TooManySwapHops);
+    require!(
+        hop_minimum_amounts_out.len() == hop_count,
+        AmmError::InvalidSwapRoute
+    );
+
+    // Check if user has sufficient input tokens
+    require!(
+        ctx.accounts.input_token_account.amount >= amount_in,
+        AmmError::InsufficientTokenBalance
+    );
+
+    let mut hop_amount_in = amount_in;
+    let mut hop_input_mint = ctx.accounts.input_token_account.mint;
+    let mut total_fees: u64 = 0;
+
+    for (hop, hop_accounts) in route.chunks(SWAP_HOP_ACCOUNTS).enumerate() {
+        let mut pool = Account::<Pool>::try_from(&hop_accounts[0])?;
+        let input_vault = Account::<TokenAccount>::try_from(&hop_accounts[1])?;
+        let output_vault = Account::<TokenAccount>::try_from(&hop_accounts[2])?;
+        let mut tick_array = Box::new(Account::<TickArray>::try_from(&hop_accounts[3])?);
+        let platform_wallet = &hop_accounts[4];
+        let creator_wallet = &hop_accounts[5];
+        let is_last_hop = hop + 1 == hop_count;
+
+        require!(pool.allows_swaps(), AmmError::PoolDisabled);
+        require!(
+            tick_array.pool_id == pool.key(),
+            AmmError::InvalidTickArray
+        );
+
+        // Vaults must belong to this pool and the input must continue the previous hop
+        let zero_for_one = input_vault.key() == pool.vault_a && output_vault.key() == pool.vault_b;
+        require!(
+            zero_for_one || (input_vault.key() == pool.vault_b && output_vault.key() == pool.vault_a),
+            AmmError::InvalidTokenAccount
+        );
+        require!(input_vault.mint == hop_input_mint, AmmError::InvalidSwapRoute);
+        if is_last_hop {
+            require!(
+                output_vault.mint == ctx.accounts.output_token_account.mint,
+                AmmError::InvalidSwapRoute
+            );
+        }
+
+        // Each hop is exact input; only the per-hop minimum bounds intermediate output
+        let sqrt_price_limit_x64 = if zero_for_one { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 };
+        let computation = calculate_swap(
+            &pool,
+            &tick_array,
+            &[],
+            hop_amount_in,
+            sqrt_price_limit_x64,
+            zero_for_one,
+            true,
+            fee_multiplier_bps,
+        )?;
+        let swap_amount_in = computation.amount_in;
+        let amount_out = computation.amount_out;
+
+        // Each hop prices within its single tick array and must consume its whole input
+        require!(!computation.is_partial_fill, AmmError::InsufficientLiquidity);
+        require!(amount_out > 0, AmmError::ZeroOutputAmount);
+        require!(output_vault.amount >= amount_out, AmmError::InsufficientTokenBalance);
+        require!(
+            amount_out <= pool.output_liquidity_reserve(zero_for_one),
+            AmmError::InsufficientLiquidity
+        );
+        require!(
+            amount_out >= hop_minimum_amounts_out[hop],
+            AmmError::SlippageExceeded
+        );
+
+        let fees = calculate_swap_fees(&pool, swap_amount_in, computation.trade_fee)?;
+
+        // First hop is funded by the user; later hops were funded by the previous pool
+        if hop == 0 {
+            let transfer_input_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.input_token_account.to_account_info(),
+                    to: input_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            token::transfer(transfer_input_ctx, swap_amount_in)?;
+        }
+
+        // Output goes straight into the next hop's input vault, or to the user on the last hop
+        let destination = if is_last_hop {
+            ctx.accounts.output_token_account.to_account_info()
+        } else {
+            route[(hop + 1) * SWAP_HOP_ACCOUNTS + 1].clone()
+        };
+
+        {
+            let pool_seeds = &[
+                POOL_SEED,
+                pool.mint_a.as_ref(),
+                pool.mint_b.as_ref(),
+                &[pool.bump],
+            ];
+            let pool_signer = &[&pool_seeds[..]];
+
+            let transfer_output_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: output_vault.to_account_info(),
+                    to: destination,
+                    authority: pool.to_account_info(),
+                },
+                pool_signer,
+            );
+            token::transfer(transfer_output_ctx, amount_out)?;
+        }
+
+        // Pay this hop's platform and creator shares out of its input vault
+        pay_out_swap_fees(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.amm_global,
+            &pool,
+            &input_vault.to_account_info(),
+            platform_wallet,
+            creator_wallet,
+            &fees,
+        )?;
+
+        cross_ticks(&mut pool, &mut tick_array, &[], &computation.crossed_ticks, zero_for_one, clock.unix_timestamp)?;
+        apply_swap_to_pool(
+            &mut pool,
+            zero_for_one,
+            &computation,
+            &fees,
+            clock.unix_timestamp,
+        )?;
+
+        // Persist now so a pool reused later in the route sees this hop
+        pool.exit(&crate::ID)?;
+        tick_array.exit(&crate::ID)?;
+
+        emit!(SwapEvent {
+            pool_id: pool.key(),
+            user: ctx.accounts.user.key(),
+            input_mint: input_vault.mint,
+            output_mint: output_vault.mint,
+            input_amount: swap_amount_in,
+            output_amount: amount_out,
+            fee_amount: fees.trade_fee,
+            sqrt_price_x64: pool.sqrt_price_x64,
+            tick_current: pool.tick_current,
+            timestamp: clock.unix_timestamp,
+        });
+
+        // Update global volume tracking
+        let amm_global = &mut ctx.accounts.amm_global;
+        amm_global.total_volume = amm_global.total_volume
+            .checked_add(swap_amount_in)
+            .ok_or(AmmError::Overflow)?;
+        amm_global.total_fees_collected = amm_global.total_fees_collected
+            .checked_add(fees.trade_fee)
+            .ok_or(AmmError::Overflow)?;
+
+        total_fees = total_fees
+            .checked_add(fees.trade_fee)
+            .ok_or(AmmError::Overflow)?;
+        hop_amount_in = amount_out;
+        hop_input_mint = output_vault.mint;
+    }
+
+    // Slippage protection on the final output only
+    require!(
+        hop_amount_in >= other_amount_threshold,
+        AmmError::SlippageExceeded
+    );
+
+    emit!(BatchSwapEvent {
+        user: ctx.accounts.user.key(),
+        input_mint: ctx.accounts.input_token_account.mint,
+        output_mint: ctx.accounts.output_token_account.mint,
+        input_amount: amount_in,
+        output_amount: hop_amount_in,
+        hops: hop_count as u8,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔀 Batch swap executed successfully");
+    msg!("Hops: {}", hop_count);
+    msg!("Input Amount: {} tokens", amount_in);
+    msg!("Output Amount: {} tokens", hop_amount_in);
+    msg!("Total Trade Fees: {} tokens", total_fees);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::swap::tests::{empty_tick_array, execute_swap, pool_at_tick};
+
+    #[test]
+    fn two_hop_route_pays_out_every_fee_share_per_hop() {
+        let mut first_pool = pool_at_tick(440, 1_000_000_000_000);
+        let mut second_pool = pool_at_tick(440, 1_000_000_000_000);
+        let first_tick_array = empty_tick_array(&first_pool);
+        let second_tick_array = empty_tick_array(&second_pool);
+
+        // Hop 1 sells token A into the first pool; hop 2 sells its token B output into the second
+        let first_reserve_a = first_pool.liquidity_reserve_a;
+        let first_reserve_b = first_pool.liquidity_reserve_b;
+        let first_fees = execute_swap(&mut first_pool, &first_tick_array, 1_000_000, true);
+        let hop_amount_out = first_reserve_b - first_pool.liquidity_reserve_b;
+
+        let second_reserve_a = second_pool.liquidity_reserve_a;
+        let second_fees = execute_swap(&mut second_pool, &second_tick_array, hop_amount_out, true);
+
+        for (pool, fees, reserve_before, amount_in) in [
+            (&first_pool, first_fees, first_reserve_a, 1_000_000),
+            (&second_pool, second_fees, second_reserve_a, hop_amount_out),
+        ] {
+            assert!(fees.platform_fee > 0 && fees.creator_fee > 0);
+            // Protocol, platform and creator shares left the vault; nothing accrues in the pool
+            assert_eq!(pool.protocol_fees_token_a, 0);
+            assert_eq!(
+                pool.liquidity_reserve_a - reserve_before,
+                amount_in - fees.protocol_fee - fees.platform_fee - fees.creator_fee
+            );
+        }
+    }
+}
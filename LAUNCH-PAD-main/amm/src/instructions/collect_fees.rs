@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{self, Token, TokenAccount, Transfer},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*};
+use crate::{constants::*, state::{AmmGlobal, Pool, Position, TickArray}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct CollectFees<'info> {
@@ -11,6 +11,7 @@ pub struct CollectFees<'info> {
     )]
     pub amm_global: Account<'info, AmmGlobal>,
 
+    #[account(mut)]
     pub pool: Account<'info, Pool>,
 
     #[account(
@@ -50,12 +51,91 @@ pub struct CollectFees<'info> {
     )]
     pub user_token_b: Account<'info, TokenAccount>,
 
+    /// Tick array for lower tick
+    #[account(
+        constraint = tick_array_lower.pool_id == pool.key(),
+        constraint = tick_array_lower.check_in_array(position.tick_lower)
+    )]
+    pub tick_array_lower: Account<'info, TickArray>,
+
+    /// Tick array for upper tick
+    #[account(
+        constraint = tick_array_upper.pool_id == pool.key(),
+        constraint = tick_array_upper.check_in_array(position.tick_upper)
+    )]
+    pub tick_array_upper: Account<'info, TickArray>,
+
     #[account(mut)]
     pub position_owner: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
+/// Same as `CollectFees`, but the owner may route fees to any token account of the right mint
+#[derive(Accounts)]
+pub struct CollectFeesToAccount<'info> {
+    #[account(
+        constraint = !amm_global.is_paused 
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        constraint = position.pool_id == pool.key() ,
+        constraint = position.owner == position_owner.key() 
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Pool vault for token A (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_a.key() == pool.vault_a 
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    /// Pool vault for token B (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_b.key() == pool.vault_b 
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    /// Destination token A account (any owner)
+    #[account(
+        mut,
+        constraint = destination_token_a.mint == vault_a.mint 
+    )]
+    pub destination_token_a: Account<'info, TokenAccount>,
+
+    /// Destination token B account (any owner)
+    #[account(
+        mut,
+        constraint = destination_token_b.mint == vault_b.mint 
+    )]
+    pub destination_token_b: Account<'info, TokenAccount>,
+
+    /// Tick array for lower tick
+    #[account(
+        constraint = tick_array_lower.pool_id == pool.key(),
+        constraint = tick_array_lower.check_in_array(position.tick_lower)
+    )]
+    pub tick_array_lower: Account<'info, TickArray>,
+
+    /// Tick array for upper tick
+    #[account(
+        constraint = tick_array_upper.pool_id == pool.key(),
+        constraint = tick_array_upper.check_in_array(position.tick_upper)
+    )]
+    pub tick_array_upper: Account<'info, TickArray>,
+
+    pub position_owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct CollectProtocolFees<'info> {
     #[account(
@@ -110,36 +190,152 @@ pub struct CollectProtocolFees<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Pools are passed in `remaining_accounts`, FEE_SWEEP_POOL_ACCOUNTS per pool:
+/// pool, vault A, vault B, platform token A, platform token B
+#[derive(Accounts)]
+pub struct CollectProtocolFeesBatch<'info> {
+    #[account(
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+            @ AmmError::InvalidAdminAuthority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for fee collection)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+            @ AmmError::InvalidMultisigAuthority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct SweepDust<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Pool vault for token A (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_a.key() == pool.vault_a
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    /// Pool vault for token B (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_b.key() == pool.vault_b
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    /// Platform wallet token A account receiving swept dust
+    #[account(
+        mut,
+        constraint = platform_token_a.owner == amm_global.platform_wallet
+            @ AmmError::PlatformWalletMismatch,
+        constraint = platform_token_a.mint == pool.mint_a
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub platform_token_a: Account<'info, TokenAccount>,
+
+    /// Platform wallet token B account receiving swept dust
+    #[account(
+        mut,
+        constraint = platform_token_b.owner == amm_global.platform_wallet
+            @ AmmError::PlatformWalletMismatch,
+        constraint = platform_token_b.mint == pool.mint_b
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub platform_token_b: Account<'info, TokenAccount>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+            @ AmmError::InvalidAdminAuthority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for dust sweeping)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+            @ AmmError::InvalidMultisigAuthority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    pub pool: Account<'info, Pool>,
+
+    /// Any token account held by the pool PDA
+    #[account(
+        mut,
+        constraint = token_account.owner == pool.key()
+            @ AmmError::InvalidAccountOwner
+    )]
+    pub token_account: Account<'info, TokenAccount>,
+
+    /// Platform wallet token account receiving rescued tokens
+    #[account(
+        mut,
+        constraint = platform_token_account.owner == amm_global.platform_wallet
+            @ AmmError::PlatformWalletMismatch,
+        constraint = platform_token_account.mint == token_account.mint
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub platform_token_account: Account<'info, TokenAccount>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+            @ AmmError::InvalidAdminAuthority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for token rescue)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+            @ AmmError::InvalidMultisigAuthority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 pub fn collect_fees(
     ctx: Context<CollectFees>,
     amount0_requested: u64,
     amount1_requested: u64,
 ) -> Result<()> {
-    let pool = &ctx.accounts.pool;
+    let pool = &mut ctx.accounts.pool;
     let position = &mut ctx.accounts.position;
     let clock = Clock::get()?;
 
-    // Calculate fees owed to this position
-    let (fees_owed_a, fees_owed_b) = calculate_fees_owed(pool, position)?;
-
-    // Determine actual amounts to collect
-    let amount0_to_collect = if amount0_requested == u64::MAX {
-        fees_owed_a
-    } else {
-        amount0_requested.min(fees_owed_a)
-    };
-
-    let amount1_to_collect = if amount1_requested == u64::MAX {
-        fees_owed_b
-    } else {
-        amount1_requested.min(fees_owed_b)
-    };
-
-    // Verify there are fees to collect
-    require!(
-        amount0_to_collect > 0 || amount1_to_collect > 0,
-        AmmError::InsufficientFees
-    );
+    // Settle owed fees and book the amounts being collected
+    let (amount0_to_collect, amount1_to_collect) = settle_fee_collection(
+        pool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        amount0_requested,
+        amount1_requested,
+    )?;
 
     // Use pool authority to transfer fees from vaults to user
     let pool_seeds = &[
@@ -178,15 +374,6 @@ pub fn collect_fees(
         token::transfer(transfer_b_ctx, amount1_to_collect)?;
     }
 
-    // Update position fees owed
-    position.fees_owed_a = position.fees_owed_a
-        .checked_sub(amount0_to_collect)
-        .ok_or(AmmError::Underflow)?;
-
-    position.fees_owed_b = position.fees_owed_b
-        .checked_sub(amount1_to_collect)
-        .ok_or(AmmError::Underflow)?;
-
     // Emit fees collected event
     emit!(FeesCollectedEvent {
         position_mint: position.mint,
@@ -205,6 +392,80 @@ pub fn collect_fees(
     Ok(())
 }
 
+pub fn collect_fees_to_account(
+    ctx: Context<CollectFeesToAccount>,
+    amount0_requested: u64,
+    amount1_requested: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    // Settle owed fees and book the amounts being collected
+    let (amount0_to_collect, amount1_to_collect) = settle_fee_collection(
+        pool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        amount0_requested,
+        amount1_requested,
+    )?;
+
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    // Transfer token A fees to the owner-chosen destination
+    if amount0_to_collect > 0 {
+        let transfer_a_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a.to_account_info(),
+                to: ctx.accounts.destination_token_a.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_a_ctx, amount0_to_collect)?;
+    }
+
+    // Transfer token B fees to the owner-chosen destination
+    if amount1_to_collect > 0 {
+        let transfer_b_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_b.to_account_info(),
+                to: ctx.accounts.destination_token_b.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_b_ctx, amount1_to_collect)?;
+    }
+
+    emit!(FeesCollectedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        amount0: amount0_to_collect,
+        amount1: amount1_to_collect,
+        collector: ctx.accounts.position_owner.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💰 Position fees collected to destination accounts");
+    msg!("Position: {}", position.mint);
+    msg!("Destination A: {}", ctx.accounts.destination_token_a.key());
+    msg!("Destination B: {}", ctx.accounts.destination_token_b.key());
+    msg!("Amount0 Collected: {} tokens", amount0_to_collect);
+    msg!("Amount1 Collected: {} tokens", amount1_to_collect);
+
+    Ok(())
+}
+
 pub fn collect_protocol_fees(
     ctx: Context<CollectProtocolFees>,
     amount0: u64,
@@ -217,9 +478,13 @@ pub fn collect_protocol_fees(
     // Verify multi-sig authorization for protocol fee collection
     amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
 
-    // Determine actual amounts to collect
-    let amount0_to_collect = amount0.min(pool.protocol_fees_token_a);
-    let amount1_to_collect = amount1.min(pool.protocol_fees_token_b);
+    // Determine actual amounts to collect, never more than the vaults hold beyond LP reserves and fund fees
+    let (collectable_a, collectable_b) = pool.collectable_protocol_fees(
+        ctx.accounts.vault_a.amount,
+        ctx.accounts.vault_b.amount,
+    );
+    let amount0_to_collect = amount0.min(collectable_a);
+    let amount1_to_collect = amount1.min(collectable_b);
 
     // Verify there are fees to collect
     require!(
@@ -300,10 +565,362 @@ pub fn collect_protocol_fees(
     Ok(())
 }
 
-fn calculate_fees_owed(_pool: &Pool, position: &Position) -> Result<(u64, u64)> {
-    // Simplified fee calculation
-    // In production, this would involve complex fee growth calculations
-    
-    // For now, return the fees already tracked in the position
+/// Sweep every listed pool's accrued protocol fees to platform wallet token accounts.
+/// Each transfer is capped at what the vault holds beyond LP reserves and fund fees;
+/// pools with nothing collectable are skipped.
+pub fn collect_protocol_fees_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CollectProtocolFeesBatch<'info>>,
+) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool_accounts = ctx.remaining_accounts;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization once for the whole sweep
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Validate batch shape; `is_multiple_of` needs a newer rustc than the SBF toolchain ships
+    #[allow(clippy::manual_is_multiple_of)]
+    let whole_pools = pool_accounts.len() % FEE_SWEEP_POOL_ACCOUNTS == 0;
+    require!(
+        !pool_accounts.is_empty() && whole_pools,
+        AmmError::InvalidFeeSweepAccounts
+    );
+    let pool_count = pool_accounts.len() / FEE_SWEEP_POOL_ACCOUNTS;
+    require!(pool_count <= MAX_FEE_SWEEP_POOLS, AmmError::TooManyFeeSweepPools);
+
+    let mut pools_swept: u8 = 0;
+
+    for accounts in pool_accounts.chunks(FEE_SWEEP_POOL_ACCOUNTS) {
+        let mut pool = Account::<Pool>::try_from(&accounts[0])?;
+        let vault_a = Account::<TokenAccount>::try_from(&accounts[1])?;
+        let vault_b = Account::<TokenAccount>::try_from(&accounts[2])?;
+        let platform_token_a = Account::<TokenAccount>::try_from(&accounts[3])?;
+        let platform_token_b = Account::<TokenAccount>::try_from(&accounts[4])?;
+
+        require!(
+            vault_a.key() == pool.vault_a && vault_b.key() == pool.vault_b,
+            AmmError::InvalidTokenAccount
+        );
+        require!(
+            platform_token_a.owner == amm_global.platform_wallet
+                && platform_token_b.owner == amm_global.platform_wallet,
+            AmmError::PlatformWalletMismatch
+        );
+        require!(
+            platform_token_a.mint == pool.mint_a && platform_token_b.mint == pool.mint_b,
+            AmmError::InvalidTokenAccount
+        );
+
+        // Never sweep more than the vaults hold beyond LP reserves and fund fees
+        let (amount0_to_collect, amount1_to_collect) =
+            pool.collectable_protocol_fees(vault_a.amount, vault_b.amount);
+
+        // Nothing accrued or nothing covered by the vaults; leave this pool for a later sweep
+        if amount0_to_collect == 0 && amount1_to_collect == 0 {
+            msg!("Pool {} has no collectable protocol fees; skipped", pool.key());
+            continue;
+        }
+
+        // Use pool authority to transfer protocol fees
+        let pool_seeds = &[
+            POOL_SEED,
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        if amount0_to_collect > 0 {
+            let transfer_a_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_a.to_account_info(),
+                    to: platform_token_a.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                pool_signer,
+            );
+            token::transfer(transfer_a_ctx, amount0_to_collect)?;
+        }
+
+        if amount1_to_collect > 0 {
+            let transfer_b_ctx = CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault_b.to_account_info(),
+                    to: platform_token_b.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                pool_signer,
+            );
+            token::transfer(transfer_b_ctx, amount1_to_collect)?;
+        }
+
+        // Anything the vaults could not cover stays accrued for a later sweep
+        pool.protocol_fees_token_a = pool.protocol_fees_token_a
+            .checked_sub(amount0_to_collect)
+            .ok_or(AmmError::Underflow)?;
+        pool.protocol_fees_token_b = pool.protocol_fees_token_b
+            .checked_sub(amount1_to_collect)
+            .ok_or(AmmError::Underflow)?;
+
+        // Persist before a later chunk could load the same pool again
+        pool.exit(&crate::ID)?;
+
+        emit!(ProtocolFeesCollectedEvent {
+            pool_id: pool.key(),
+            amount0: amount0_to_collect,
+            amount1: amount1_to_collect,
+            collector: ctx.accounts.admin_authority.key(),
+            destination: amm_global.platform_wallet,
+            timestamp: clock.unix_timestamp,
+        });
+
+        pools_swept += 1;
+    }
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "PROTOCOL_FEES_BATCH_COLLECTED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: amm_global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💰 Protocol fee sweep completed");
+    msg!("Pools Listed: {}", pool_count);
+    msg!("Pools Swept: {}", pools_swept);
+
+    Ok(())
+}
+
+pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for vault sweeping
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Only the balance above what the pool accounts for may leave the vaults
+    let (owned_a, owned_b) = pool.accounted_vault_amounts()?;
+    let dust_a = ctx.accounts.vault_a.amount.saturating_sub(owned_a);
+    let dust_b = ctx.accounts.vault_b.amount.saturating_sub(owned_b);
+
+    require!(dust_a > 0 || dust_b > 0, AmmError::InsufficientTokenBalance);
+
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    // Transfer token A dust to platform wallet
+    if dust_a > 0 {
+        let transfer_a_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a.to_account_info(),
+                to: ctx.accounts.platform_token_a.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_a_ctx, dust_a)?;
+    }
+
+    // Transfer token B dust to platform wallet
+    if dust_b > 0 {
+        let transfer_b_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_b.to_account_info(),
+                to: ctx.accounts.platform_token_b.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_b_ctx, dust_b)?;
+    }
+
+    pool.updated_at = clock.unix_timestamp;
+
+    // Emit dust swept event
+    emit!(DustSweptEvent {
+        pool_id: pool.key(),
+        amount0: dust_a,
+        amount1: dust_b,
+        accounted_amount0: owned_a,
+        accounted_amount1: owned_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "POOL_DUST_SWEPT".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: pool.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🧹 Pool vault dust swept to platform wallet");
+    msg!("Pool: {}", pool.key());
+    msg!("Amount0 Swept: {} tokens", dust_a);
+    msg!("Amount1 Swept: {} tokens", dust_b);
+
+    Ok(())
+}
+
+fn calculate_fees_owed(
+    pool: &Pool,
+    position: &mut Position,
+    tick_array_lower: &TickArray,
+    tick_array_upper: &TickArray,
+) -> Result<(u64, u64)> {
+    // Accrue fees earned inside the position's range since its last snapshot
+    let (fee_growth_inside_a, fee_growth_inside_b) = pool.fee_growth_inside(
+        tick_array_lower,
+        tick_array_upper,
+        position.tick_lower,
+        position.tick_upper,
+    )?;
+    position.update_fees(fee_growth_inside_a, fee_growth_inside_b)?;
+
     Ok((position.fees_owed_a, position.fees_owed_b))
-}
\ No newline at end of file
+}
+
+// Resolves requested amounts against owed fees and debits them from the position and pool reserves
+fn settle_fee_collection(
+    pool: &mut Pool,
+    position: &mut Position,
+    tick_array_lower: &TickArray,
+    tick_array_upper: &TickArray,
+    amount0_requested: u64,
+    amount1_requested: u64,
+) -> Result<(u64, u64)> {
+    // Calculate fees owed to this position
+    let (fees_owed_a, fees_owed_b) = calculate_fees_owed(
+        pool,
+        position,
+        tick_array_lower,
+        tick_array_upper,
+    )?;
+
+    // Determine actual amounts to collect
+    let amount0_to_collect = if amount0_requested == u64::MAX {
+        fees_owed_a
+    } else {
+        amount0_requested.min(fees_owed_a)
+    };
+
+    let amount1_to_collect = if amount1_requested == u64::MAX {
+        fees_owed_b
+    } else {
+        amount1_requested.min(fees_owed_b)
+    };
+
+    // Verify there are fees to collect
+    require!(
+        amount0_to_collect > 0 || amount1_to_collect > 0,
+        AmmError::InsufficientFees
+    );
+
+    // Update position fees owed
+    position.fees_owed_a = position.fees_owed_a
+        .checked_sub(amount0_to_collect)
+        .ok_or(AmmError::Underflow)?;
+
+    position.fees_owed_b = position.fees_owed_b
+        .checked_sub(amount1_to_collect)
+        .ok_or(AmmError::Underflow)?;
+
+    // Collected fees no longer back liquidity in the vaults
+    pool.liquidity_reserve_a = pool.liquidity_reserve_a
+        .checked_sub(amount0_to_collect)
+        .ok_or(AmmError::Underflow)?;
+
+    pool.liquidity_reserve_b = pool.liquidity_reserve_b
+        .checked_sub(amount1_to_collect)
+        .ok_or(AmmError::Underflow)?;
+
+    Ok((amount0_to_collect, amount1_to_collect))
+}
+
+pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &ctx.accounts.pool;
+    let token_account = &ctx.accounts.token_account;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for token rescue
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Reward vaults back emissions owed to positions and are never rescuable
+    require!(
+        pool.reward_infos.iter().all(|reward_info| reward_info.vault != token_account.key()),
+        AmmError::InvalidTokenAccount
+    );
+
+    // Pool vaults keep everything the pool accounts for; any other pool-held account is all excess
+    let (owned_a, owned_b) = pool.accounted_vault_amounts()?;
+    let accounted_amount = if token_account.key() == pool.vault_a {
+        owned_a
+    } else if token_account.key() == pool.vault_b {
+        owned_b
+    } else {
+        0
+    };
+    let rescue_amount = token_account.amount.saturating_sub(accounted_amount);
+
+    require!(rescue_amount > 0, AmmError::InsufficientTokenBalance);
+
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: token_account.to_account_info(),
+            to: ctx.accounts.platform_token_account.to_account_info(),
+            authority: pool.to_account_info(),
+        },
+        pool_signer,
+    );
+    token::transfer(transfer_ctx, rescue_amount)?;
+
+    // Emit tokens rescued event
+    emit!(TokensRescuedEvent {
+        pool_id: pool.key(),
+        token_account: token_account.key(),
+        mint: token_account.mint,
+        amount: rescue_amount,
+        accounted_amount,
+        destination: ctx.accounts.platform_token_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "TOKENS_RESCUED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: token_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🛟 Stuck tokens rescued to platform wallet");
+    msg!("Pool: {}", pool.key());
+    msg!("Token Account: {}", token_account.key());
+    msg!("Amount Rescued: {} tokens", rescue_amount);
+
+    Ok(())
+}
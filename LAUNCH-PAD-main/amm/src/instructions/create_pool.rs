@@ -3,7 +3,7 @@ use anchor_spl::{
     associated_token::AssociatedToken,
     token::{Mint, Token, TokenAccount},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*, math::MathUtil};
+use crate::{constants::*, state::{AmmGlobal, Pool, PoolFeeTier}, events::*, errors::*, math::MathUtil};
 
 #[derive(Accounts)]
 pub struct CreatePool<'info> {
@@ -87,6 +87,9 @@ pub fn create_pool(
     ctx: Context<CreatePool>,
     sqrt_price_x64: u128,
     tick_spacing: u16,
+    platform_fee_basis_points: Option<u16>,
+    creator_fee_basis_points: Option<u16>,
+    token_creator: Option<Pubkey>,
 ) -> Result<()> {
     let amm_global = &mut ctx.accounts.amm_global;
     let pool = &mut ctx.accounts.pool;
@@ -105,30 +108,17 @@ pub fn create_pool(
 
     // Validate sqrt price
     require!(
-        sqrt_price_x64 >= MIN_SQRT_PRICE_X64 && sqrt_price_x64 <= MAX_SQRT_PRICE_X64,
+        (MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64),
         AmmError::InvalidSqrtPrice
     );
 
-    // Collect pool creation fee
+    // Check the creator can cover the creation fee (charged once setup succeeds)
     let creation_fee = amm_global.create_pool_fee;
     require!(
         ctx.accounts.pool_creator.lamports() >= creation_fee,
         AmmError::PoolCreationFeeNotPaid
     );
 
-    // Transfer creation fee to platform wallet
-    let transfer_fee_ix = anchor_lang::system_program::Transfer {
-        from: ctx.accounts.pool_creator.to_account_info(),
-        to: ctx.accounts.platform_wallet.to_account_info(),
-    };
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_fee_ix,
-        ),
-        creation_fee,
-    )?;
-
     // Calculate initial tick from sqrt price
     let tick_current = MathUtil::sqrt_price_x64_to_tick(sqrt_price_x64)?;
 
@@ -157,6 +147,15 @@ pub fn create_pool(
     pool.total_volume_b = 0;
     pool.created_at = clock.unix_timestamp;
     pool.updated_at = clock.unix_timestamp;
+    pool.liquidity_reserve_a = 0;
+    pool.liquidity_reserve_b = 0;
+    pool.created_by = ctx.accounts.pool_creator.key();
+    pool.volume_fee_tiers = [PoolFeeTier::default(); POOL_FEE_TIER_COUNT];
+    pool.protocol_liquidity = 0;
+    pool.platform_fee_basis_points = platform_fee_basis_points.unwrap_or(PLATFORM_FEE_BASIS_POINTS);
+    pool.creator_fee_basis_points = creator_fee_basis_points.unwrap_or(CREATOR_FEE_BASIS_POINTS);
+    pool.token_creator = token_creator;
+    pool.validate_fee_split()?;
 
     // Initialize reward infos (empty initially)
     pool.reward_infos = [Default::default(); 3];
@@ -170,6 +169,19 @@ pub fn create_pool(
         .checked_add(creation_fee)
         .ok_or(AmmError::Overflow)?;
 
+    // Transfer creation fee to platform wallet only after all validation and state writes
+    let transfer_fee_ix = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.pool_creator.to_account_info(),
+        to: ctx.accounts.platform_wallet.to_account_info(),
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_fee_ix,
+        ),
+        creation_fee,
+    )?;
+
     // Emit pool creation event
     emit!(PoolCreatedEvent {
         pool_id: pool.key(),
@@ -183,7 +195,10 @@ pub fn create_pool(
         trade_fee_rate: pool.trade_fee_rate,
         protocol_fee_rate: pool.protocol_fee_rate,
         fund_fee_rate: pool.fund_fee_rate,
-        created_by: ctx.accounts.pool_creator.key(),
+        platform_fee_basis_points: pool.platform_fee_basis_points,
+        creator_fee_basis_points: pool.creator_fee_basis_points,
+        token_creator: pool.token_creator,
+        created_by: pool.created_by,
         timestamp: clock.unix_timestamp,
     });
 
@@ -217,6 +232,9 @@ pub fn create_pool(
     msg!("Tick Spacing: {}", tick_spacing);
     msg!("Trade Fee: {}%", pool.trade_fee_rate as f64 / 10000.0);
     msg!("Protocol Fee: {}%", pool.protocol_fee_rate as f64 / 10000.0);
+    if let Some(token_creator) = pool.token_creator {
+        msg!("Token Creator: {}", token_creator);
+    }
     msg!("Creation Fee Paid: {} SOL", creation_fee as f64 / 1_000_000_000.0);
 
     Ok(())
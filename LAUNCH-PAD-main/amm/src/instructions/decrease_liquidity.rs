@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{self, Token, TokenAccount, Transfer},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*, math::MathUtil};
+use crate::{constants::*, state::{require_not_expired, AmmGlobal, Pool, Position, TickArray}, events::*, errors::*, math::MathUtil};
 
 #[derive(Accounts)]
 pub struct DecreaseLiquidity<'info> {
@@ -13,8 +13,8 @@ pub struct DecreaseLiquidity<'info> {
 
     #[account(
         mut,
-        constraint = pool.status == POOL_STATUS_INITIALIZED || pool.status == POOL_STATUS_WITHDRAW_ONLY 
-            
+        constraint = pool.allows_withdrawals()
+            @ AmmError::PoolDisabled
     )]
     pub pool: Account<'info, Pool>,
 
@@ -82,11 +82,15 @@ pub fn decrease_liquidity(
     liquidity_delta: u128,
     amount0_min: u64,
     amount1_min: u64,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let position = &mut ctx.accounts.position;
     let clock = Clock::get()?;
 
+    // Reject transactions held past the caller's deadline
+    require_not_expired(deadline, clock.unix_timestamp)?;
+
     // Validate liquidity amount
     require!(liquidity_delta > 0, AmmError::InvalidLiquidityAmount);
     require!(liquidity_delta <= position.liquidity, AmmError::InsufficientLiquidity);
@@ -154,14 +158,35 @@ pub fn decrease_liquidity(
         token::transfer(transfer_b_ctx, amount1_to_withdraw)?;
     }
 
+    // Settle fees earned at the current liquidity before it changes
+    let (fee_growth_inside_a, fee_growth_inside_b) = pool.fee_growth_inside(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        position.tick_lower,
+        position.tick_upper,
+    )?;
+    position.update_fees(fee_growth_inside_a, fee_growth_inside_b)?;
+
     // Update position liquidity
     position.liquidity = position.liquidity
         .checked_sub(liquidity_delta)
         .ok_or(AmmError::Underflow)?;
 
+    // Release withdrawn tokens from the liquidity-backed vault balance
+    pool.liquidity_reserve_a = pool.liquidity_reserve_a
+        .checked_sub(amount0_to_withdraw)
+        .ok_or(AmmError::Underflow)?;
+    pool.liquidity_reserve_b = pool.liquidity_reserve_b
+        .checked_sub(amount1_to_withdraw)
+        .ok_or(AmmError::Underflow)?;
+
     // Update pool liquidity if position is in range
     if pool.tick_current >= position.tick_lower && pool.tick_current < position.tick_upper {
-        pool.liquidity = pool.liquidity
+        remove_in_range_liquidity(pool, position.owner, liquidity_delta, clock.unix_timestamp);
+    }
+
+    if ctx.accounts.amm_global.is_protocol_authority(&position.owner) {
+        pool.protocol_liquidity = pool.protocol_liquidity
             .checked_sub(liquidity_delta)
             .ok_or(AmmError::Underflow)?;
     }
@@ -170,6 +195,7 @@ pub fn decrease_liquidity(
     update_ticks_for_liquidity_decrease(
         &mut ctx.accounts.tick_array_lower,
         &mut ctx.accounts.tick_array_upper,
+        pool.tick_spacing,
         position.tick_lower,
         position.tick_upper,
         liquidity_delta,
@@ -188,6 +214,15 @@ pub fn decrease_liquidity(
         timestamp: clock.unix_timestamp,
     });
 
+    emit!(PoolLiquiditySnapshotEvent {
+        pool_id: pool.key(),
+        liquidity: pool.liquidity,
+        protocol_liquidity: pool.protocol_liquidity,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
     msg!("💧 Liquidity decreased successfully");
     msg!("Position: {}", position.mint);
     msg!("Liquidity Delta: {}", liquidity_delta);
@@ -198,7 +233,322 @@ pub fn decrease_liquidity(
     Ok(())
 }
 
-fn calculate_amounts_for_liquidity_withdrawal(
+#[derive(Accounts)]
+pub struct EmergencyDecreaseLiquidity<'info> {
+    /// Only usable while the AMM is paused
+    #[account(
+        constraint = amm_global.is_paused
+            @ AmmError::OperationsNotPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        constraint = position.pool_id == pool.key()
+            @ AmmError::InvalidPosition,
+        constraint = position.owner == position_owner.key()
+            @ AmmError::InvalidAccountOwner
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Pool vault for token A (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_a.key() == pool.vault_a
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    /// Pool vault for token B (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_b.key() == pool.vault_b
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+
+    /// User's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.owner == position_owner.key()
+            @ AmmError::InvalidAccountOwner,
+        constraint = user_token_a.mint == vault_a.mint
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub user_token_a: Account<'info, TokenAccount>,
+
+    /// User's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.owner == position_owner.key()
+            @ AmmError::InvalidAccountOwner,
+        constraint = user_token_b.mint == vault_b.mint
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub user_token_b: Account<'info, TokenAccount>,
+
+    /// Tick array for lower tick
+    #[account(
+        mut,
+        constraint = tick_array_lower.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_lower.check_in_array(position.tick_lower)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_lower: Account<'info, TickArray>,
+
+    /// Tick array for upper tick
+    #[account(
+        mut,
+        constraint = tick_array_upper.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_upper.check_in_array(position.tick_upper)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_upper: Account<'info, TickArray>,
+
+    #[account(mut)]
+    pub position_owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw liquidity while the AMM is paused. Pool status is ignored and
+/// fees are not settled, so owed fees on the withdrawn liquidity are forfeited.
+pub fn emergency_decrease_liquidity(
+    ctx: Context<EmergencyDecreaseLiquidity>,
+    liquidity_delta: u128,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    // Validate liquidity amount
+    require!(liquidity_delta > 0, AmmError::InvalidLiquidityAmount);
+    require!(liquidity_delta <= position.liquidity, AmmError::InsufficientLiquidity);
+
+    // Price is frozen while paused, so the position's share is taken at the current price
+    let sqrt_price_lower_x64 = MathUtil::tick_to_sqrt_price_x64(position.tick_lower)?;
+    let sqrt_price_upper_x64 = MathUtil::tick_to_sqrt_price_x64(position.tick_upper)?;
+
+    let (amount0_to_withdraw, amount1_to_withdraw) = calculate_amounts_for_liquidity_withdrawal(
+        pool.sqrt_price_x64,
+        sqrt_price_lower_x64,
+        sqrt_price_upper_x64,
+        liquidity_delta,
+    )?;
+
+    // Verify pool has sufficient tokens
+    require!(
+        ctx.accounts.vault_a.amount >= amount0_to_withdraw,
+        AmmError::InsufficientTokenBalance
+    );
+    require!(
+        ctx.accounts.vault_b.amount >= amount1_to_withdraw,
+        AmmError::InsufficientTokenBalance
+    );
+
+    // Use pool authority to transfer tokens from vaults to user
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    if amount0_to_withdraw > 0 {
+        let transfer_a_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a.to_account_info(),
+                to: ctx.accounts.user_token_a.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_a_ctx, amount0_to_withdraw)?;
+    }
+
+    if amount1_to_withdraw > 0 {
+        let transfer_b_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_b.to_account_info(),
+                to: ctx.accounts.user_token_b.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_b_ctx, amount1_to_withdraw)?;
+    }
+
+    // Keep liquidity bookkeeping consistent for when operations resume
+    position.liquidity = position.liquidity
+        .checked_sub(liquidity_delta)
+        .ok_or(AmmError::Underflow)?;
+
+    pool.liquidity_reserve_a = pool.liquidity_reserve_a
+        .checked_sub(amount0_to_withdraw)
+        .ok_or(AmmError::Underflow)?;
+    pool.liquidity_reserve_b = pool.liquidity_reserve_b
+        .checked_sub(amount1_to_withdraw)
+        .ok_or(AmmError::Underflow)?;
+
+    if pool.tick_current >= position.tick_lower && pool.tick_current < position.tick_upper {
+        remove_in_range_liquidity(pool, position.owner, liquidity_delta, clock.unix_timestamp);
+    }
+
+    if ctx.accounts.amm_global.is_protocol_authority(&position.owner) {
+        pool.protocol_liquidity = pool.protocol_liquidity
+            .checked_sub(liquidity_delta)
+            .ok_or(AmmError::Underflow)?;
+    }
+
+    update_ticks_for_liquidity_decrease(
+        &mut ctx.accounts.tick_array_lower,
+        &mut ctx.accounts.tick_array_upper,
+        pool.tick_spacing,
+        position.tick_lower,
+        position.tick_upper,
+        liquidity_delta,
+    )?;
+
+    pool.updated_at = clock.unix_timestamp;
+
+    emit!(EmergencyLiquidityWithdrawnEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        owner: ctx.accounts.position_owner.key(),
+        liquidity_delta,
+        amount0: amount0_to_withdraw,
+        amount1: amount1_to_withdraw,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PoolLiquiditySnapshotEvent {
+        pool_id: pool.key(),
+        liquidity: pool.liquidity,
+        protocol_liquidity: pool.protocol_liquidity,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🚨 Emergency liquidity withdrawal");
+    msg!("Position: {}", position.mint);
+    msg!("Liquidity Delta: {}", liquidity_delta);
+    msg!("Amount0 Withdrawn: {} tokens", amount0_to_withdraw);
+    msg!("Amount1 Withdrawn: {} tokens", amount1_to_withdraw);
+    msg!("Remaining Position Liquidity: {}", position.liquidity);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetPositionAmounts<'info> {
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        constraint = position.pool_id == pool.key()
+            @ AmmError::InvalidPosition
+    )]
+    pub position: Account<'info, Position>,
+
+    /// Tick array for lower tick
+    #[account(
+        constraint = tick_array_lower.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_lower.check_in_array(position.tick_lower)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_lower: Account<'info, TickArray>,
+
+    /// Tick array for upper tick
+    #[account(
+        constraint = tick_array_upper.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_upper.check_in_array(position.tick_upper)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_upper: Account<'info, TickArray>,
+}
+
+/// Report what withdrawing the whole position would return at the current price,
+/// plus uncollected fees, using the same math as `decrease_liquidity`.
+pub fn get_position_amounts(ctx: Context<GetPositionAmounts>) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    let sqrt_price_lower_x64 = MathUtil::tick_to_sqrt_price_x64(position.tick_lower)?;
+    let sqrt_price_upper_x64 = MathUtil::tick_to_sqrt_price_x64(position.tick_upper)?;
+
+    let (amount0, amount1) = if position.liquidity > 0 {
+        calculate_amounts_for_liquidity_withdrawal(
+            pool.sqrt_price_x64,
+            sqrt_price_lower_x64,
+            sqrt_price_upper_x64,
+            position.liquidity,
+        )?
+    } else {
+        (0, 0)
+    };
+
+    let (fee_growth_inside_a, fee_growth_inside_b) = pool.fee_growth_inside(
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        position.tick_lower,
+        position.tick_upper,
+    )?;
+    let (fees_owed_a, fees_owed_b) = position.pending_fees(fee_growth_inside_a, fee_growth_inside_b)?;
+
+    emit!(PositionAmountsEvent {
+        position_mint: position.mint,
+        pool_id: pool.key(),
+        liquidity: position.liquidity,
+        amount0,
+        amount1,
+        fees_owed_a,
+        fees_owed_b,
+        sqrt_price_x64: pool.sqrt_price_x64,
+        tick_current: pool.tick_current,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 Position amounts");
+    msg!("Position: {}", position.mint);
+    msg!("Amount0: {} tokens", amount0);
+    msg!("Amount1: {} tokens", amount1);
+    msg!("Fees Owed A: {} tokens", fees_owed_a);
+    msg!("Fees Owed B: {} tokens", fees_owed_b);
+
+    Ok(())
+}
+
+// Clamp the in-range decrement so accounting drift in pool.liquidity never blocks an LP's withdrawal
+pub(crate) fn remove_in_range_liquidity(pool: &mut Account<Pool>, owner: Pubkey, liquidity_delta: u128, timestamp: i64) {
+    if pool.liquidity < liquidity_delta {
+        emit!(SecurityAmmAlertEvent {
+            alert_type: "POOL_LIQUIDITY_DRIFT".to_string(),
+            details: format!(
+                "Pool {} active liquidity {} below in-range withdrawal {}; clamped to zero",
+                pool.key(),
+                pool.liquidity,
+                liquidity_delta
+            ),
+            authority: owner,
+            timestamp,
+        });
+        msg!("⚠️ Pool liquidity drift detected; clamping decrement to {}", pool.liquidity);
+    }
+    pool.liquidity = pool.liquidity.saturating_sub(liquidity_delta);
+}
+
+pub(crate) fn calculate_amounts_for_liquidity_withdrawal(
     sqrt_price_current_x64: u128,
     sqrt_price_lower_x64: u128,
     sqrt_price_upper_x64: u128,
@@ -238,47 +588,40 @@ fn calculate_amounts_for_liquidity_withdrawal(
     Ok((amount0, amount1))
 }
 
-fn update_ticks_for_liquidity_decrease(
+pub(crate) fn update_ticks_for_liquidity_decrease(
     tick_array_lower: &mut TickArray,
     tick_array_upper: &mut TickArray,
+    tick_spacing: u16,
     tick_lower: i32,
     tick_upper: i32,
     liquidity_delta: u128,
 ) -> Result<()> {
-    // Simplified tick update logic for liquidity decrease
-    
     // Update lower tick
-    let lower_index = ((tick_lower - tick_array_lower.start_tick_index) / 1) as usize;
-    if lower_index < tick_array_lower.ticks.len() {
-        let tick = &mut tick_array_lower.ticks[lower_index];
-        tick.liquidity_net = tick.liquidity_net
-            .checked_sub(liquidity_delta as i128)
-            .ok_or(AmmError::Underflow)?;
-        tick.liquidity_gross = tick.liquidity_gross
-            .checked_sub(liquidity_delta)
-            .ok_or(AmmError::Underflow)?;
-        
-        // If no liquidity left, mark as uninitialized
-        if tick.liquidity_gross == 0 {
-            tick.initialized = false;
-        }
+    let tick = tick_array_lower.get_tick_mut(tick_lower, tick_spacing)?;
+    tick.liquidity_net = tick.liquidity_net
+        .checked_sub(liquidity_delta as i128)
+        .ok_or(AmmError::Underflow)?;
+    tick.liquidity_gross = tick.liquidity_gross
+        .checked_sub(liquidity_delta)
+        .ok_or(AmmError::Underflow)?;
+    
+    // If no liquidity left, mark as uninitialized
+    if tick.liquidity_gross == 0 {
+        tick.initialized = false;
     }
 
     // Update upper tick
-    let upper_index = ((tick_upper - tick_array_upper.start_tick_index) / 1) as usize;
-    if upper_index < tick_array_upper.ticks.len() {
-        let tick = &mut tick_array_upper.ticks[upper_index];
-        tick.liquidity_net = tick.liquidity_net
-            .checked_add(liquidity_delta as i128)
-            .ok_or(AmmError::Overflow)?;
-        tick.liquidity_gross = tick.liquidity_gross
-            .checked_sub(liquidity_delta)
-            .ok_or(AmmError::Underflow)?;
-        
-        // If no liquidity left, mark as uninitialized
-        if tick.liquidity_gross == 0 {
-            tick.initialized = false;
-        }
+    let tick = tick_array_upper.get_tick_mut(tick_upper, tick_spacing)?;
+    tick.liquidity_net = tick.liquidity_net
+        .checked_add(liquidity_delta as i128)
+        .ok_or(AmmError::Overflow)?;
+    tick.liquidity_gross = tick.liquidity_gross
+        .checked_sub(liquidity_delta)
+        .ok_or(AmmError::Underflow)?;
+    
+    // If no liquidity left, mark as uninitialized
+    if tick.liquidity_gross == 0 {
+        tick.initialized = false;
     }
 
     Ok(())
@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{hash::hash, instruction::{AccountMeta, Instruction}, program::invoke};
+use anchor_spl::{
+    token::{self, Token, TokenAccount, Transfer},
+};
+use crate::{constants::*, state::{AmmGlobal, Pool, TickArray}, events::*, errors::*};
+use super::swap::{apply_swap_to_pool, calculate_swap, calculate_swap_fees, cross_ticks, pay_out_swap_fees};
+
+#[derive(Accounts)]
+pub struct FlashSwap<'info> {
+    #[account(
+        mut,
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(
+        mut,
+        constraint = pool.allows_swaps()
+            @ AmmError::PoolDisabled
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Pool vault the borrower repays into (multi-sig protected)
+    #[account(
+        mut,
+        constraint = (input_vault.key() == pool.vault_a || input_vault.key() == pool.vault_b)
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub input_vault: Account<'info, TokenAccount>,
+
+    /// Pool vault the borrowed tokens are sent from (multi-sig protected)
+    #[account(
+        mut,
+        constraint = (output_vault.key() == pool.vault_a || output_vault.key() == pool.vault_b)
+            @ AmmError::InvalidTokenAccount,
+        constraint = input_vault.key() != output_vault.key()
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub output_vault: Account<'info, TokenAccount>,
+
+    /// Receives the borrowed tokens before the callback runs
+    #[account(
+        mut,
+        constraint = output_token_account.mint == output_vault.mint
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub output_token_account: Account<'info, TokenAccount>,
+
+    /// Tick array for current price range
+    #[account(
+        mut,
+        constraint = tick_array.pool_id == pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array: Account<'info, TickArray>,
+
+    /// Program invoked with `remaining_accounts` to use and repay the borrowed tokens
+    /// CHECK: Must be executable and not this program; repayment is verified on the vault
+    #[account(
+        executable,
+        constraint = callback_program.key() != crate::ID
+            @ AmmError::InvalidFlashSwapCallback
+    )]
+    pub callback_program: UncheckedAccount<'info>,
+
+    /// Platform fee collection wallet (multi-sig controlled)
+    /// CHECK: Validated against global configuration
+    #[account(
+        mut,
+        constraint = platform_wallet.key() == amm_global.platform_wallet
+            @ AmmError::PlatformWalletMismatch
+    )]
+    pub platform_wallet: UncheckedAccount<'info>,
+
+    /// Creator fee collection wallet: the pool's token creator if recorded, else the global wallet
+    /// CHECK: Validated against pool and global configuration
+    #[account(
+        mut,
+        constraint = creator_wallet.key() == pool.creator_fee_wallet(amm_global.creator_wallet)
+            @ AmmError::CreatorWalletMismatch
+    )]
+    pub creator_wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Send the swap output first, invoke `callback_program` with every account in
+/// `remaining_accounts`, then require the input vault to have received the full
+/// input amount including the trade fee. The callback receives the Anchor sighash of
+/// `flash_swap_callback` followed by amount owed, amount sent and direction.
+pub fn flash_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+    amount: u64,
+    is_base_input: bool,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let tick_array = &mut ctx.accounts.tick_array;
+    let fee_multiplier_bps = ctx.accounts.amm_global.fee_multiplier_bps();
+    let clock = Clock::get()?;
+
+    // Validate input amount
+    require!(amount > 0, AmmError::InvalidTokenAmount);
+
+    // Determine if this is a zero-for-one swap (token A for token B); the vaults
+    // must be the pool's two sides, one each
+    let zero_for_one = pool
+        .swap_direction(ctx.accounts.input_vault.key(), ctx.accounts.output_vault.key())
+        .ok_or(AmmError::InvalidTokenAccount)?;
+    let sqrt_price_limit_x64 = if zero_for_one { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 };
+
+    // Remaining accounts belong to the callback, so pricing stays within one tick array
+    let computation = calculate_swap(
+        pool,
+        tick_array,
+        &[],
+        amount,
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+        fee_multiplier_bps,
+    )?;
+    let amount_in = computation.amount_in;
+    let amount_out = computation.amount_out;
+
+    // A flash swap is all or nothing
+    require!(!computation.is_partial_fill, AmmError::InsufficientLiquidity);
+    require!(amount_out > 0, AmmError::ZeroOutputAmount);
+    require!(
+        ctx.accounts.output_vault.amount >= amount_out,
+        AmmError::InsufficientTokenBalance
+    );
+    require!(
+        amount_out <= pool.output_liquidity_reserve(zero_for_one),
+        AmmError::InsufficientLiquidity
+    );
+
+    let fees = calculate_swap_fees(pool, amount_in, computation.trade_fee)?;
+    let input_vault_balance_before = ctx.accounts.input_vault.amount;
+
+    // Send the borrowed tokens out using pool authority
+    {
+        let pool_seeds = &[
+            POOL_SEED,
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let transfer_output_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.output_vault.to_account_info(),
+                to: ctx.accounts.output_token_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_output_ctx, amount_out)?;
+    }
+
+    // Hand control to the borrower's program
+    let mut data = hash(FLASH_SWAP_CALLBACK_PREIMAGE).to_bytes()[..8].to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&amount_out.to_le_bytes());
+    data.push(zero_for_one as u8);
+
+    let callback_ix = Instruction {
+        program_id: ctx.accounts.callback_program.key(),
+        accounts: ctx.remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+    let mut callback_accounts = ctx.remaining_accounts.to_vec();
+    callback_accounts.push(ctx.accounts.callback_program.to_account_info());
+    invoke(&callback_ix, &callback_accounts)?;
+
+    // Repayment must cover the whole input amount including the trade fee
+    ctx.accounts.input_vault.reload()?;
+    let amount_repaid = ctx.accounts.input_vault.amount
+        .checked_sub(input_vault_balance_before)
+        .ok_or(AmmError::FlashSwapNotRepaid)?;
+    require!(amount_repaid >= amount_in, AmmError::FlashSwapNotRepaid);
+
+    // Pay the platform and creator shares out of the repaid input, as swap does
+    pay_out_swap_fees(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.amm_global,
+        pool,
+        &ctx.accounts.input_vault.to_account_info(),
+        &ctx.accounts.platform_wallet.to_account_info(),
+        &ctx.accounts.creator_wallet.to_account_info(),
+        &fees,
+    )?;
+
+    cross_ticks(pool, tick_array, &[], &computation.crossed_ticks, zero_for_one, clock.unix_timestamp)?;
+    apply_swap_to_pool(
+        pool,
+        zero_for_one,
+        &computation,
+        &fees,
+        clock.unix_timestamp,
+    )?;
+
+    // Update global volume tracking
+    let amm_global = &mut ctx.accounts.amm_global;
+    amm_global.total_volume = amm_global.total_volume
+        .checked_add(amount_in)
+        .ok_or(AmmError::Overflow)?;
+    amm_global.total_fees_collected = amm_global.total_fees_collected
+        .checked_add(fees.trade_fee)
+        .ok_or(AmmError::Overflow)?;
+
+    emit!(FlashSwapEvent {
+        pool_id: pool.key(),
+        user: ctx.accounts.user.key(),
+        callback_program: ctx.accounts.callback_program.key(),
+        input_mint: ctx.accounts.input_vault.mint,
+        output_mint: ctx.accounts.output_vault.mint,
+        amount_owed: amount_in,
+        amount_repaid,
+        output_amount: amount_out,
+        fee_amount: fees.trade_fee,
+        sqrt_price_x64: pool.sqrt_price_x64,
+        tick_current: pool.tick_current,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("⚡ Flash swap executed successfully");
+    msg!("Output Amount: {} tokens", amount_out);
+    msg!("Amount Owed: {} tokens", amount_in);
+    msg!("Amount Repaid: {} tokens", amount_repaid);
+    msg!("Trade Fee: {} tokens", fees.trade_fee);
+    msg!("New Price: {}", pool.sqrt_price_x64);
+    msg!("New Tick: {}", pool.tick_current);
+
+    Ok(())
+}
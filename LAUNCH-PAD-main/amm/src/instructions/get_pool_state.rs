@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::{constants::*, state::{AmmGlobal, Pool, PoolStateView}, errors::*, math::MathUtil};
+use super::swap::emit_fee_growth_snapshot;
+
+#[derive(Accounts)]
+pub struct GetPoolState<'info> {
+    pub pool: Account<'info, Pool>,
+
+    /// Pool vault for token A
+    #[account(
+        constraint = vault_a.key() == pool.vault_a
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_a: Account<'info, TokenAccount>,
+
+    /// Pool vault for token B
+    #[account(
+        constraint = vault_b.key() == pool.vault_b
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub vault_b: Account<'info, TokenAccount>,
+}
+
+/// Consolidated pool snapshot for indexers, returned via return data so clients
+/// do not depend on the raw `Pool` account layout.
+pub fn get_pool_state(ctx: Context<GetPoolState>) -> Result<PoolStateView> {
+    let pool = &ctx.accounts.pool;
+    let vault_a_balance = ctx.accounts.vault_a.amount;
+    let vault_b_balance = ctx.accounts.vault_b.amount;
+
+    // Token B per token A, Q64.64
+    let price_x64 = MathUtil::mul_div_floor(pool.sqrt_price_x64, pool.sqrt_price_x64, Q64)?;
+
+    // Value both vaults in token A at the current price
+    let vault_b_in_a = if price_x64 > 0 {
+        MathUtil::mul_div_floor(vault_b_balance as u128, Q64, price_x64)?
+    } else {
+        0
+    };
+    let tvl_in_token_a = (vault_a_balance as u128)
+        .checked_add(vault_b_in_a)
+        .ok_or(AmmError::Overflow)?;
+
+    Ok(PoolStateView {
+        version: POOL_STATE_VIEW_VERSION,
+        pool_id: pool.key(),
+        status: pool.status,
+        sqrt_price_x64: pool.sqrt_price_x64,
+        price_x64,
+        tick_current: pool.tick_current,
+        liquidity: pool.liquidity,
+        vault_a_balance,
+        vault_b_balance,
+        tvl_in_token_a,
+        trade_fee_rate: pool.trade_fee_rate,
+        protocol_fees_token_a: pool.protocol_fees_token_a,
+        protocol_fees_token_b: pool.protocol_fees_token_b,
+        fund_fees_token_a: pool.fund_fees_token_a,
+        fund_fees_token_b: pool.fund_fees_token_b,
+        total_volume_a: pool.total_volume_a,
+        total_volume_b: pool.total_volume_b,
+        updated_at: pool.updated_at,
+    })
+}
+
+#[derive(Accounts)]
+pub struct PokeFeeGrowthSnapshot<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+}
+
+/// Emit a FeeGrowthSnapshotEvent for pools that have not swapped since the last
+/// interval. Permissionless; rejected until the snapshot interval has elapsed.
+pub fn poke_fee_growth_snapshot(ctx: Context<PokeFeeGrowthSnapshot>) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    require!(
+        pool.is_fee_snapshot_due(ctx.accounts.amm_global.fee_snapshot_interval(), clock.unix_timestamp),
+        AmmError::FeeSnapshotTooSoon
+    );
+
+    emit_fee_growth_snapshot(pool, clock.unix_timestamp);
+
+    Ok(())
+}
@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::{
     token::{self, Token, TokenAccount, Transfer},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*, math::MathUtil};
+use crate::{state::{require_not_expired, AmmGlobal, Pool, Position, TickArray, Tick}, events::*, errors::*, math::MathUtil};
 
 #[derive(Accounts)]
 pub struct IncreaseLiquidity<'info> {
@@ -13,7 +13,8 @@ pub struct IncreaseLiquidity<'info> {
 
     #[account(
         mut,
-        constraint = pool.status == POOL_STATUS_INITIALIZED
+        constraint = pool.allows_deposits()
+            @ AmmError::PoolDisabled
     )]
     pub pool: Account<'info, Pool>,
 
@@ -81,14 +82,85 @@ pub fn increase_liquidity(
     liquidity_delta: u128,
     amount0_max: u64,
     amount1_max: u64,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let pool = &mut ctx.accounts.pool;
     let position = &mut ctx.accounts.position;
     let clock = Clock::get()?;
 
+    // Reject transactions held past the caller's deadline
+    require_not_expired(deadline, clock.unix_timestamp)?;
+
+    let (amount0_required, amount1_required) = apply_liquidity_increase(
+        &ctx.accounts.amm_global,
+        pool,
+        position,
+        &mut ctx.accounts.tick_array_lower,
+        &mut ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        amount0_max,
+        amount1_max,
+        clock.unix_timestamp,
+    )?;
+
+    transfer_liquidity_deposit(
+        &ctx.accounts.token_program,
+        &ctx.accounts.position_owner,
+        &ctx.accounts.user_token_a,
+        &ctx.accounts.user_token_b,
+        &ctx.accounts.vault_a,
+        &ctx.accounts.vault_b,
+        amount0_required,
+        amount1_required,
+    )?;
+
+    // Emit liquidity increased event
+    emit!(LiquidityIncreasedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        liquidity_delta,
+        amount0: amount0_required,
+        amount1: amount1_required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PoolLiquiditySnapshotEvent {
+        pool_id: pool.key(),
+        liquidity: pool.liquidity,
+        protocol_liquidity: pool.protocol_liquidity,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💧 Liquidity increased successfully");
+    msg!("Position: {}", position.mint);
+    msg!("Liquidity Delta: {}", liquidity_delta);
+    msg!("Amount0 Deposited: {} tokens", amount0_required);
+    msg!("Amount1 Deposited: {} tokens", amount1_required);
+    msg!("New Position Liquidity: {}", position.liquidity);
+
+    Ok(())
+}
+
+/// Price a liquidity increase and apply it to ticks, position and pool.
+/// Returns the token amounts the owner must deposit.
+#[allow(clippy::too_many_arguments)] // Shared by several instructions, each passing its own accounts
+pub(crate) fn apply_liquidity_increase(
+    amm_global: &AmmGlobal,
+    pool: &mut Pool,
+    position: &mut Position,
+    tick_array_lower: &mut TickArray,
+    tick_array_upper: &mut TickArray,
+    liquidity_delta: u128,
+    amount0_max: u64,
+    amount1_max: u64,
+    timestamp: i64,
+) -> Result<(u64, u64)> {
     // Validate liquidity amount
     require!(liquidity_delta > 0, AmmError::InvalidLiquidityAmount);
-    require!(amount0_max > 0 && amount1_max > 0, AmmError::InvalidTokenAmount);
+    // Out-of-range positions only need one side, so a single nonzero maximum is enough
+    require!(amount0_max > 0 || amount1_max > 0, AmmError::InvalidTokenAmount);
 
     // Calculate required token amounts
     let sqrt_price_lower_x64 = MathUtil::tick_to_sqrt_price_x64(position.tick_lower)?;
@@ -102,86 +174,115 @@ pub fn increase_liquidity(
         liquidity_delta,
     )?;
 
+    // Liquidity too small to round to any deposit would be free
+    require!(
+        amount0_required > 0 || amount1_required > 0,
+        AmmError::InvalidLiquidityAmount
+    );
+
     // Check slippage protection
     require!(amount0_required <= amount0_max, AmmError::SlippageExceeded);
     require!(amount1_required <= amount1_max, AmmError::SlippageExceeded);
 
+    // Update tick arrays (initializes fee growth outside on first use)
+    update_ticks_for_liquidity_change(
+        tick_array_lower,
+        tick_array_upper,
+        pool,
+        position.tick_lower,
+        position.tick_upper,
+        liquidity_delta as i128, // Positive for increase
+    )?;
+
+    // Settle fees earned at the previous liquidity before it changes
+    let (fee_growth_inside_a, fee_growth_inside_b) = pool.fee_growth_inside(
+        tick_array_lower,
+        tick_array_upper,
+        position.tick_lower,
+        position.tick_upper,
+    )?;
+    position.update_fees(fee_growth_inside_a, fee_growth_inside_b)?;
+
+    // Update position liquidity
+    position.liquidity = position.liquidity
+        .checked_add(liquidity_delta)
+        .ok_or(AmmError::Overflow)?;
+
+    // Track deposited tokens as liquidity-backed vault balance
+    pool.liquidity_reserve_a = pool.liquidity_reserve_a
+        .checked_add(amount0_required)
+        .ok_or(AmmError::Overflow)?;
+    pool.liquidity_reserve_b = pool.liquidity_reserve_b
+        .checked_add(amount1_required)
+        .ok_or(AmmError::Overflow)?;
+
+    // Update pool liquidity if position is in range
+    if pool.tick_current >= position.tick_lower && pool.tick_current < position.tick_upper {
+        pool.liquidity = pool.liquidity
+            .checked_add(liquidity_delta)
+            .ok_or(AmmError::Overflow)?;
+    }
+
+    // Track treasury-owned depth separately from user liquidity
+    if amm_global.is_protocol_authority(&position.owner) {
+        pool.protocol_liquidity = pool.protocol_liquidity
+            .checked_add(liquidity_delta)
+            .ok_or(AmmError::Overflow)?;
+    }
+
+    // Update pool timestamp
+    pool.updated_at = timestamp;
+
+    Ok((amount0_required, amount1_required))
+}
+
+/// Move a priced deposit from the owner's token accounts into the pool vaults
+#[allow(clippy::too_many_arguments)] // Shared by several instructions, each passing its own accounts
+pub(crate) fn transfer_liquidity_deposit<'info>(
+    token_program: &Program<'info, Token>,
+    position_owner: &Signer<'info>,
+    user_token_a: &Account<'info, TokenAccount>,
+    user_token_b: &Account<'info, TokenAccount>,
+    vault_a: &Account<'info, TokenAccount>,
+    vault_b: &Account<'info, TokenAccount>,
+    amount0: u64,
+    amount1: u64,
+) -> Result<()> {
     // Verify user has sufficient tokens
     require!(
-        ctx.accounts.user_token_a.amount >= amount0_required,
+        user_token_a.amount >= amount0,
         AmmError::InsufficientTokenBalance
     );
     require!(
-        ctx.accounts.user_token_b.amount >= amount1_required,
+        user_token_b.amount >= amount1,
         AmmError::InsufficientTokenBalance
     );
 
     // Transfer tokens from user to pool vaults
-    if amount0_required > 0 {
+    if amount0 > 0 {
         let transfer_a_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.user_token_a.to_account_info(),
-                to: ctx.accounts.vault_a.to_account_info(),
-                authority: ctx.accounts.position_owner.to_account_info(),
+                from: user_token_a.to_account_info(),
+                to: vault_a.to_account_info(),
+                authority: position_owner.to_account_info(),
             },
         );
-        token::transfer(transfer_a_ctx, amount0_required)?;
+        token::transfer(transfer_a_ctx, amount0)?;
     }
 
-    if amount1_required > 0 {
+    if amount1 > 0 {
         let transfer_b_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
+            token_program.to_account_info(),
             Transfer {
-                from: ctx.accounts.user_token_b.to_account_info(),
-                to: ctx.accounts.vault_b.to_account_info(),
-                authority: ctx.accounts.position_owner.to_account_info(),
+                from: user_token_b.to_account_info(),
+                to: vault_b.to_account_info(),
+                authority: position_owner.to_account_info(),
             },
         );
-        token::transfer(transfer_b_ctx, amount1_required)?;
-    }
-
-    // Update position liquidity
-    position.liquidity = position.liquidity
-        .checked_add(liquidity_delta)
-        .ok_or(AmmError::Overflow)?;
-
-    // Update pool liquidity if position is in range
-    if pool.tick_current >= position.tick_lower && pool.tick_current < position.tick_upper {
-        pool.liquidity = pool.liquidity
-            .checked_add(liquidity_delta)
-            .ok_or(AmmError::Overflow)?;
+        token::transfer(transfer_b_ctx, amount1)?;
     }
 
-    // Update tick arrays (simplified - would involve complex tick management)
-    update_ticks_for_liquidity_change(
-        &mut ctx.accounts.tick_array_lower,
-        &mut ctx.accounts.tick_array_upper,
-        position.tick_lower,
-        position.tick_upper,
-        liquidity_delta as i128, // Positive for increase
-    )?;
-
-    // Update pool timestamp
-    pool.updated_at = clock.unix_timestamp;
-
-    // Emit liquidity increased event
-    emit!(LiquidityIncreasedEvent {
-        position_mint: position.mint,
-        pool_id: position.pool_id,
-        liquidity_delta,
-        amount0: amount0_required,
-        amount1: amount1_required,
-        timestamp: clock.unix_timestamp,
-    });
-
-    msg!("💧 Liquidity increased successfully");
-    msg!("Position: {}", position.mint);
-    msg!("Liquidity Delta: {}", liquidity_delta);
-    msg!("Amount0 Deposited: {} tokens", amount0_required);
-    msg!("Amount1 Deposited: {} tokens", amount1_required);
-    msg!("New Position Liquidity: {}", position.liquidity);
-
     Ok(())
 }
 
@@ -228,38 +329,50 @@ fn calculate_amounts_for_liquidity(
 fn update_ticks_for_liquidity_change(
     tick_array_lower: &mut TickArray,
     tick_array_upper: &mut TickArray,
+    pool: &Pool,
     tick_lower: i32,
     tick_upper: i32,
     liquidity_delta: i128,
 ) -> Result<()> {
-    // Simplified tick update logic
-    // In production, this would involve complex tick array management
-    
     // Update lower tick
-    let lower_index = ((tick_lower - tick_array_lower.start_tick_index) / 1) as usize;
-    if lower_index < tick_array_lower.ticks.len() {
-        let tick = &mut tick_array_lower.ticks[lower_index];
-        tick.liquidity_net = tick.liquidity_net
-            .checked_add(liquidity_delta)
-            .ok_or(AmmError::Overflow)?;
-        tick.liquidity_gross = tick.liquidity_gross
-            .checked_add(liquidity_delta.abs() as u128)
-            .ok_or(AmmError::Overflow)?;
-        tick.initialized = true;
-    }
+    let tick = tick_array_lower.get_tick_mut(tick_lower, pool.tick_spacing)?;
+    initialize_tick_fee_growth(tick, tick_lower, pool);
+    tick.liquidity_net = tick.liquidity_net
+        .checked_add(liquidity_delta)
+        .ok_or(AmmError::Overflow)?;
+    tick.liquidity_gross = tick.liquidity_gross
+        .checked_add(liquidity_delta.unsigned_abs())
+        .ok_or(AmmError::Overflow)?;
+    tick.initialized = true;
 
     // Update upper tick
-    let upper_index = ((tick_upper - tick_array_upper.start_tick_index) / 1) as usize;
-    if upper_index < tick_array_upper.ticks.len() {
-        let tick = &mut tick_array_upper.ticks[upper_index];
-        tick.liquidity_net = tick.liquidity_net
-            .checked_sub(liquidity_delta)
-            .ok_or(AmmError::Underflow)?;
-        tick.liquidity_gross = tick.liquidity_gross
-            .checked_add(liquidity_delta.abs() as u128)
-            .ok_or(AmmError::Overflow)?;
-        tick.initialized = true;
-    }
+    let tick = tick_array_upper.get_tick_mut(tick_upper, pool.tick_spacing)?;
+    initialize_tick_fee_growth(tick, tick_upper, pool);
+    tick.liquidity_net = tick.liquidity_net
+        .checked_sub(liquidity_delta)
+        .ok_or(AmmError::Underflow)?;
+    tick.liquidity_gross = tick.liquidity_gross
+        .checked_add(liquidity_delta.unsigned_abs())
+        .ok_or(AmmError::Overflow)?;
+    tick.initialized = true;
 
     Ok(())
+}
+
+// By convention all fee growth so far happened below a newly initialized tick at or below the current tick
+fn initialize_tick_fee_growth(tick: &mut Tick, tick_index: i32, pool: &Pool) {
+    if tick.initialized {
+        return;
+    }
+    if tick_index <= pool.tick_current {
+        tick.fee_growth_outside_a_x64 = pool.fee_growth_global_a_x64;
+        tick.fee_growth_outside_b_x64 = pool.fee_growth_global_b_x64;
+        for (outside, reward_info) in tick.reward_growth_outside.iter_mut().zip(pool.reward_infos.iter()) {
+            *outside = reward_info.growth_global_x64;
+        }
+    } else {
+        tick.fee_growth_outside_a_x64 = 0;
+        tick.fee_growth_outside_b_x64 = 0;
+        tick.reward_growth_outside = [0; 3];
+    }
 }
\ No newline at end of file
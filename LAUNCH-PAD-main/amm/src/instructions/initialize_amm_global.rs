@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, state::{AmmGlobal, Pool, Position, TickArray, PersonalPosition, PoolReward, ObservationState}, events::*, errors::*};
+use crate::{constants::*, state::{AmmGlobal, AmmGlobalConfig}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct InitializeAmmGlobal<'info> {
@@ -45,10 +45,13 @@ pub struct InitializeAmmGlobal<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn initialize_amm_global(ctx: Context<InitializeAmmGlobal>) -> Result<()> {
+pub fn initialize_amm_global(ctx: Context<InitializeAmmGlobal>, config: AmmGlobalConfig) -> Result<()> {
     let amm_global = &mut ctx.accounts.amm_global;
     let clock = Clock::get()?;
 
+    // Fee settings are per deployment; authority wallets stay pinned to the compiled constants
+    config.validate()?;
+
     // Set multi-sig authorities
     amm_global.admin_authority = ctx.accounts.admin_authority.key();
     amm_global.multisig_authority = ctx.accounts.multisig_authority.key();
@@ -58,17 +61,20 @@ pub fn initialize_amm_global(ctx: Context<InitializeAmmGlobal>) -> Result<()> {
     amm_global.creator_wallet = ctx.accounts.creator_wallet.key();
 
     // Initialize fee settings
-    amm_global.protocol_fee_rate = DEFAULT_PROTOCOL_FEE_RATE;
-    amm_global.fund_fee_rate = DEFAULT_FUND_FEE_RATE;
-    amm_global.default_trade_fee_rate = DEFAULT_TRADE_FEE_RATE;
-    amm_global.create_pool_fee = 1_000_000_000; // 1 SOL
+    amm_global.protocol_fee_rate = config.protocol_fee_rate;
+    amm_global.fund_fee_rate = config.fund_fee_rate;
+    amm_global.default_trade_fee_rate = config.default_trade_fee_rate;
+    amm_global.create_pool_fee = config.create_pool_fee;
 
     // Initialize flags and counters
     amm_global.is_paused = false;
     amm_global.total_pools = 0;
     amm_global.total_volume = 0;
     amm_global.total_fees_collected = 0;
-    amm_global.version = 1;
+    amm_global.version = AMM_GLOBAL_VERSION;
+    amm_global.min_tick_range_multiple = 0;
+    amm_global.global_fee_multiplier_bps = DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS;
+    amm_global.fee_snapshot_interval_seconds = DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS;
 
     // Emit initialization event
     emit!(AmmGlobalInitializedEvent {
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*};
+use crate::{constants::*, state::{AmmGlobal, Pool, TickArray, Tick}, events::*, errors::*};
 
 #[derive(Accounts)]
 #[instruction(start_tick_index: i32)]
@@ -42,7 +42,7 @@ pub fn initialize_tick_array(
 
     // Validate tick index bounds
     require!(
-        start_tick_index >= MIN_TICK && start_tick_index <= MAX_TICK,
+        (MIN_TICK..=MAX_TICK).contains(&start_tick_index),
         AmmError::TickOutOfBounds
     );
 
@@ -70,4 +70,188 @@ pub fn initialize_tick_array(
     msg!("End Tick Index: {}", start_tick_index + TICK_ARRAY_SIZE - 1);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct RecomputeTickArray<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        constraint = tick_array.pool_id == pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array: Account<'info, TickArray>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for tick array recovery)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+/// Recovery tool: clears ticks with no gross liquidity and rebuilds the initialized count.
+/// `remaining_accounts` must hold every other initialized tick array of the pool, so the
+/// repaired net liquidity across all arrays can be checked to sum to zero.
+pub fn recompute_tick_array<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RecomputeTickArray<'info>>,
+) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &ctx.accounts.pool;
+    let tick_array = &mut ctx.accounts.tick_array;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for tick array recovery
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Ticks without gross liquidity cannot carry net liquidity or growth snapshots
+    let mut ticks_cleared: u32 = 0;
+    for tick in tick_array.ticks.iter_mut() {
+        if tick.liquidity_gross == 0 && (tick.initialized || tick.liquidity_net != 0) {
+            *tick = Tick::default();
+            ticks_cleared += 1;
+        }
+    }
+
+    let previous_initialized_tick_count = tick_array.initialized_tick_count;
+    tick_array.initialized_tick_count = tick_array.ticks
+        .iter()
+        .filter(|tick| tick.initialized)
+        .count() as u32;
+
+    // Every position adds +L at its lower tick and -L at its upper tick, so net sums to zero
+    let mut total_liquidity_net = tick_array.ticks
+        .iter()
+        .try_fold(0i128, |sum, tick| sum.checked_add(tick.liquidity_net))
+        .ok_or(AmmError::Overflow)?;
+    let mut seen_arrays = vec![tick_array.key()];
+    for account_info in ctx.remaining_accounts.iter() {
+        let other_array = Account::<TickArray>::try_from(account_info)?;
+        require!(
+            other_array.pool_id == pool.key() && !seen_arrays.contains(&other_array.key()),
+            AmmError::InvalidTickArray
+        );
+        seen_arrays.push(other_array.key());
+        total_liquidity_net = other_array.ticks
+            .iter()
+            .try_fold(total_liquidity_net, |sum, tick| sum.checked_add(tick.liquidity_net))
+            .ok_or(AmmError::Overflow)?;
+    }
+    require!(total_liquidity_net == 0, AmmError::TickLiquidityImbalance);
+
+    emit!(TickArrayRecomputedEvent {
+        pool_id: pool.key(),
+        tick_array: tick_array.key(),
+        previous_initialized_tick_count,
+        initialized_tick_count: tick_array.initialized_tick_count,
+        ticks_cleared,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "TICK_ARRAY_RECOMPUTED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: tick_array.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🛠️ Tick array accounting recomputed");
+    msg!("Tick Array: {}", tick_array.key());
+    msg!("Ticks Cleared: {}", ticks_cleared);
+    msg!("Initialized Ticks: {} -> {}", previous_initialized_tick_count, tick_array.initialized_tick_count);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseTickArray<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    pub pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        close = rent_recipient,
+        constraint = tick_array.pool_id == pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array: Account<'info, TickArray>,
+
+    /// Receives the reclaimed rent; normally the wallet that initialized the array
+    /// CHECK: Only receives lamports
+    #[account(mut)]
+    pub rent_recipient: UncheckedAccount<'info>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for tick array cleanup)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+/// Close a tick array with no initialized ticks and return its rent. The array covering
+/// the pool's current tick is kept so swaps always have it; any array can be re-created
+/// later with initialize_tick_array.
+pub fn close_tick_array(ctx: Context<CloseTickArray>) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &ctx.accounts.pool;
+    let tick_array = &ctx.accounts.tick_array;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for tick array cleanup
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Check every tick rather than trusting the counter alone
+    require!(
+        tick_array.initialized_tick_count == 0
+            && tick_array.ticks.iter().all(|tick| !tick.initialized && tick.liquidity_gross == 0),
+        AmmError::TickArrayNotEmpty
+    );
+    require!(
+        !tick_array.contains_tick(pool.tick_current, pool.tick_spacing),
+        AmmError::ActiveTickArray
+    );
+
+    let rent_reclaimed = tick_array.to_account_info().lamports();
+
+    emit!(TickArrayClosedEvent {
+        pool_id: pool.key(),
+        tick_array: tick_array.key(),
+        start_tick_index: tick_array.start_tick_index,
+        rent_recipient: ctx.accounts.rent_recipient.key(),
+        rent_reclaimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigAmmOperationEvent {
+        operation: "TICK_ARRAY_CLOSED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: tick_array.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🧹 Empty tick array closed");
+    msg!("Tick Array: {}", tick_array.key());
+    msg!("Start Tick Index: {}", tick_array.start_tick_index);
+    msg!("Rent Reclaimed: {} lamports", rent_reclaimed);
+
+    Ok(())
+}
@@ -1,21 +1,37 @@
 pub mod initialize_amm_global;
 pub mod create_pool;
 pub mod open_position;
+pub mod open_position_with_liquidity;
 pub mod increase_liquidity;
 pub mod decrease_liquidity;
+pub mod rebalance_position;
 pub mod swap;
+pub mod batch_swap;
+pub mod flash_swap;
+pub mod swap_commitment;
+pub mod swap_router_exact_out;
 pub mod collect_fees;
 pub mod admin_operations;
 pub mod initialize_tick_array;
 pub mod reward_operations;
+pub mod get_pool_state;
+pub mod user_operations;
 
 pub use initialize_amm_global::*;
 pub use create_pool::*;
 pub use open_position::*;
+pub use open_position_with_liquidity::*;
 pub use increase_liquidity::*;
 pub use decrease_liquidity::*;
+pub use rebalance_position::*;
 pub use swap::*;
+pub use batch_swap::*;
+pub use flash_swap::*;
+pub use swap_commitment::*;
+pub use swap_router_exact_out::*;
 pub use collect_fees::*;
 pub use admin_operations::*;
 pub use initialize_tick_array::*;
-pub use reward_operations::*;
\ No newline at end of file
+pub use reward_operations::*;
+pub use get_pool_state::*;
+pub use user_operations::*;
\ No newline at end of file
@@ -8,9 +8,10 @@ use anchor_spl::{
         CreateMetadataAccountsV3, Metadata,
     },
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*};
+use crate::{constants::*, state::{AmmGlobal, Pool, Position, TickArray, PersonalPosition}, events::*, errors::*};
 
 #[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
 pub struct OpenPosition<'info> {
     #[account(
         constraint = !amm_global.is_paused 
@@ -18,7 +19,8 @@ pub struct OpenPosition<'info> {
     pub amm_global: Account<'info, AmmGlobal>,
 
     #[account(
-        constraint = pool.status == POOL_STATUS_INITIALIZED 
+        constraint = pool.allows_deposits()
+            @ AmmError::PoolDisabled
     )]
     pub pool: Account<'info, Pool>,
 
@@ -73,6 +75,30 @@ pub struct OpenPosition<'info> {
     )]
     pub personal_position: Account<'info, PersonalPosition>,
 
+    /// Tick array covering tick_lower (must already be initialized)
+    /// CHECK: PDA derived from tick_lower; initialization checked in handler
+    #[account(
+        seeds = [
+            TICK_ARRAY_SEED,
+            pool.key().as_ref(),
+            &TickArray::start_index_for(tick_lower, pool.tick_spacing).to_le_bytes()
+        ],
+        bump
+    )]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// Tick array covering tick_upper (must already be initialized)
+    /// CHECK: PDA derived from tick_upper; initialization checked in handler
+    #[account(
+        seeds = [
+            TICK_ARRAY_SEED,
+            pool.key().as_ref(),
+            &TickArray::start_index_for(tick_upper, pool.tick_spacing).to_le_bytes()
+        ],
+        bump
+    )]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
     #[account(mut)]
     pub position_owner: Signer<'info>,
 
@@ -93,14 +119,75 @@ pub fn open_position(
     let personal_position = &mut ctx.accounts.personal_position;
     let clock = Clock::get()?;
 
+    validate_position_range(&ctx.accounts.amm_global, pool, tick_lower, tick_upper)?;
+
+    // Surface missing tick arrays now rather than on the first increase_liquidity
+    for tick_array_info in [&ctx.accounts.tick_array_lower, &ctx.accounts.tick_array_upper] {
+        require!(
+            tick_array_info.owner == &crate::ID && !tick_array_info.data_is_empty(),
+            AmmError::TickArrayNotInitialized
+        );
+    }
+
+    initialize_position_state(
+        position,
+        personal_position,
+        pool.key(),
+        ctx.accounts.position_mint.key(),
+        ctx.accounts.position_owner.key(),
+        tick_lower,
+        tick_upper,
+        ctx.bumps.position,
+        ctx.bumps.personal_position,
+    );
+
+    mint_position_nft(
+        position,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.metadata_account,
+        &ctx.accounts.position_owner,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+    )?;
+
+    // Emit position opened event
+    emit!(PositionOpenedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        owner: position.owner,
+        tick_lower: position.tick_lower,
+        tick_upper: position.tick_upper,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🎯 CLMM Position opened successfully");
+    msg!("Position Mint: {}", position.mint);
+    msg!("Pool: {}", position.pool_id);
+    msg!("Owner: {}", position.owner);
+    msg!("Tick Range: {} to {}", tick_lower, tick_upper);
+    msg!("Position NFT minted to owner");
+
+    Ok(())
+}
+
+/// Tick bounds, spacing and minimum width checks for a new position
+pub(crate) fn validate_position_range(
+    amm_global: &AmmGlobal,
+    pool: &Pool,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<()> {
     // Validate tick range
     require!(tick_lower < tick_upper, AmmError::InvalidTickRange);
     require!(
-        tick_lower >= MIN_TICK && tick_lower <= MAX_TICK,
+        (MIN_TICK..=MAX_TICK).contains(&tick_lower),
         AmmError::TickOutOfBounds
     );
     require!(
-        tick_upper >= MIN_TICK && tick_upper <= MAX_TICK,
+        (MIN_TICK..=MAX_TICK).contains(&tick_upper),
         AmmError::TickOutOfBounds
     );
 
@@ -114,10 +201,37 @@ pub fn open_position(
         AmmError::InvalidTickSpacing
     );
 
+    // Enforce the configured minimum range width so positions cannot act as limit orders
+    let min_tick_range_multiple = amm_global.min_tick_range_multiple;
+    if min_tick_range_multiple > 0 {
+        let min_tick_range = (min_tick_range_multiple as i64)
+            .checked_mul(pool.tick_spacing as i64)
+            .ok_or(AmmError::Overflow)?;
+        require!(
+            (tick_upper as i64) - (tick_lower as i64) >= min_tick_range,
+            AmmError::TickRangeTooNarrow
+        );
+    }
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)] // Shared by several instructions, each passing its own accounts
+pub(crate) fn initialize_position_state(
+    position: &mut Position,
+    personal_position: &mut PersonalPosition,
+    pool_id: Pubkey,
+    position_mint: Pubkey,
+    position_owner: Pubkey,
+    tick_lower: i32,
+    tick_upper: i32,
+    position_bump: u8,
+    personal_position_bump: u8,
+) {
     // Initialize position state
-    position.mint = ctx.accounts.position_mint.key();
-    position.owner = ctx.accounts.position_owner.key();
-    position.pool_id = pool.key();
+    position.mint = position_mint;
+    position.owner = position_owner;
+    position.pool_id = pool_id;
     position.tick_lower = tick_lower;
     position.tick_upper = tick_upper;
     position.liquidity = 0;
@@ -127,16 +241,30 @@ pub fn open_position(
     position.fees_owed_b = 0;
     position.reward_growth_inside_last = [0; 3];
     position.rewards_owed = [0; 3];
-    position.bump = ctx.bumps.position;
+    position.bump = position_bump;
 
     // Initialize personal position tracking
-    personal_position.owner = ctx.accounts.position_owner.key();
-    personal_position.pool_id = pool.key();
-    personal_position.position_mint = ctx.accounts.position_mint.key();
-    personal_position.bump = ctx.bumps.personal_position;
+    personal_position.owner = position_owner;
+    personal_position.pool_id = pool_id;
+    personal_position.position_mint = position_mint;
+    personal_position.bump = personal_position_bump;
+}
 
+/// Mint the position NFT to the owner and create its metadata, signed by the position PDA
+#[allow(clippy::too_many_arguments)] // Shared by several instructions, each passing its own accounts
+pub(crate) fn mint_position_nft<'info>(
+    position: &Account<'info, Position>,
+    position_mint: &Account<'info, Mint>,
+    position_token_account: &Account<'info, TokenAccount>,
+    metadata_account: &UncheckedAccount<'info>,
+    position_owner: &Signer<'info>,
+    metadata_program: &Program<'info, Metadata>,
+    token_program: &Program<'info, Token>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+) -> Result<()> {
     // Mint position NFT using position authority
-    let position_mint_key = ctx.accounts.position_mint.key();
+    let position_mint_key = position_mint.key();
     let seeds = &[
         POSITION_SEED,
         position_mint_key.as_ref(),
@@ -145,10 +273,10 @@ pub fn open_position(
     let signer = &[&seeds[..]];
 
     let mint_to_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
+        token_program.to_account_info(),
         MintTo {
-            mint: ctx.accounts.position_mint.to_account_info(),
-            to: ctx.accounts.position_token_account.to_account_info(),
+            mint: position_mint.to_account_info(),
+            to: position_token_account.to_account_info(),
             authority: position.to_account_info(),
         },
         signer,
@@ -157,15 +285,15 @@ pub fn open_position(
 
     // Create position NFT metadata
     let metadata_ctx = CpiContext::new_with_signer(
-        ctx.accounts.metadata_program.to_account_info(),
+        metadata_program.to_account_info(),
         CreateMetadataAccountsV3 {
-            metadata: ctx.accounts.metadata_account.to_account_info(),
-            mint: ctx.accounts.position_mint.to_account_info(),
+            metadata: metadata_account.to_account_info(),
+            mint: position_mint.to_account_info(),
             mint_authority: position.to_account_info(),
             update_authority: position.to_account_info(),
-            payer: ctx.accounts.position_owner.to_account_info(),
-            system_program: ctx.accounts.system_program.to_account_info(),
-            rent: ctx.accounts.rent.to_account_info(),
+            payer: position_owner.to_account_info(),
+            system_program: system_program.to_account_info(),
+            rent: rent.to_account_info(),
         },
         signer,
     );
@@ -176,7 +304,7 @@ pub fn open_position(
         uri: "https://api.example.com/position-metadata".to_string(), // Would be dynamic
         seller_fee_basis_points: 0,
         creators: Some(vec![Creator {
-            address: ctx.accounts.position_owner.key(),
+            address: position_owner.key(),
             verified: true,
             share: 100,
         }]),
@@ -192,22 +320,5 @@ pub fn open_position(
         Some(CollectionDetails::V1 { size: 0 }),
     )?;
 
-    // Emit position opened event
-    emit!(PositionOpenedEvent {
-        position_mint: position.mint,
-        pool_id: position.pool_id,
-        owner: position.owner,
-        tick_lower: position.tick_lower,
-        tick_upper: position.tick_upper,
-        timestamp: clock.unix_timestamp,
-    });
-
-    msg!("🎯 CLMM Position opened successfully");
-    msg!("Position Mint: {}", position.mint);
-    msg!("Pool: {}", position.pool_id);
-    msg!("Owner: {}", position.owner);
-    msg!("Tick Range: {} to {}", tick_lower, tick_upper);
-    msg!("Position NFT minted to owner");
-
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,236 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+    metadata::Metadata,
+};
+use crate::{constants::*, state::{AmmGlobal, Pool, Position, TickArray, PersonalPosition}, events::*, errors::*};
+use super::open_position::{initialize_position_state, mint_position_nft, validate_position_range};
+use super::increase_liquidity::{apply_liquidity_increase, transfer_liquidity_deposit};
+
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct OpenPositionWithLiquidity<'info> {
+    #[account(
+        constraint = !amm_global.is_paused
+    )]
+    pub amm_global: Box<Account<'info, AmmGlobal>>,
+
+    #[account(
+        mut,
+        constraint = pool.allows_deposits()
+            @ AmmError::PoolDisabled
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        init,
+        payer = position_owner,
+        space = Position::LEN,
+        seeds = [POSITION_SEED, position_mint.key().as_ref()],
+        bump
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    #[account(
+        init,
+        payer = position_owner,
+        mint::decimals = 0,
+        mint::authority = position,
+        mint::freeze_authority = position,
+    )]
+    pub position_mint: Box<Account<'info, Mint>>,
+
+    /// Position metadata account (NFT)
+    /// CHECK: Created via CPI to metadata program
+    #[account(
+        mut,
+        seeds = [
+            b"metadata",
+            metadata_program.key().as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata_account: UncheckedAccount<'info>,
+
+    /// Position NFT token account
+    #[account(
+        init,
+        payer = position_owner,
+        associated_token::mint = position_mint,
+        associated_token::authority = position_owner
+    )]
+    pub position_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Personal position tracking
+    #[account(
+        init,
+        payer = position_owner,
+        space = PersonalPosition::LEN,
+        seeds = [PERSONAL_POSITION_SEED, position_owner.key().as_ref(), pool.key().as_ref()],
+        bump
+    )]
+    pub personal_position: Box<Account<'info, PersonalPosition>>,
+
+    /// Pool vault for token A (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_a.key() == pool.vault_a
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+
+    /// Pool vault for token B (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_b.key() == pool.vault_b
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+
+    /// User's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.owner == position_owner.key(),
+        constraint = user_token_a.mint == vault_a.mint
+    )]
+    pub user_token_a: Box<Account<'info, TokenAccount>>,
+
+    /// User's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.owner == position_owner.key(),
+        constraint = user_token_b.mint == vault_b.mint
+    )]
+    pub user_token_b: Box<Account<'info, TokenAccount>>,
+
+    /// Tick array covering tick_lower (must already be initialized)
+    #[account(
+        mut,
+        constraint = tick_array_lower.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_lower.check_in_array(tick_lower)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_lower: Box<Account<'info, TickArray>>,
+
+    /// Tick array covering tick_upper (must already be initialized)
+    #[account(
+        mut,
+        constraint = tick_array_upper.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_upper.check_in_array(tick_upper)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_upper: Box<Account<'info, TickArray>>,
+
+    #[account(mut)]
+    pub position_owner: Signer<'info>,
+
+    pub metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Same state as `open_position` followed by `increase_liquidity`, in one instruction
+pub fn open_position_with_liquidity(
+    ctx: Context<OpenPositionWithLiquidity>,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity_delta: u128,
+    amount0_max: u64,
+    amount1_max: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let position = &mut ctx.accounts.position;
+    let clock = Clock::get()?;
+
+    validate_position_range(&ctx.accounts.amm_global, pool, tick_lower, tick_upper)?;
+
+    initialize_position_state(
+        position,
+        &mut ctx.accounts.personal_position,
+        pool.key(),
+        ctx.accounts.position_mint.key(),
+        ctx.accounts.position_owner.key(),
+        tick_lower,
+        tick_upper,
+        ctx.bumps.position,
+        ctx.bumps.personal_position,
+    );
+
+    mint_position_nft(
+        position,
+        &ctx.accounts.position_mint,
+        &ctx.accounts.position_token_account,
+        &ctx.accounts.metadata_account,
+        &ctx.accounts.position_owner,
+        &ctx.accounts.metadata_program,
+        &ctx.accounts.token_program,
+        &ctx.accounts.system_program,
+        &ctx.accounts.rent,
+    )?;
+
+    let (amount0_required, amount1_required) = apply_liquidity_increase(
+        &ctx.accounts.amm_global,
+        pool,
+        position,
+        &mut ctx.accounts.tick_array_lower,
+        &mut ctx.accounts.tick_array_upper,
+        liquidity_delta,
+        amount0_max,
+        amount1_max,
+        clock.unix_timestamp,
+    )?;
+
+    transfer_liquidity_deposit(
+        &ctx.accounts.token_program,
+        &ctx.accounts.position_owner,
+        &ctx.accounts.user_token_a,
+        &ctx.accounts.user_token_b,
+        &ctx.accounts.vault_a,
+        &ctx.accounts.vault_b,
+        amount0_required,
+        amount1_required,
+    )?;
+
+    // Emit the same events as the two-step flow
+    emit!(PositionOpenedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        owner: position.owner,
+        tick_lower: position.tick_lower,
+        tick_upper: position.tick_upper,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(LiquidityIncreasedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        liquidity_delta,
+        amount0: amount0_required,
+        amount1: amount1_required,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PoolLiquiditySnapshotEvent {
+        pool_id: pool.key(),
+        liquidity: pool.liquidity,
+        protocol_liquidity: pool.protocol_liquidity,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🎯 CLMM Position opened with liquidity");
+    msg!("Position Mint: {}", position.mint);
+    msg!("Pool: {}", position.pool_id);
+    msg!("Tick Range: {} to {}", tick_lower, tick_upper);
+    msg!("Liquidity: {}", liquidity_delta);
+    msg!("Amount0 Deposited: {} tokens", amount0_required);
+    msg!("Amount1 Deposited: {} tokens", amount1_required);
+
+    Ok(())
+}
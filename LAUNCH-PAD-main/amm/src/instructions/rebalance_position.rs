@@ -0,0 +1,341 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, TokenAccount, Transfer},
+};
+use crate::{constants::*, state::{AmmGlobal, Pool, Position, TickArray}, events::*, errors::*, math::MathUtil};
+use super::open_position::validate_position_range;
+use super::increase_liquidity::{apply_liquidity_increase, transfer_liquidity_deposit};
+use super::decrease_liquidity::{
+    calculate_amounts_for_liquidity_withdrawal, remove_in_range_liquidity, update_ticks_for_liquidity_decrease,
+};
+
+#[derive(Accounts)]
+#[instruction(new_tick_lower: i32, new_tick_upper: i32)]
+pub struct RebalancePosition<'info> {
+    #[account(
+        constraint = !amm_global.is_paused
+    )]
+    pub amm_global: Box<Account<'info, AmmGlobal>>,
+
+    #[account(
+        mut,
+        constraint = pool.allows_withdrawals()
+            @ AmmError::PoolDisabled,
+        constraint = pool.allows_deposits()
+            @ AmmError::PoolDisabled
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = position.pool_id == pool.key(),
+        constraint = position.owner == position_owner.key()
+    )]
+    pub position: Box<Account<'info, Position>>,
+
+    /// Pool vault for token A (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_a.key() == pool.vault_a
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+
+    /// Pool vault for token B (multi-sig protected)
+    #[account(
+        mut,
+        constraint = vault_b.key() == pool.vault_b
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+
+    /// User's token A account
+    #[account(
+        mut,
+        constraint = user_token_a.owner == position_owner.key(),
+        constraint = user_token_a.mint == vault_a.mint
+    )]
+    pub user_token_a: Box<Account<'info, TokenAccount>>,
+
+    /// User's token B account
+    #[account(
+        mut,
+        constraint = user_token_b.owner == position_owner.key(),
+        constraint = user_token_b.mint == vault_b.mint
+    )]
+    pub user_token_b: Box<Account<'info, TokenAccount>>,
+
+    /// Tick array for the current lower tick
+    #[account(
+        mut,
+        constraint = tick_array_lower.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_lower.check_in_array(position.tick_lower)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_lower: Box<Account<'info, TickArray>>,
+
+    /// Tick array for the current upper tick
+    #[account(
+        mut,
+        constraint = tick_array_upper.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = tick_array_upper.check_in_array(position.tick_upper)
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array_upper: Box<Account<'info, TickArray>>,
+
+    /// Tick array covering new_tick_lower; may be one of the arrays above.
+    /// Declared after them so its write-back lands last.
+    #[account(
+        mut,
+        constraint = new_tick_array_lower.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = new_tick_array_lower.check_in_array(new_tick_lower)
+            @ AmmError::InvalidTickArray
+    )]
+    pub new_tick_array_lower: Box<Account<'info, TickArray>>,
+
+    /// Tick array covering new_tick_upper; may be one of the arrays above
+    #[account(
+        mut,
+        constraint = new_tick_array_upper.pool_id == pool.key()
+            @ AmmError::InvalidTickArray,
+        constraint = new_tick_array_upper.check_in_array(new_tick_upper)
+            @ AmmError::InvalidTickArray
+    )]
+    pub new_tick_array_upper: Box<Account<'info, TickArray>>,
+
+    #[account(mut)]
+    pub position_owner: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+/// Withdraw all liquidity from the position's current range, move the position to
+/// `[new_tick_lower, new_tick_upper)` and deposit `new_liquidity` there. Withdrawn
+/// amounts must meet `amount0_min`/`amount1_min` and deposits must stay within
+/// `amount0_max`/`amount1_max`; only the net difference per token is transferred.
+/// Fees owed from the old range are kept on the position.
+#[allow(clippy::too_many_arguments)] // Mirrors the instruction arguments
+pub fn rebalance_position(
+    ctx: Context<RebalancePosition>,
+    new_tick_lower: i32,
+    new_tick_upper: i32,
+    new_liquidity: u128,
+    amount0_min: u64,
+    amount1_min: u64,
+    amount0_max: u64,
+    amount1_max: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    let previous_tick_lower = ctx.accounts.position.tick_lower;
+    let previous_tick_upper = ctx.accounts.position.tick_upper;
+    let liquidity_removed = ctx.accounts.position.liquidity;
+
+    // Validate the move
+    require!(liquidity_removed > 0, AmmError::InsufficientLiquidity);
+    require!(
+        new_tick_lower != previous_tick_lower || new_tick_upper != previous_tick_upper,
+        AmmError::InvalidTickRange
+    );
+    validate_position_range(&ctx.accounts.amm_global, &ctx.accounts.pool, new_tick_lower, new_tick_upper)?;
+
+    // Price the withdrawal from the current range
+    let sqrt_price_lower_x64 = MathUtil::tick_to_sqrt_price_x64(previous_tick_lower)?;
+    let sqrt_price_upper_x64 = MathUtil::tick_to_sqrt_price_x64(previous_tick_upper)?;
+    let (amount0_withdrawn, amount1_withdrawn) = calculate_amounts_for_liquidity_withdrawal(
+        ctx.accounts.pool.sqrt_price_x64,
+        sqrt_price_lower_x64,
+        sqrt_price_upper_x64,
+        liquidity_removed,
+    )?;
+
+    // Check slippage protection on the withdrawal
+    require!(amount0_withdrawn >= amount0_min, AmmError::SlippageExceeded);
+    require!(amount1_withdrawn >= amount1_min, AmmError::SlippageExceeded);
+
+    // Settle fees earned in the current range before leaving it
+    {
+        let pool = &ctx.accounts.pool;
+        let (fee_growth_inside_a, fee_growth_inside_b) = pool.fee_growth_inside(
+            &ctx.accounts.tick_array_lower,
+            &ctx.accounts.tick_array_upper,
+            previous_tick_lower,
+            previous_tick_upper,
+        )?;
+        ctx.accounts.position.update_fees(fee_growth_inside_a, fee_growth_inside_b)?;
+    }
+
+    // Remove the liquidity from the current range
+    {
+        let pool = &mut ctx.accounts.pool;
+        let position = &mut ctx.accounts.position;
+
+        position.liquidity = 0;
+
+        pool.liquidity_reserve_a = pool.liquidity_reserve_a
+            .checked_sub(amount0_withdrawn)
+            .ok_or(AmmError::Underflow)?;
+        pool.liquidity_reserve_b = pool.liquidity_reserve_b
+            .checked_sub(amount1_withdrawn)
+            .ok_or(AmmError::Underflow)?;
+
+        if pool.tick_current >= previous_tick_lower && pool.tick_current < previous_tick_upper {
+            remove_in_range_liquidity(pool, position.owner, liquidity_removed, clock.unix_timestamp);
+        }
+
+        if ctx.accounts.amm_global.is_protocol_authority(&position.owner) {
+            pool.protocol_liquidity = pool.protocol_liquidity
+                .checked_sub(liquidity_removed)
+                .ok_or(AmmError::Underflow)?;
+        }
+
+        update_ticks_for_liquidity_decrease(
+            &mut ctx.accounts.tick_array_lower,
+            &mut ctx.accounts.tick_array_upper,
+            pool.tick_spacing,
+            previous_tick_lower,
+            previous_tick_upper,
+            liquidity_removed,
+        )?;
+    }
+
+    // The new range may share tick arrays with the old one; persist the decrease and
+    // reload so the increase builds on it
+    ctx.accounts.tick_array_lower.exit(&crate::ID)?;
+    ctx.accounts.tick_array_upper.exit(&crate::ID)?;
+    ctx.accounts.new_tick_array_lower.reload()?;
+    ctx.accounts.new_tick_array_upper.reload()?;
+
+    // Move the position and deposit into the new range; with zero liquidity the fee
+    // snapshot resets to the new range without touching fees owed
+    ctx.accounts.position.tick_lower = new_tick_lower;
+    ctx.accounts.position.tick_upper = new_tick_upper;
+
+    let (amount0_deposited, amount1_deposited) = apply_liquidity_increase(
+        &ctx.accounts.amm_global,
+        &mut ctx.accounts.pool,
+        &mut ctx.accounts.position,
+        &mut ctx.accounts.new_tick_array_lower,
+        &mut ctx.accounts.new_tick_array_upper,
+        new_liquidity,
+        amount0_max,
+        amount1_max,
+        clock.unix_timestamp,
+    )?;
+
+    // Settle only the net difference per token
+    let amount0_in = amount0_deposited.saturating_sub(amount0_withdrawn);
+    let amount1_in = amount1_deposited.saturating_sub(amount1_withdrawn);
+    let amount0_out = amount0_withdrawn.saturating_sub(amount0_deposited);
+    let amount1_out = amount1_withdrawn.saturating_sub(amount1_deposited);
+
+    transfer_liquidity_deposit(
+        &ctx.accounts.token_program,
+        &ctx.accounts.position_owner,
+        &ctx.accounts.user_token_a,
+        &ctx.accounts.user_token_b,
+        &ctx.accounts.vault_a,
+        &ctx.accounts.vault_b,
+        amount0_in,
+        amount1_in,
+    )?;
+
+    require!(
+        ctx.accounts.vault_a.amount >= amount0_out,
+        AmmError::InsufficientTokenBalance
+    );
+    require!(
+        ctx.accounts.vault_b.amount >= amount1_out,
+        AmmError::InsufficientTokenBalance
+    );
+
+    let pool = &ctx.accounts.pool;
+    let position = &ctx.accounts.position;
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    if amount0_out > 0 {
+        let transfer_a_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_a.to_account_info(),
+                to: ctx.accounts.user_token_a.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_a_ctx, amount0_out)?;
+    }
+
+    if amount1_out > 0 {
+        let transfer_b_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_b.to_account_info(),
+                to: ctx.accounts.user_token_b.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_b_ctx, amount1_out)?;
+    }
+
+    emit!(LiquidityDecreasedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        liquidity_delta: liquidity_removed,
+        amount0: amount0_withdrawn,
+        amount1: amount1_withdrawn,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(LiquidityIncreasedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        liquidity_delta: new_liquidity,
+        amount0: amount0_deposited,
+        amount1: amount1_deposited,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PositionRebalancedEvent {
+        position_mint: position.mint,
+        pool_id: position.pool_id,
+        previous_tick_lower,
+        previous_tick_upper,
+        tick_lower: new_tick_lower,
+        tick_upper: new_tick_upper,
+        liquidity_removed,
+        liquidity_added: new_liquidity,
+        amount0_withdrawn,
+        amount1_withdrawn,
+        amount0_deposited,
+        amount1_deposited,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(PoolLiquiditySnapshotEvent {
+        pool_id: pool.key(),
+        liquidity: pool.liquidity,
+        protocol_liquidity: pool.protocol_liquidity,
+        liquidity_reserve_a: pool.liquidity_reserve_a,
+        liquidity_reserve_b: pool.liquidity_reserve_b,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔄 Position rebalanced successfully");
+    msg!("Position: {}", position.mint);
+    msg!("Old Tick Range: {} to {}", previous_tick_lower, previous_tick_upper);
+    msg!("New Tick Range: {} to {}", new_tick_lower, new_tick_upper);
+    msg!("Liquidity: {} -> {}", liquidity_removed, new_liquidity);
+    msg!("Amount0 Withdrawn/Deposited: {}/{} tokens", amount0_withdrawn, amount0_deposited);
+    msg!("Amount1 Withdrawn/Deposited: {}/{} tokens", amount1_withdrawn, amount1_deposited);
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, Token, TokenAccount},
+    token::{self, Mint, Token, TokenAccount, Transfer},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*};
+use crate::{constants::*, state::{AmmGlobal, Pool, PoolRewardSlot, RewardInfo}, events::*, errors::*, math::MathUtil};
 
 #[derive(Accounts)]
 #[instruction(reward_index: u8)]
@@ -30,6 +30,16 @@ pub struct InitializeReward<'info> {
     )]
     pub reward_vault: Account<'info, TokenAccount>,
 
+    /// Overflow reward slot, created when reward_index is at least REWARD_NUM
+    #[account(
+        init,
+        payer = reward_authority,
+        space = PoolRewardSlot::LEN,
+        seeds = [REWARD_SEED, pool.key().as_ref(), &reward_index.to_le_bytes()],
+        bump
+    )]
+    pub overflow_reward: Option<Account<'info, PoolRewardSlot>>,
+
     /// Admin authority (required for multi-sig)
     #[account(
         constraint = admin_authority.key() == amm_global.admin_authority 
@@ -54,6 +64,7 @@ pub struct InitializeReward<'info> {
 }
 
 #[derive(Accounts)]
+#[instruction(reward_index: u8)]
 pub struct SetPoolReward<'info> {
     #[account(
         constraint = !amm_global.is_paused 
@@ -63,24 +74,122 @@ pub struct SetPoolReward<'info> {
     #[account(mut)]
     pub pool: Account<'info, Pool>,
 
-    /// Admin authority (required for multi-sig)
+    /// Reward vault of the slot (emissions must be covered by its balance)
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Overflow reward slot, required when reward_index is at least REWARD_NUM
+    #[account(
+        mut,
+        seeds = [REWARD_SEED, pool.key().as_ref(), &reward_index.to_le_bytes()],
+        bump = overflow_reward.bump
+    )]
+    pub overflow_reward: Option<Account<'info, PoolRewardSlot>>,
+
+    /// Admin authority (multi-sig path, together with multisig_authority)
     #[account(
         constraint = admin_authority.key() == amm_global.admin_authority 
             
     )]
-    pub admin_authority: Signer<'info>,
+    pub admin_authority: Option<Signer<'info>>,
 
-    /// Multi-sig authority (required for reward settings)
+    /// Multi-sig authority (multi-sig path, together with admin_authority)
     #[account(
         constraint = multisig_authority.key() == amm_global.multisig_authority 
             
     )]
+    pub multisig_authority: Option<Signer<'info>>,
+
+    /// Reward authority of the slot (self-managed path)
+    pub reward_authority: Option<Signer<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRewardAuthority<'info> {
+    #[account(
+        constraint = !amm_global.is_paused 
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Current reward authority or the multi-sig authority
+    pub authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct SetRewardAuthority<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == amm_global.admin_authority
+            @ AmmError::InvalidAdminAuthority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for reward authority rotation)
+    #[account(
+        constraint = multisig_authority.key() == amm_global.multisig_authority
+            @ AmmError::InvalidMultisigAuthority
+    )]
     pub multisig_authority: Signer<'info>,
+
+    /// Current reward authority of the slot
+    #[account(
+        constraint = (reward_index as usize) < REWARD_NUM
+            && reward_authority.key() == pool.reward_infos[reward_index as usize].authority
+            @ AmmError::UnauthorizedAccess
+    )]
+    pub reward_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct FundReward<'info> {
+    #[account(
+        constraint = !amm_global.is_paused 
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(mut)]
+    pub pool: Account<'info, Pool>,
+
+    /// Reward vault of the slot being funded
+    #[account(mut)]
+    pub reward_vault: Account<'info, TokenAccount>,
+
+    /// Overflow reward slot, required when reward_index is at least REWARD_NUM
+    #[account(
+        mut,
+        seeds = [REWARD_SEED, pool.key().as_ref(), &reward_index.to_le_bytes()],
+        bump = overflow_reward.bump
+    )]
+    pub overflow_reward: Option<Account<'info, PoolRewardSlot>>,
+
+    /// Funder's reward token account
+    #[account(
+        mut,
+        constraint = funder_token_account.owner == funder.key()
+            @ AmmError::InvalidAccountOwner,
+        constraint = funder_token_account.mint == reward_vault.mint
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub funder_token_account: Account<'info, TokenAccount>,
+
+    pub funder: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 pub fn initialize_reward(
     ctx: Context<InitializeReward>,
     reward_index: u8,
+    max_emissions_per_second_x64: u128,
 ) -> Result<()> {
     let amm_global = &ctx.accounts.amm_global;
     let pool = &mut ctx.accounts.pool;
@@ -89,17 +198,28 @@ pub fn initialize_reward(
     // Verify multi-sig authorization for reward initialization
     amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
 
-    // Validate reward index
-    require!(reward_index < REWARD_NUM as u8, AmmError::InvalidRewardIndex);
+    // The same mint at two indices would double-emit one token
+    require!(
+        pool.reward_infos.iter().all(|reward_info| reward_info.mint != ctx.accounts.reward_mint.key()),
+        AmmError::DuplicateRewardMint
+    );
+
+    // Indices past the in-struct slots live in their own freshly created account
+    if let Some(overflow_reward) = ctx.accounts.overflow_reward.as_mut() {
+        overflow_reward.pool = pool.key();
+        overflow_reward.reward_index = reward_index;
+        overflow_reward.bump = ctx.bumps.overflow_reward.ok_or(AmmError::InvalidRewardSlot)?;
+    }
+    let reward_info = reward_slot_mut(pool, ctx.accounts.overflow_reward.as_deref_mut(), reward_index)?;
 
     // Check if reward is already initialized
     require!(
-        pool.reward_infos[reward_index as usize].mint == Pubkey::default(),
+        reward_info.mint == Pubkey::default(),
         AmmError::RewardAlreadyInitialized
     );
 
     // Initialize reward info
-    pool.reward_infos[reward_index as usize] = RewardInfo {
+    *reward_info = RewardInfo {
         mint: ctx.accounts.reward_mint.key(),
         vault: ctx.accounts.reward_vault.key(),
         authority: ctx.accounts.reward_authority.key(),
@@ -107,6 +227,8 @@ pub fn initialize_reward(
         growth_global_x64: 0,
         last_update_time: clock.unix_timestamp as u64,
         total_amount_owed: 0,
+        funded_amount: 0,
+        max_emissions_per_second_x64,
     };
 
     // Update pool timestamp
@@ -137,6 +259,7 @@ pub fn initialize_reward(
     msg!("Reward Mint: {}", ctx.accounts.reward_mint.key());
     msg!("Reward Vault: {}", ctx.accounts.reward_vault.key());
     msg!("Reward Authority: {}", ctx.accounts.reward_authority.key());
+    msg!("Max Emissions per Second: {}", max_emissions_per_second_x64);
 
     Ok(())
 }
@@ -150,23 +273,74 @@ pub fn set_pool_reward(
     let pool = &mut ctx.accounts.pool;
     let clock = Clock::get()?;
 
-    // Verify multi-sig authorization for reward emission settings
-    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
-
-    // Validate reward index
-    require!(reward_index < REWARD_NUM as u8, AmmError::InvalidRewardIndex);
+    // Extract pool fields before the slot borrows it mutably
+    let pool_key = pool.key();
+    let pool_liquidity = pool.liquidity;
+    let reward_info = reward_slot_mut(pool, ctx.accounts.overflow_reward.as_deref_mut(), reward_index)?;
 
     // Check if reward is initialized
     require!(
-        pool.reward_infos[reward_index as usize].mint != Pubkey::default(),
+        reward_info.mint != Pubkey::default(),
         AmmError::RewardNotInitialized
     );
+    require!(
+        ctx.accounts.reward_vault.key() == reward_info.vault,
+        AmmError::InvalidTokenAccount
+    );
+
+    // Either full multi-sig or the slot's own reward authority may set emissions
+    let multisig_signers = match (&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority) {
+        (Some(admin_authority), Some(multisig_authority)) => {
+            amm_global.verify_multisig_auth(admin_authority, multisig_authority)?;
+            Some((admin_authority.key(), multisig_authority.key()))
+        }
+        _ => None,
+    };
+    let authority = match (&multisig_signers, &ctx.accounts.reward_authority) {
+        (Some((admin_signer, _)), _) => *admin_signer,
+        (None, Some(reward_authority))
+            if reward_authority.key() == reward_info.authority =>
+        {
+            reward_authority.key()
+        }
+        _ => return err!(AmmError::UnauthorizedAccess),
+    };
+
+    // Nonzero emissions need in-range liquidity to accrue against
+    require!(
+        emissions_per_second_x64 == 0 || pool_liquidity > 0,
+        AmmError::InsufficientLiquidity
+    );
+
+    // Emissions may never exceed the cap fixed at initialization
+    require!(
+        emissions_per_second_x64 <= reward_info.max_emissions_per_second_x64,
+        AmmError::EmissionsExceedCap
+    );
+
+    // Emissions must be covered by funded rewards for at least MIN_REWARD_DURATION;
+    // this also bounds what a self-managing reward authority can set
+    let required_funding_x64 = emissions_per_second_x64
+        .checked_mul(MIN_REWARD_DURATION as u128)
+        .ok_or(AmmError::Overflow)?;
+    let funded_x64 = (reward_info.funded_amount as u128)
+        .checked_mul(Q64)
+        .ok_or(AmmError::Overflow)?;
+    require!(
+        required_funding_x64 <= funded_x64,
+        AmmError::EmissionsExceedFunding
+    );
+
+    // The vault must actually hold that much beyond rewards already owed to positions
+    let available_x64 = (ctx.accounts.reward_vault.amount
+        .saturating_sub(reward_info.total_amount_owed) as u128)
+        .checked_mul(Q64)
+        .ok_or(AmmError::Overflow)?;
+    require!(
+        required_funding_x64 <= available_x64,
+        AmmError::EmissionsExceedFunding
+    );
 
-    // Update reward emissions
-    // Extract pool liquidity before mutable borrow to avoid borrow checker issues
-    let pool_liquidity = pool.liquidity;
-    let reward_info = &mut pool.reward_infos[reward_index as usize];
-    
     // Update growth before changing emissions
     update_reward_growth(reward_info, pool_liquidity, clock.unix_timestamp as u64)?;
     
@@ -179,52 +353,247 @@ pub fn set_pool_reward(
 
     // Emit reward emission updated event
     emit!(RewardEmissionUpdatedEvent {
-        pool_id: pool.key(),
+        pool_id: pool_key,
         reward_index,
         emissions_per_second_x64,
-        authority: ctx.accounts.admin_authority.key(),
+        authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    if let Some((admin_signer, multisig_signer)) = multisig_signers {
+        emit!(MultisigAmmOperationEvent {
+            operation: "REWARD_EMISSION_UPDATED".to_string(),
+            admin_signer,
+            multisig_signer,
+            target_account: pool.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    msg!("🎁 Reward emissions updated successfully");
+    msg!("Pool: {}", pool.key());
+    msg!("Reward Index: {}", reward_index);
+    msg!("Emissions per Second: {}", emissions_per_second_x64);
+
+    Ok(())
+}
+
+pub fn fund_reward(
+    ctx: Context<FundReward>,
+    reward_index: u8,
+    amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    let reward_info = reward_slot_mut(pool, ctx.accounts.overflow_reward.as_deref_mut(), reward_index)?;
+
+    // Check if reward is initialized
+    require!(
+        reward_info.mint != Pubkey::default(),
+        AmmError::RewardNotInitialized
+    );
+    require!(
+        ctx.accounts.reward_vault.key() == reward_info.vault,
+        AmmError::InvalidTokenAccount
+    );
+    require!(amount > 0, AmmError::InvalidRewardAmount);
+    require!(
+        ctx.accounts.funder_token_account.amount >= amount,
+        AmmError::InsufficientTokenBalance
+    );
+
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.funder_token_account.to_account_info(),
+            to: ctx.accounts.reward_vault.to_account_info(),
+            authority: ctx.accounts.funder.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+
+    // Track funding so emission schedules can be validated against it
+    reward_info.funded_amount = reward_info.funded_amount
+        .checked_add(amount)
+        .ok_or(AmmError::Overflow)?;
+    let funded_amount = reward_info.funded_amount;
+
+    // Update pool timestamp
+    pool.updated_at = clock.unix_timestamp;
+
+    emit!(RewardFundedEvent {
+        pool_id: pool.key(),
+        reward_index,
+        funder: ctx.accounts.funder.key(),
+        amount,
+        funded_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🎁 Reward vault funded");
+    msg!("Pool: {}", pool.key());
+    msg!("Reward Index: {}", reward_index);
+    msg!("Amount: {} tokens", amount);
+    msg!("Total Funded: {} tokens", funded_amount);
+
+    Ok(())
+}
+
+pub fn update_reward_authority(
+    ctx: Context<UpdateRewardAuthority>,
+    reward_index: u8,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &mut ctx.accounts.pool;
+    let authority = ctx.accounts.authority.key();
+    let clock = Clock::get()?;
+
+    // Validate reward index
+    require!(reward_index < REWARD_NUM as u8, AmmError::InvalidRewardIndex);
+
+    // Check if reward is initialized
+    require!(
+        pool.reward_infos[reward_index as usize].mint != Pubkey::default(),
+        AmmError::RewardNotInitialized
+    );
+
+    // Only the current reward authority or the multi-sig may hand off the slot
+    let previous_authority = pool.reward_infos[reward_index as usize].authority;
+    require!(
+        authority == previous_authority || authority == amm_global.multisig_authority,
+        AmmError::UnauthorizedAccess
+    );
+    require!(new_authority != Pubkey::default(), AmmError::UnauthorizedAccess);
+
+    pool.reward_infos[reward_index as usize].authority = new_authority;
+
+    // Update pool timestamp
+    pool.updated_at = clock.unix_timestamp;
+
+    emit!(RewardAuthorityUpdatedEvent {
+        pool_id: pool.key(),
+        reward_index,
+        previous_authority,
+        new_authority,
+        updated_by: authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🎁 Reward authority updated successfully");
+    msg!("Pool: {}", pool.key());
+    msg!("Reward Index: {}", reward_index);
+    msg!("Previous Authority: {}", previous_authority);
+    msg!("New Authority: {}", new_authority);
+
+    Ok(())
+}
+
+/// Rotate a reward slot's authority with both global authorities and the
+/// current reward authority signing
+pub fn set_reward_authority(
+    ctx: Context<SetRewardAuthority>,
+    reward_index: u8,
+    new_authority: Pubkey,
+) -> Result<()> {
+    let amm_global = &ctx.accounts.amm_global;
+    let pool = &mut ctx.accounts.pool;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for reward authority rotation
+    amm_global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Validate reward index
+    require!(reward_index < REWARD_NUM as u8, AmmError::InvalidRewardIndex);
+
+    // Check if reward is initialized
+    require!(
+        pool.reward_infos[reward_index as usize].mint != Pubkey::default(),
+        AmmError::RewardNotInitialized
+    );
+    require!(new_authority != Pubkey::default(), AmmError::UnauthorizedAccess);
+
+    let previous_authority = pool.reward_infos[reward_index as usize].authority;
+    pool.reward_infos[reward_index as usize].authority = new_authority;
+
+    // Update pool timestamp
+    pool.updated_at = clock.unix_timestamp;
+
+    emit!(RewardAuthorityUpdatedEvent {
+        pool_id: pool.key(),
+        reward_index,
+        previous_authority,
+        new_authority,
+        updated_by: ctx.accounts.reward_authority.key(),
         timestamp: clock.unix_timestamp,
     });
 
     // Multi-sig operation log
     emit!(MultisigAmmOperationEvent {
-        operation: "REWARD_EMISSION_UPDATED".to_string(),
+        operation: "REWARD_AUTHORITY_SET".to_string(),
         admin_signer: ctx.accounts.admin_authority.key(),
         multisig_signer: ctx.accounts.multisig_authority.key(),
         target_account: pool.key(),
         timestamp: clock.unix_timestamp,
     });
 
-    msg!("🎁 Reward emissions updated successfully");
+    msg!("🎁 Reward authority set with multi-sig protection");
     msg!("Pool: {}", pool.key());
     msg!("Reward Index: {}", reward_index);
-    msg!("Emissions per Second: {}", emissions_per_second_x64);
+    msg!("Previous Authority: {}", previous_authority);
+    msg!("New Authority: {}", new_authority);
 
     Ok(())
 }
 
-fn update_reward_growth(
+/// Reward state for `reward_index`: the pool's in-struct slot below REWARD_NUM,
+/// otherwise the overflow slot account, which must then be passed
+pub(crate) fn reward_slot_mut<'a>(
+    pool: &'a mut Pool,
+    overflow_reward: Option<&'a mut PoolRewardSlot>,
+    reward_index: u8,
+) -> Result<&'a mut RewardInfo> {
+    let index = reward_index as usize;
+    require!(index < MAX_REWARD_SLOTS, AmmError::InvalidRewardIndex);
+
+    if index < REWARD_NUM {
+        require!(overflow_reward.is_none(), AmmError::InvalidRewardSlot);
+        return Ok(&mut pool.reward_infos[index]);
+    }
+
+    let overflow_reward = overflow_reward.ok_or(AmmError::InvalidRewardSlot)?;
+    require!(overflow_reward.reward_index == reward_index, AmmError::InvalidRewardSlot);
+    Ok(&mut overflow_reward.reward_info)
+}
+
+pub(crate) fn update_reward_growth(
     reward_info: &mut RewardInfo,
     pool_liquidity: u128,
     current_time: u64,
 ) -> Result<()> {
-    if pool_liquidity == 0 {
+    // Nothing accrues without liquidity or emissions; snapshot so the gap is never credited later
+    if pool_liquidity == 0 || reward_info.emissions_per_second_x64 == 0 {
         reward_info.last_update_time = current_time;
         return Ok(());
     }
 
     let time_delta = current_time
         .checked_sub(reward_info.last_update_time)
-        .ok_or(AmmError::Underflow)?;
+        .ok_or(AmmError::Underflow)?
+        .min(MAX_REWARD_ACCRUAL_SECONDS);
 
     if time_delta == 0 {
         return Ok(());
     }
 
-    let reward_growth_delta = reward_info.emissions_per_second_x64
-        .checked_mul(time_delta as u128)
-        .and_then(|x| x.checked_div(pool_liquidity))
-        .ok_or(AmmError::Overflow)?;
+    // 256-bit intermediate so long idle gaps cannot overflow before the liquidity division
+    let reward_growth_delta = MathUtil::mul_div_floor(
+        reward_info.emissions_per_second_x64,
+        time_delta as u128,
+        pool_liquidity,
+    )?;
 
     reward_info.growth_global_x64 = reward_info.growth_global_x64
         .checked_add(reward_growth_delta)
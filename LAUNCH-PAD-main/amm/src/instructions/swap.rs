@@ -1,8 +1,9 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{self, Token, TokenAccount, Transfer},
+    token::{self, spl_token::native_mint, CloseAccount, SyncNative, Token, TokenAccount, Transfer},
 };
-use crate::{constants::*, state::{AmmGlobal, Pool, RewardInfo, Position, TickArray, Tick, PersonalPosition}, events::*, errors::*, math::MathUtil};
+use crate::{constants::*, state::{require_not_expired, AmmGlobal, Pool, TickArray, UserVolumeAccumulator}, events::*, errors::*, math::MathUtil};
+use super::reward_operations::update_reward_growth;
 
 #[derive(Accounts)]
 pub struct Swap<'info> {
@@ -14,7 +15,7 @@ pub struct Swap<'info> {
 
     #[account(
         mut,
-        constraint = pool.status == POOL_STATUS_INITIALIZED || pool.status == POOL_STATUS_SWAP_ONLY
+        constraint = pool.allows_swaps()
             @ AmmError::PoolDisabled
     )]
     pub pool: Account<'info, Pool>,
@@ -74,48 +75,73 @@ pub struct Swap<'info> {
     )]
     pub platform_wallet: UncheckedAccount<'info>,
 
-    /// Creator fee collection wallet (multi-sig controlled)
-    /// CHECK: Validated against global configuration
+    /// Creator fee collection wallet: the pool's token creator if recorded, else the global wallet
+    /// CHECK: Validated against pool and global configuration
     #[account(
         mut,
-        constraint = creator_wallet.key() == amm_global.creator_wallet
+        constraint = creator_wallet.key() == pool.creator_fee_wallet(amm_global.creator_wallet)
             @ AmmError::CreatorWalletMismatch
     )]
     pub creator_wallet: UncheckedAccount<'info>,
 
+    /// Optional per-user swap stats, created with init_user_volume_accumulator
+    #[account(
+        mut,
+        seeds = [USER_VOLUME_SEED, user.key().as_ref()],
+        bump = user_volume_accumulator.bump
+    )]
+    pub user_volume_accumulator: Option<Account<'info, UserVolumeAccumulator>>,
+
     #[account(mut)]
     pub user: Signer<'info>,
 
     pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
 }
 
-pub fn swap(
-    ctx: Context<Swap>,
+/// Additional tick arrays may follow in `remaining_accounts`, contiguous in the swap direction.
+/// `wrap_sol` tops up a wSOL input account from the user's lamports; `unwrap_sol` closes a
+/// wSOL output account afterwards so the user receives native SOL.
+#[allow(clippy::too_many_arguments)] // Mirrors the instruction arguments
+pub fn swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
     amount: u64,
     other_amount_threshold: u64,
     sqrt_price_limit_x64: u128,
     is_base_input: bool,
+    wrap_sol: bool,
+    unwrap_sol: bool,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let amm_global = &mut ctx.accounts.amm_global;
     let pool = &mut ctx.accounts.pool;
     let tick_array = &mut ctx.accounts.tick_array;
+    let fee_multiplier_bps = amm_global.fee_multiplier_bps();
     let clock = Clock::get()?;
 
+    // Reject transactions held past the caller's deadline
+    require_not_expired(deadline, clock.unix_timestamp)?;
+
     // Validate input amount
     require!(amount > 0, AmmError::InvalidTokenAmount);
 
     // Validate sqrt price limit
     require!(
-        sqrt_price_limit_x64 >= MIN_SQRT_PRICE_X64 && sqrt_price_limit_x64 <= MAX_SQRT_PRICE_X64,
+        (MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_limit_x64),
         AmmError::InvalidSqrtPrice
     );
 
-    // Determine if this is a zero-for-one swap (token A for token B)
-    let zero_for_one = if is_base_input {
-        ctx.accounts.input_vault.key() == pool.vault_a
-    } else {
-        ctx.accounts.output_vault.key() == pool.vault_a
-    };
+    // A limit at the current price could never move the price
+    require!(
+        sqrt_price_limit_x64 != pool.sqrt_price_x64,
+        AmmError::InvalidSqrtPrice
+    );
+
+    // Determine if this is a zero-for-one swap (token A for token B); the vaults
+    // must be the pool's two sides, one each
+    let zero_for_one = pool
+        .swap_direction(ctx.accounts.input_vault.key(), ctx.accounts.output_vault.key())
+        .ok_or(AmmError::InvalidTokenAccount)?;
 
     // Validate price limit direction
     if zero_for_one {
@@ -130,21 +156,57 @@ pub fn swap(
         );
     }
 
-    // Check if user has sufficient input tokens
+    // Native SOL handling only applies to wSOL token accounts
+    if wrap_sol {
+        require!(
+            ctx.accounts.input_token_account.mint == native_mint::ID,
+            AmmError::InvalidNativeMint
+        );
+    }
+    if unwrap_sol {
+        require!(
+            ctx.accounts.output_token_account.mint == native_mint::ID,
+            AmmError::InvalidNativeMint
+        );
+    }
+
+    // Check if user has sufficient input tokens (native SOL counts when wrapping)
+    let available_input = if wrap_sol {
+        ctx.accounts.input_token_account.amount.saturating_add(ctx.accounts.user.lamports())
+    } else {
+        ctx.accounts.input_token_account.amount
+    };
     require!(
-        ctx.accounts.input_token_account.amount >= amount,
+        available_input >= amount,
         AmmError::InsufficientTokenBalance
     );
 
-    // Perform the swap calculation
-    let (amount_in, amount_out, new_sqrt_price, new_tick) = calculate_swap(
+    // Perform the swap calculation, walking into additional tick arrays as needed
+    let computation = calculate_swap(
         pool,
         tick_array,
+        ctx.remaining_accounts,
         amount,
         sqrt_price_limit_x64,
         zero_for_one,
         is_base_input,
+        fee_multiplier_bps,
     )?;
+    let amount_in = computation.amount_in;
+    let amount_out = computation.amount_out;
+
+    // Never charge fees for a swap that delivers nothing; revert before any transfer or state change
+    require!(amount_out > 0, AmmError::ZeroOutputAmount);
+
+    // Revert cleanly rather than in the token program, and never pay out protocol fees as swap output
+    require!(
+        ctx.accounts.output_vault.amount >= amount_out,
+        AmmError::InsufficientTokenBalance
+    );
+    require!(
+        amount_out <= pool.output_liquidity_reserve(zero_for_one),
+        AmmError::InsufficientLiquidity
+    );
 
     // Check slippage protection
     if is_base_input {
@@ -154,31 +216,46 @@ pub fn swap(
     }
 
     // Calculate fees
-    let trade_fee = amount_in
-        .checked_mul(pool.trade_fee_rate as u64)
-        .and_then(|x| x.checked_div(FEE_RATE_DENOMINATOR_VALUE))
-        .ok_or(AmmError::Overflow)?;
+    let fees = calculate_swap_fees(pool, amount_in, computation.trade_fee)?;
+    let SwapFees {
+        trade_fee,
+        protocol_fee,
+        platform_fee,
+        creator_fee,
+        lp_fee,
+        ..
+    } = fees;
 
-    let protocol_fee = trade_fee
-        .checked_mul(pool.protocol_fee_rate as u64)
-        .and_then(|x| x.checked_div(FEE_RATE_DENOMINATOR_VALUE))
-        .ok_or(AmmError::Overflow)?;
-
-    let platform_fee = trade_fee
-        .checked_mul(PLATFORM_FEE_BASIS_POINTS as u64)
-        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
-        .ok_or(AmmError::Overflow)?;
-
-    let creator_fee = trade_fee
-        .checked_mul(CREATOR_FEE_BASIS_POINTS as u64)
-        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
-        .ok_or(AmmError::Overflow)?;
+    // Below FEE_RATE_DENOMINATOR / fee rate the trade fee rounds to zero; reject such dust swaps
+    require!(
+        pool.effective_trade_fee_rate(fee_multiplier_bps) == 0 || trade_fee > 0,
+        AmmError::SwapTooSmall
+    );
 
-    let net_amount_in = amount_in
-        .checked_sub(trade_fee)
-        .ok_or(AmmError::Underflow)?;
+    // Wrap just enough native SOL to cover the input
+    if wrap_sol {
+        let wrap_amount = amount_in.saturating_sub(ctx.accounts.input_token_account.amount);
+        if wrap_amount > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.user.to_account_info(),
+                        to: ctx.accounts.input_token_account.to_account_info(),
+                    },
+                ),
+                wrap_amount,
+            )?;
+            token::sync_native(CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                SyncNative {
+                    account: ctx.accounts.input_token_account.to_account_info(),
+                },
+            ))?;
+        }
+    }
 
-    // Transfer input tokens from user to pool
+    // Transfer input tokens (including the trade fee) from user to pool
     let transfer_input_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
         Transfer {
@@ -187,9 +264,19 @@ pub fn swap(
             authority: ctx.accounts.user.to_account_info(),
         },
     );
-    token::transfer(transfer_input_ctx, net_amount_in)?;
+    token::transfer(transfer_input_ctx, amount_in)?;
+
+    // Pay the platform and creator shares out of the input vault
+    pay_out_swap_fees(
+        &ctx.accounts.token_program.to_account_info(),
+        amm_global,
+        pool,
+        &ctx.accounts.input_vault.to_account_info(),
+        &ctx.accounts.platform_wallet.to_account_info(),
+        &ctx.accounts.creator_wallet.to_account_info(),
+        &fees,
+    )?;
 
-    // Transfer fees to respective wallets using pool authority
     let pool_seeds = &[
         POOL_SEED,
         pool.mint_a.as_ref(),
@@ -198,48 +285,6 @@ pub fn swap(
     ];
     let pool_signer = &[&pool_seeds[..]];
 
-    // Transfer protocol fee to platform wallet
-    if protocol_fee > 0 {
-        let transfer_protocol_fee_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.input_vault.to_account_info(),
-                to: ctx.accounts.platform_wallet.to_account_info(),
-                authority: pool.to_account_info(),
-            },
-            pool_signer,
-        );
-        token::transfer(transfer_protocol_fee_ctx, protocol_fee)?;
-    }
-
-    // Transfer platform fee
-    if platform_fee > 0 {
-        let transfer_platform_fee_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.input_vault.to_account_info(),
-                to: ctx.accounts.platform_wallet.to_account_info(),
-                authority: pool.to_account_info(),
-            },
-            pool_signer,
-        );
-        token::transfer(transfer_platform_fee_ctx, platform_fee)?;
-    }
-
-    // Transfer creator fee
-    if creator_fee > 0 {
-        let transfer_creator_fee_ctx = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.input_vault.to_account_info(),
-                to: ctx.accounts.creator_wallet.to_account_info(),
-                authority: pool.to_account_info(),
-            },
-            pool_signer,
-        );
-        token::transfer(transfer_creator_fee_ctx, creator_fee)?;
-    }
-
     // Transfer output tokens from pool to user
     let transfer_output_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
@@ -252,26 +297,30 @@ pub fn swap(
     );
     token::transfer(transfer_output_ctx, amount_out)?;
 
-    // Update pool state
-    pool.sqrt_price_x64 = new_sqrt_price;
-    pool.tick_current = new_tick;
-    pool.updated_at = clock.unix_timestamp;
+    // Close the wSOL output account so its whole balance returns as native SOL
+    if unwrap_sol {
+        token::close_account(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.output_token_account.to_account_info(),
+                destination: ctx.accounts.user.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ))?;
+    }
+
+    // Flip crossed ticks against the fee growth reached when each was crossed, then update pool state
+    cross_ticks(pool, tick_array, ctx.remaining_accounts, &computation.crossed_ticks, zero_for_one, clock.unix_timestamp)?;
+    apply_swap_to_pool(
+        pool,
+        zero_for_one,
+        &computation,
+        &fees,
+        clock.unix_timestamp,
+    )?;
 
-    // Update protocol fees
-    if zero_for_one {
-        pool.protocol_fees_token_a = pool.protocol_fees_token_a
-            .checked_add(protocol_fee)
-            .ok_or(AmmError::Overflow)?;
-        pool.total_volume_a = pool.total_volume_a
-            .checked_add(amount_in)
-            .ok_or(AmmError::Overflow)?;
-    } else {
-        pool.protocol_fees_token_b = pool.protocol_fees_token_b
-            .checked_add(protocol_fee)
-            .ok_or(AmmError::Overflow)?;
-        pool.total_volume_b = pool.total_volume_b
-            .checked_add(amount_in)
-            .ok_or(AmmError::Overflow)?;
+    if pool.is_fee_snapshot_due(amm_global.fee_snapshot_interval(), clock.unix_timestamp) {
+        emit_fee_growth_snapshot(pool, clock.unix_timestamp);
     }
 
     // Update global volume tracking
@@ -283,6 +332,11 @@ pub fn swap(
         .checked_add(trade_fee)
         .ok_or(AmmError::Overflow)?;
 
+    // Update user volume accumulator if the swapper has one
+    if let Some(user_volume) = ctx.accounts.user_volume_accumulator.as_mut() {
+        user_volume.record_swap(zero_for_one, amount_in, trade_fee, clock.unix_timestamp)?;
+    }
+
     // Emit swap event
     emit!(SwapEvent {
         pool_id: pool.key(),
@@ -304,47 +358,789 @@ pub fn swap(
     msg!("Protocol Fee: {} tokens", protocol_fee);
     msg!("Platform Fee: {} tokens", platform_fee);
     msg!("Creator Fee: {} tokens", creator_fee);
-    msg!("New Price: {}", new_sqrt_price);
-    msg!("New Tick: {}", new_tick);
+    msg!("LP Fee: {} tokens", lp_fee);
+    msg!("New Price: {}", pool.sqrt_price_x64);
+    msg!("New Tick: {}", pool.tick_current);
+    msg!("Ticks Crossed: {}", computation.crossed_ticks.len());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct QuoteSwap<'info> {
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(
+        constraint = pool.allows_swaps()
+            @ AmmError::PoolDisabled
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// Tick array for current price range
+    #[account(
+        constraint = tick_array.pool_id == pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub tick_array: Account<'info, TickArray>,
+}
+
+/// Preview a swap with the same pricing as `swap`, without mutating state.
+/// Takes the same additional tick arrays in `remaining_accounts`.
+pub fn quote_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QuoteSwap<'info>>,
+    amount: u64,
+    sqrt_price_limit_x64: u128,
+    zero_for_one: bool,
+    is_base_input: bool,
+) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let fee_multiplier_bps = ctx.accounts.amm_global.fee_multiplier_bps();
+    let clock = Clock::get()?;
+
+    // Same input validation as swap
+    require!(amount > 0, AmmError::InvalidTokenAmount);
+    require!(
+        (MIN_SQRT_PRICE_X64..=MAX_SQRT_PRICE_X64).contains(&sqrt_price_limit_x64),
+        AmmError::InvalidSqrtPrice
+    );
+    require!(
+        sqrt_price_limit_x64 != pool.sqrt_price_x64,
+        AmmError::InvalidSqrtPrice
+    );
+    if zero_for_one {
+        require!(
+            sqrt_price_limit_x64 < pool.sqrt_price_x64,
+            AmmError::InvalidSqrtPrice
+        );
+    } else {
+        require!(
+            sqrt_price_limit_x64 > pool.sqrt_price_x64,
+            AmmError::InvalidSqrtPrice
+        );
+    }
+
+    let computation = calculate_swap(
+        pool,
+        &ctx.accounts.tick_array,
+        ctx.remaining_accounts,
+        amount,
+        sqrt_price_limit_x64,
+        zero_for_one,
+        is_base_input,
+        fee_multiplier_bps,
+    )?;
+    let fees = calculate_swap_fees(pool, computation.amount_in, computation.trade_fee)?;
+
+    emit!(SwapQuoteEvent {
+        pool_id: pool.key(),
+        zero_for_one,
+        is_base_input,
+        amount_in: computation.amount_in,
+        amount_out: computation.amount_out,
+        new_sqrt_price: computation.sqrt_price_x64,
+        new_tick: computation.tick,
+        fee_amount: fees.trade_fee,
+        is_partial_fill: computation.is_partial_fill,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 Swap quote");
+    msg!("Amount In: {} tokens", computation.amount_in);
+    msg!("Amount Out: {} tokens", computation.amount_out);
+    msg!("Trade Fee: {} tokens", fees.trade_fee);
+    msg!("New Price: {}", computation.sqrt_price_x64);
+    msg!("Partial Fill: {}", computation.is_partial_fill);
+
+    Ok(())
+}
+
+/// Breakdown of the trade fee charged on a swap input
+#[derive(Clone, Copy)]
+pub(crate) struct SwapFees {
+    pub trade_fee: u64,
+    pub protocol_fee: u64,
+    pub platform_fee: u64,
+    pub creator_fee: u64,
+    pub lp_fee: u64,
+    pub net_amount_in: u64,
+}
+
+/// Split the trade fee priced by `calculate_swap` between protocol, platform, creator and LPs
+pub(crate) fn calculate_swap_fees(pool: &Pool, amount_in: u64, trade_fee: u64) -> Result<SwapFees> {
+    let protocol_fee = trade_fee
+        .checked_mul(pool.protocol_fee_rate as u64)
+        .and_then(|x| x.checked_div(FEE_RATE_DENOMINATOR_VALUE))
+        .ok_or(AmmError::Overflow)?;
+
+    let platform_fee = trade_fee
+        .checked_mul(pool.platform_fee_basis_points as u64)
+        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+        .ok_or(AmmError::Overflow)?;
+
+    let creator_fee = trade_fee
+        .checked_mul(pool.creator_fee_basis_points as u64)
+        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+        .ok_or(AmmError::Overflow)?;
+
+    let net_amount_in = amount_in
+        .checked_sub(trade_fee)
+        .ok_or(AmmError::Underflow)?;
+
+    // Remainder of the trade fee stays in the vault for in-range LPs
+    let lp_fee = trade_fee
+        .checked_sub(protocol_fee)
+        .and_then(|x| x.checked_sub(platform_fee))
+        .and_then(|x| x.checked_sub(creator_fee))
+        .ok_or(AmmError::Underflow)?;
+
+    Ok(SwapFees {
+        trade_fee,
+        protocol_fee,
+        platform_fee,
+        creator_fee,
+        lp_fee,
+        net_amount_in,
+    })
+}
+
+/// Pay a swap's protocol and platform shares to the platform wallet and its creator share
+/// to the creator wallet, out of the pool's input vault. Every swap path pays through here
+/// so none of these shares is left behind in the vault.
+pub(crate) fn pay_out_swap_fees<'info>(
+    token_program: &AccountInfo<'info>,
+    amm_global: &AmmGlobal,
+    pool: &Account<'info, Pool>,
+    input_vault: &AccountInfo<'info>,
+    platform_wallet: &AccountInfo<'info>,
+    creator_wallet: &AccountInfo<'info>,
+    fees: &SwapFees,
+) -> Result<()> {
+    require!(
+        platform_wallet.key() == amm_global.platform_wallet,
+        AmmError::PlatformWalletMismatch
+    );
+    require!(
+        creator_wallet.key() == pool.creator_fee_wallet(amm_global.creator_wallet),
+        AmmError::CreatorWalletMismatch
+    );
+
+    let pool_seeds = &[
+        POOL_SEED,
+        pool.mint_a.as_ref(),
+        pool.mint_b.as_ref(),
+        &[pool.bump],
+    ];
+    let pool_signer = &[&pool_seeds[..]];
+
+    // Protocol and platform shares both go to the platform wallet
+    let platform_amount = fees.protocol_fee
+        .checked_add(fees.platform_fee)
+        .ok_or(AmmError::Overflow)?;
+    if platform_amount > 0 {
+        let transfer_platform_fee_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: input_vault.clone(),
+                to: platform_wallet.clone(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_platform_fee_ctx, platform_amount)?;
+    }
+
+    if fees.creator_fee > 0 {
+        let transfer_creator_fee_ctx = CpiContext::new_with_signer(
+            token_program.clone(),
+            Transfer {
+                from: input_vault.clone(),
+                to: creator_wallet.clone(),
+                authority: pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_creator_fee_ctx, fees.creator_fee)?;
+    }
+
+    Ok(())
+}
+
+/// Emit the pool's cumulative fee growth for APR indexing and restart the snapshot interval
+pub(crate) fn emit_fee_growth_snapshot(pool: &mut Account<Pool>, timestamp: i64) {
+    pool.last_fee_snapshot_at = timestamp;
+
+    emit!(FeeGrowthSnapshotEvent {
+        pool_id: pool.key(),
+        fee_growth_global_a_x64: pool.fee_growth_global_a_x64,
+        fee_growth_global_b_x64: pool.fee_growth_global_b_x64,
+        liquidity: pool.liquidity,
+        timestamp,
+    });
+}
+
+/// Apply a priced swap to pool price, liquidity, fee growth, reserves and volume.
+/// The protocol, platform and creator shares are paid out by `pay_out_swap_fees`,
+/// so none of them is booked on the pool.
+pub(crate) fn apply_swap_to_pool(
+    pool: &mut Pool,
+    zero_for_one: bool,
+    computation: &SwapComputation,
+    fees: &SwapFees,
+    timestamp: i64,
+) -> Result<()> {
+    let amount_in = computation.amount_in;
+    let amount_out = computation.amount_out;
+
+    pool.sqrt_price_x64 = computation.sqrt_price_x64;
+    pool.tick_current = computation.tick;
+    pool.liquidity = computation.liquidity;
+    pool.updated_at = timestamp;
+
+    // Net input backs liquidity; so does the part of the LP fee credited to fee growth
+    let retained_input = fees.net_amount_in
+        .checked_add(computation.lp_fee_credited)
+        .ok_or(AmmError::Overflow)?;
+
+    // LP fee growth was accumulated step by step in calculate_swap
+    if zero_for_one {
+        pool.fee_growth_global_a_x64 = computation.fee_growth_global_x64;
+        pool.liquidity_reserve_a = pool.liquidity_reserve_a
+            .checked_add(retained_input)
+            .ok_or(AmmError::Overflow)?;
+        pool.liquidity_reserve_b = pool.liquidity_reserve_b
+            .checked_sub(amount_out)
+            .ok_or(AmmError::Underflow)?;
+    } else {
+        pool.fee_growth_global_b_x64 = computation.fee_growth_global_x64;
+        pool.liquidity_reserve_b = pool.liquidity_reserve_b
+            .checked_add(retained_input)
+            .ok_or(AmmError::Overflow)?;
+        pool.liquidity_reserve_a = pool.liquidity_reserve_a
+            .checked_sub(amount_out)
+            .ok_or(AmmError::Underflow)?;
+    }
+
+    // Update volume
+    if zero_for_one {
+        pool.total_volume_a = pool.total_volume_a
+            .checked_add(amount_in)
+            .ok_or(AmmError::Overflow)?;
+    } else {
+        pool.total_volume_b = pool.total_volume_b
+            .checked_add(amount_in)
+            .ok_or(AmmError::Overflow)?;
+    }
 
     Ok(())
 }
 
-// Simplified swap calculation (would be more complex in production)
-fn calculate_swap(
+/// Result of walking a swap through the pool's initialized ticks
+pub(crate) struct SwapComputation {
+    /// Input amount including the trade fee
+    pub amount_in: u64,
+    /// Trade fee in the input token: `amount_in` less what the curve consumed
+    pub trade_fee: u64,
+    pub amount_out: u64,
+    pub sqrt_price_x64: u128,
+    pub tick: i32,
+    pub liquidity: u128,
+    /// Input-side fee growth global after the LP fee of every step is credited
+    pub fee_growth_global_x64: u128,
+    /// Part of the LP fee that reached fee growth; a step too small to move it leaves dust
+    pub lp_fee_credited: u64,
+    /// Crossed ticks as (tick array slot, tick index, input-side fee growth global when
+    /// crossed); slot 0 is the primary array, slot n is `extra_tick_arrays[n - 1]`
+    pub crossed_ticks: Vec<(usize, i32, u128)>,
+    /// Price limit or the last supplied tick array stopped the swap early
+    pub is_partial_fill: bool,
+}
+
+/// Price a swap by stepping between initialized ticks. Adjacent tick arrays are
+/// loaded from `extra_tick_arrays` only when price crosses the current array's boundary.
+///
+/// The trade fee is always taken in the input token. Exact input charges it on the
+/// gross amount (`floor(amount * rate)`) and prices the remainder on the curve; exact
+/// output, and an exact input that fills only partially, price the net input first and
+/// gross it up (`ceil(net / (1 - rate))`). Either way `trade_fee = amount_in - net`.
+///
+/// The LP share of the trade fee is split across the steps in proportion to each step's
+/// input and credited to the liquidity in range during that step, so positions that were
+/// only in range before a tick flip still earn their part.
+#[allow(clippy::too_many_arguments)] // Pure swap math over the pool's state and tick arrays
+pub(crate) fn calculate_swap<'a>(
     pool: &Pool,
-    _tick_array: &TickArray,
+    tick_array: &TickArray,
+    extra_tick_arrays: &'a [AccountInfo<'a>],
     amount: u64,
     sqrt_price_limit_x64: u128,
     zero_for_one: bool,
     is_base_input: bool,
-) -> Result<(u64, u64, u128, i32)> {
-    // This is a simplified calculation
-    // In production, this would involve complex CLMM math with tick arrays
-    
-    let current_sqrt_price = pool.sqrt_price_x64;
-    
-    // Simple constant product approximation for demo
-    let amount_in = if is_base_input { amount } else { amount * 99 / 100 }; // Approximate input needed
-    let amount_out = if is_base_input { amount * 99 / 100 } else { amount }; // Approximate output
-    
-    // Calculate new price (simplified)
-    let price_impact = (amount_in as u128 * 100) / (pool.liquidity + 1); // Prevent division by zero
-    let new_sqrt_price = if zero_for_one {
-        current_sqrt_price.saturating_sub(price_impact)
+    fee_multiplier_bps: u16,
+) -> Result<SwapComputation> {
+    let tick_spacing = pool.tick_spacing;
+    let span = TickArray::span(tick_spacing);
+    require!(
+        tick_array.contains_tick(pool.tick_current, tick_spacing),
+        AmmError::InvalidTickArray
+    );
+
+    // Nothing meaningful to trade against at the current tick, e.g. a fresh pool with no
+    // in-range position; dust liquidity would let a tiny trade move the price arbitrarily
+    require!(pool.liquidity >= MIN_LIQUIDITY, AmmError::InsufficientLiquidity);
+
+    // Exact input is priced on the amount left after the trade fee
+    let fee_rate = pool.effective_trade_fee_rate(fee_multiplier_bps) as u64;
+    let mut amount_remaining = if is_base_input {
+        let fee = amount
+            .checked_mul(fee_rate)
+            .and_then(|x| x.checked_div(FEE_RATE_DENOMINATOR_VALUE))
+            .ok_or(AmmError::Overflow)?;
+        amount.checked_sub(fee).ok_or(AmmError::Underflow)?
     } else {
-        current_sqrt_price.saturating_add(price_impact)
+        amount
     };
-    
-    // Clamp to price limit
-    let final_sqrt_price = if zero_for_one {
-        new_sqrt_price.max(sqrt_price_limit_x64)
+
+    let mut net_amount_in: u64 = 0;
+    let mut amount_out: u64 = 0;
+    let mut sqrt_price_x64 = pool.sqrt_price_x64;
+    let mut tick = pool.tick_current;
+    let mut liquidity = pool.liquidity;
+    // (input, liquidity in range) per step, and (slot, tick, steps taken) per crossing
+    let mut steps: Vec<(u64, u128)> = Vec::new();
+    let mut crossings: Vec<(usize, i32, usize)> = Vec::new();
+
+    let mut array_slot = 0usize;
+    let mut array_start = tick_array.start_tick_index;
+    let mut loaded_array: Option<Box<Account<'a, TickArray>>> = None;
+    let mut out_of_tick_arrays = false;
+
+    while amount_remaining > 0 && sqrt_price_x64 != sqrt_price_limit_x64 {
+        let current_array: &TickArray = match &loaded_array {
+            Some(array) => array,
+            None => tick_array,
+        };
+
+        // Next initialized tick, or the array boundary in the swap direction
+        let (tick_next, initialized) = match current_array.next_initialized_tick(tick, tick_spacing, zero_for_one) {
+            Some(tick_index) => (tick_index, true),
+            None if zero_for_one => (array_start, false),
+            None => (array_start + span, false),
+        };
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_tick_x64 = MathUtil::tick_to_sqrt_price_x64(tick_next)?;
+        let sqrt_price_target_x64 = if zero_for_one {
+            sqrt_price_next_tick_x64.max(sqrt_price_limit_x64)
+        } else {
+            sqrt_price_next_tick_x64.min(sqrt_price_limit_x64)
+        };
+
+        let (sqrt_price_step_x64, step_amount_in, step_amount_out) = MathUtil::compute_swap_step(
+            sqrt_price_x64,
+            sqrt_price_target_x64,
+            liquidity,
+            amount_remaining,
+            zero_for_one,
+            is_base_input,
+        )?;
+        sqrt_price_x64 = sqrt_price_step_x64;
+        steps.push((step_amount_in, liquidity));
+
+        let step_consumed = if is_base_input { step_amount_in } else { step_amount_out };
+        amount_remaining = amount_remaining
+            .checked_sub(step_consumed)
+            .ok_or(AmmError::Underflow)?;
+        net_amount_in = net_amount_in
+            .checked_add(step_amount_in)
+            .ok_or(AmmError::Overflow)?;
+        amount_out = amount_out
+            .checked_add(step_amount_out)
+            .ok_or(AmmError::Overflow)?;
+
+        if sqrt_price_x64 != sqrt_price_next_tick_x64 {
+            tick = MathUtil::sqrt_price_x64_to_tick(sqrt_price_x64)?;
+            continue;
+        }
+
+        // Reached the next tick: apply its liquidity change if initialized
+        if initialized {
+            let liquidity_net = current_array.get_tick(tick_next, tick_spacing)?.liquidity_net;
+            let liquidity_delta = if zero_for_one { -liquidity_net } else { liquidity_net };
+            liquidity = if liquidity_delta >= 0 {
+                liquidity
+                    .checked_add(liquidity_delta as u128)
+                    .ok_or(AmmError::Overflow)?
+            } else {
+                liquidity
+                    .checked_sub(liquidity_delta.unsigned_abs())
+                    .ok_or(AmmError::Underflow)?
+            };
+            crossings.push((array_slot, tick_next, steps.len()));
+        }
+        // An upward array boundary is the next array's first tick, which is not crossed
+        // until that array is loaded and searched
+        tick = if zero_for_one || !initialized { tick_next - 1 } else { tick_next };
+
+        // Leaving this array: walk into the adjacent one if the caller supplied it
+        let leaving_array = !initialized || (zero_for_one && tick_next == array_start);
+        if leaving_array && amount_remaining > 0 && sqrt_price_x64 != sqrt_price_limit_x64 {
+            if tick_next <= MIN_TICK || tick_next >= MAX_TICK || array_slot >= extra_tick_arrays.len() {
+                out_of_tick_arrays = true;
+                break;
+            }
+            let next_start = if zero_for_one { array_start - span } else { array_start + span };
+            let next_array = Box::new(Account::<TickArray>::try_from(&extra_tick_arrays[array_slot])?);
+            require!(
+                next_array.pool_id == pool.id && next_array.start_tick_index == next_start,
+                AmmError::InvalidTickArray
+            );
+            loaded_array = Some(next_array);
+            array_slot += 1;
+            array_start = next_start;
+        }
+    }
+
+    // Gross up to the fee-inclusive input the user pays
+    let amount_in = if is_base_input && amount_remaining == 0 {
+        amount
     } else {
-        new_sqrt_price.min(sqrt_price_limit_x64)
+        u64::try_from(MathUtil::mul_div_ceil(
+            net_amount_in as u128,
+            FEE_RATE_DENOMINATOR_VALUE as u128,
+            (FEE_RATE_DENOMINATOR_VALUE - fee_rate) as u128,
+        )?)
+        .map_err(|_| AmmError::Overflow)?
     };
-    
-    // Calculate new tick
-    let new_tick = MathUtil::sqrt_price_x64_to_tick(final_sqrt_price)?;
-    
-    Ok((amount_in, amount_out, final_sqrt_price, new_tick))
-}
\ No newline at end of file
+
+    let trade_fee = amount_in
+        .checked_sub(net_amount_in)
+        .ok_or(AmmError::Underflow)?;
+
+    // Credit each step's share of the LP fee to the liquidity it traded against; the last
+    // step with input takes the rounding remainder
+    let lp_fee = calculate_swap_fees(pool, amount_in, trade_fee)?.lp_fee;
+    let last_fee_step = steps
+        .iter()
+        .rposition(|(step_amount_in, step_liquidity)| *step_amount_in > 0 && *step_liquidity > 0);
+    let mut fee_growth_global_x64 = if zero_for_one {
+        pool.fee_growth_global_a_x64
+    } else {
+        pool.fee_growth_global_b_x64
+    };
+    let mut fee_growth_after_step = Vec::with_capacity(steps.len());
+    let mut lp_fee_assigned: u64 = 0;
+    let mut lp_fee_credited: u64 = 0;
+    for (index, (step_amount_in, step_liquidity)) in steps.iter().enumerate() {
+        let step_lp_fee = match last_fee_step {
+            Some(last) if index == last => lp_fee
+                .checked_sub(lp_fee_assigned)
+                .ok_or(AmmError::Underflow)?,
+            Some(last) if index < last && *step_liquidity > 0 => {
+                MathUtil::mul_div_floor(lp_fee as u128, *step_amount_in as u128, net_amount_in as u128)? as u64
+            }
+            _ => 0,
+        };
+        lp_fee_assigned = lp_fee_assigned
+            .checked_add(step_lp_fee)
+            .ok_or(AmmError::Overflow)?;
+
+        if *step_liquidity > 0 {
+            let fee_growth_delta_x64 = (step_lp_fee as u128)
+                .checked_mul(Q64)
+                .and_then(|x| x.checked_div(*step_liquidity))
+                .ok_or(AmmError::Overflow)?;
+            if fee_growth_delta_x64 > 0 {
+                fee_growth_global_x64 = fee_growth_global_x64.wrapping_add(fee_growth_delta_x64);
+                lp_fee_credited = lp_fee_credited
+                    .checked_add(step_lp_fee)
+                    .ok_or(AmmError::Overflow)?;
+            }
+        }
+        fee_growth_after_step.push(fee_growth_global_x64);
+    }
+
+    // A tick flips against the fee growth reached by the steps before it
+    let crossed_ticks = crossings
+        .into_iter()
+        .map(|(slot, tick_index, steps_taken)| (slot, tick_index, fee_growth_after_step[steps_taken - 1]))
+        .collect();
+
+    Ok(SwapComputation {
+        amount_in,
+        trade_fee,
+        amount_out,
+        sqrt_price_x64,
+        tick,
+        liquidity,
+        fee_growth_global_x64,
+        lp_fee_credited,
+        crossed_ticks,
+        is_partial_fill: amount_remaining > 0 || out_of_tick_arrays,
+    })
+}
+
+/// Flip fee and reward growth outside on every tick a swap crossed, persisting extra tick arrays.
+/// Input-side fee growth is the value recorded when the tick was crossed; the output side
+/// does not change during a swap.
+pub(crate) fn cross_ticks<'a>(
+    pool: &mut Pool,
+    tick_array: &mut TickArray,
+    extra_tick_arrays: &'a [AccountInfo<'a>],
+    crossed_ticks: &[(usize, i32, u128)],
+    zero_for_one: bool,
+    timestamp: i64,
+) -> Result<()> {
+    // Rewards accrue up to now under the pre-swap liquidity before any tick flips
+    let pool_liquidity = pool.liquidity;
+    for reward_info in pool.reward_infos.iter_mut() {
+        update_reward_growth(reward_info, pool_liquidity, timestamp as u64)?;
+    }
+    let reward_growths_global_x64 = [
+        pool.reward_infos[0].growth_global_x64,
+        pool.reward_infos[1].growth_global_x64,
+        pool.reward_infos[2].growth_global_x64,
+    ];
+
+    let mut index = 0;
+    while index < crossed_ticks.len() {
+        let slot = crossed_ticks[index].0;
+        let group_end = crossed_ticks[index..]
+            .iter()
+            .position(|(other_slot, _, _)| *other_slot != slot)
+            .map_or(crossed_ticks.len(), |offset| index + offset);
+        let fee_growths_x64 = |fee_growth_input_x64: u128| if zero_for_one {
+            (fee_growth_input_x64, pool.fee_growth_global_b_x64)
+        } else {
+            (pool.fee_growth_global_a_x64, fee_growth_input_x64)
+        };
+
+        if slot == 0 {
+            for (_, tick_index, fee_growth_input_x64) in &crossed_ticks[index..group_end] {
+                let (fee_growth_a_x64, fee_growth_b_x64) = fee_growths_x64(*fee_growth_input_x64);
+                tick_array
+                    .get_tick_mut(*tick_index, pool.tick_spacing)?
+                    .cross(fee_growth_a_x64, fee_growth_b_x64, &reward_growths_global_x64);
+            }
+        } else {
+            let mut extra_array = Box::new(Account::<TickArray>::try_from(&extra_tick_arrays[slot - 1])?);
+            for (_, tick_index, fee_growth_input_x64) in &crossed_ticks[index..group_end] {
+                let (fee_growth_a_x64, fee_growth_b_x64) = fee_growths_x64(*fee_growth_input_x64);
+                extra_array
+                    .get_tick_mut(*tick_index, pool.tick_spacing)?
+                    .cross(fee_growth_a_x64, fee_growth_b_x64, &reward_growths_global_x64);
+            }
+            extra_array.exit(&crate::ID)?;
+        }
+
+        index = group_end;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::state::Tick;
+
+    /// Live pool with `liquidity` in range at `tick`, default fee settings and tick spacing 10
+    pub(crate) fn pool_at_tick(tick: i32, liquidity: u128) -> Pool {
+        Pool {
+            id: Pubkey::new_unique(),
+            sqrt_price_x64: MathUtil::tick_to_sqrt_price_x64(tick).unwrap(),
+            tick_current: tick,
+            tick_spacing: TICK_SPACING_10,
+            status: POOL_STATUS_INITIALIZED,
+            trade_fee_rate: DEFAULT_TRADE_FEE_RATE,
+            protocol_fee_rate: DEFAULT_PROTOCOL_FEE_RATE,
+            liquidity,
+            liquidity_reserve_a: 1_000_000_000_000,
+            liquidity_reserve_b: 1_000_000_000_000,
+            platform_fee_basis_points: PLATFORM_FEE_BASIS_POINTS,
+            creator_fee_basis_points: CREATOR_FEE_BASIS_POINTS,
+            ..Default::default()
+        }
+    }
+
+    /// Tick array of `pool` with no initialized ticks, covering the pool's current tick
+    pub(crate) fn empty_tick_array(pool: &Pool) -> TickArray {
+        TickArray {
+            start_tick_index: TickArray::start_index_for(pool.tick_current, pool.tick_spacing),
+            ticks: [Tick::default(); TICK_ARRAY_SIZE as usize],
+            initialized_tick_count: 0,
+            pool_id: pool.id,
+            bump: 0,
+        }
+    }
+
+    /// Price and settle an exact-input swap with no price limit, as every swap path does
+    pub(crate) fn execute_swap(pool: &mut Pool, tick_array: &TickArray, amount: u64, zero_for_one: bool) -> SwapFees {
+        let sqrt_price_limit_x64 = if zero_for_one { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 };
+        let computation = calculate_swap(
+            pool,
+            tick_array,
+            &[],
+            amount,
+            sqrt_price_limit_x64,
+            zero_for_one,
+            true,
+            DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS,
+        )
+        .unwrap();
+        let fees = calculate_swap_fees(pool, computation.amount_in, computation.trade_fee).unwrap();
+        apply_swap_to_pool(pool, zero_for_one, &computation, &fees, 0).unwrap();
+        fees
+    }
+
+    #[test]
+    fn lp_fee_is_credited_to_the_liquidity_in_range_at_each_step() {
+        // 2e12 in range above tick 400, half of it ends there
+        let mut pool = pool_at_tick(440, 2_000_000_000_000);
+        let mut tick_array = empty_tick_array(&pool);
+        let tick = tick_array.get_tick_mut(400, pool.tick_spacing).unwrap();
+        tick.initialized = true;
+        tick.liquidity_net = 1_000_000_000_000;
+        tick.liquidity_gross = 1_000_000_000_000;
+
+        let computation = calculate_swap(
+            &pool,
+            &tick_array,
+            &[],
+            6_000_000_000,
+            MIN_SQRT_PRICE_X64,
+            true,
+            true,
+            DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS,
+        )
+        .unwrap();
+        let fees = calculate_swap_fees(&pool, computation.amount_in, computation.trade_fee).unwrap();
+
+        assert_eq!(computation.liquidity, 1_000_000_000_000);
+        assert_eq!(computation.crossed_ticks.len(), 1);
+        let (_, crossed_tick, fee_growth_at_cross_x64) = computation.crossed_ticks[0];
+        assert_eq!(crossed_tick, 400);
+        // Liquidity above the tick earned its part before the flip
+        assert!(fee_growth_at_cross_x64 > 0);
+        assert!(computation.fee_growth_global_x64 > fee_growth_at_cross_x64);
+
+        // Each step's growth times the liquidity it traded against adds back up to the LP fee
+        let earned_before_cross = fee_growth_at_cross_x64 * 2_000_000_000_000 / Q64;
+        let earned_after_cross = (computation.fee_growth_global_x64 - fee_growth_at_cross_x64) * 1_000_000_000_000 / Q64;
+        let earned = (earned_before_cross + earned_after_cross) as u64;
+        assert!(earned <= fees.lp_fee && fees.lp_fee - earned <= 2);
+        assert_eq!(computation.lp_fee_credited, fees.lp_fee);
+
+        // The tick flips against the growth reached when it was crossed, not the final one
+        cross_ticks(&mut pool, &mut tick_array, &[], &computation.crossed_ticks, true, 0).unwrap();
+        apply_swap_to_pool(&mut pool, true, &computation, &fees, 0).unwrap();
+        let tick = tick_array.get_tick(400, pool.tick_spacing).unwrap();
+        assert_eq!(tick.fee_growth_outside_a_x64, fee_growth_at_cross_x64);
+        assert_eq!(pool.fee_growth_global_a_x64, computation.fee_growth_global_x64);
+    }
+
+    #[test]
+    fn vault_balance_matches_accounted_amounts_after_a_series_of_swaps() {
+        let mut pool = pool_at_tick(440, 1_000_000_000_000);
+        let tick_array = empty_tick_array(&pool);
+        let (mut vault_a, mut vault_b) = (pool.liquidity_reserve_a, pool.liquidity_reserve_b);
+
+        for (amount, zero_for_one) in [(1_000_000, true), (2_500_000, false), (40_000, true), (7_777_777, false), (123_456, true)] {
+            let (reserve_a, reserve_b) = (pool.liquidity_reserve_a, pool.liquidity_reserve_b);
+            let fees = execute_swap(&mut pool, &tick_array, amount, zero_for_one);
+            let paid_out = fees.protocol_fee + fees.platform_fee + fees.creator_fee;
+
+            // Input arrives in one vault less the shares paid out; output leaves the other
+            if zero_for_one {
+                vault_a += amount - paid_out;
+                vault_b -= reserve_b - pool.liquidity_reserve_b;
+            } else {
+                vault_b += amount - paid_out;
+                vault_a -= reserve_a - pool.liquidity_reserve_a;
+            }
+        }
+
+        // Every token left in the vaults is owed to someone, so sweep_dust finds nothing
+        assert_eq!(pool.accounted_vault_amounts().unwrap(), (vault_a, vault_b));
+
+        // A direct transfer into a vault is exactly what sweep_dust would take
+        let (owned_a, _) = pool.accounted_vault_amounts().unwrap();
+        assert_eq!((vault_a + 5_000).saturating_sub(owned_a), 5_000);
+    }
+
+    #[test]
+    fn unmigrated_v1_global_swaps_with_the_full_fee() {
+        let amm_global = crate::state::tests::unmigrated_amm_global(Pubkey::new_unique());
+        let pool = pool_at_tick(440, 1_000_000_000_000);
+        let tick_array = empty_tick_array(&pool);
+        let quote = |fee_multiplier_bps| {
+            calculate_swap(&pool, &tick_array, &[], 1_000_000, MIN_SQRT_PRICE_X64, true, true, fee_multiplier_bps).unwrap()
+        };
+
+        // The zeroed v1 multiplier would have priced this swap fee-free
+        let computation = quote(amm_global.fee_multiplier_bps());
+        assert_eq!(computation.trade_fee, 2_500);
+        assert_eq!(computation.trade_fee, quote(DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS).trade_fee);
+        assert_eq!(quote(amm_global.global_fee_multiplier_bps).trade_fee, 0);
+    }
+
+    #[test]
+    fn swap_vaults_must_be_the_pools_two_sides_in_either_role() {
+        let mut pool = pool_at_tick(440, 1_000_000_000_000);
+        let (vault_a, vault_b, foreign_vault) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        pool.vault_a = vault_a;
+        pool.vault_b = vault_b;
+
+        assert_eq!(pool.swap_direction(vault_a, vault_b), Some(true));
+        assert_eq!(pool.swap_direction(vault_b, vault_a), Some(false));
+
+        // Same-side pairs or a vault from another pool cannot fake a direction
+        for (input_vault, output_vault) in [
+            (vault_a, vault_a),
+            (vault_b, vault_b),
+            (vault_a, foreign_vault),
+            (foreign_vault, vault_b),
+        ] {
+            assert_eq!(pool.swap_direction(input_vault, output_vault), None);
+        }
+    }
+
+    #[test]
+    fn swaps_need_min_liquidity_in_range() {
+        let quote = |liquidity| {
+            let pool = pool_at_tick(440, liquidity);
+            let tick_array = empty_tick_array(&pool);
+            calculate_swap(&pool, &tick_array, &[], 10, MIN_SQRT_PRICE_X64, true, true, DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS)
+                .map(|computation| computation.amount_in)
+        };
+
+        // Dust liquidity would let a tiny trade push the price arbitrarily far
+        assert_eq!(quote(MIN_LIQUIDITY - 1).unwrap_err(), AmmError::InsufficientLiquidity.into());
+        assert_eq!(quote(0).unwrap_err(), AmmError::InsufficientLiquidity.into());
+        assert!(quote(MIN_LIQUIDITY).is_ok());
+    }
+
+    #[test]
+    fn swaps_past_their_deadline_are_rejected() {
+        let now = 1_700_000_000;
+        assert!(require_not_expired(None, now).is_ok());
+        assert!(require_not_expired(Some(now), now).is_ok());
+        assert!(require_not_expired(Some(now + 30), now).is_ok());
+        assert_eq!(
+            require_not_expired(Some(now - 1), now).unwrap_err(),
+            AmmError::TransactionExpired.into()
+        );
+    }
+
+    #[test]
+    fn swap_fee_shares_add_up_to_the_trade_fee() {
+        let pool = pool_at_tick(440, 1_000_000_000_000);
+        let fees = calculate_swap_fees(&pool, 1_000_000, 2_500).unwrap();
+
+        assert_eq!(fees.net_amount_in, 997_500);
+        assert_eq!(fees.platform_fee, 75);
+        assert_eq!(fees.creator_fee, 25);
+        assert_eq!(
+            fees.protocol_fee + fees.platform_fee + fees.creator_fee + fees.lp_fee,
+            fees.trade_fee
+        );
+    }
+}
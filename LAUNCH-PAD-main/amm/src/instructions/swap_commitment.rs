@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, state::{AmmGlobal, Pool, SwapCommitment}, events::*, errors::*};
+use super::swap::{swap, Swap, SwapBumps, __client_accounts_swap, __cpi_client_accounts_swap};
+
+#[derive(Accounts)]
+pub struct CommitSwap<'info> {
+    #[account(
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(
+        constraint = pool.allows_swaps()
+            @ AmmError::PoolDisabled
+    )]
+    pub pool: Account<'info, Pool>,
+
+    /// One pending commitment per user and pool
+    #[account(
+        init,
+        payer = user,
+        space = SwapCommitment::LEN,
+        seeds = [SWAP_COMMITMENT_SEED, pool.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevealSwap<'info> {
+    /// Commitment being revealed; closed to the user before the swap executes
+    #[account(
+        mut,
+        seeds = [SWAP_COMMITMENT_SEED, swap.pool.key().as_ref(), swap.user.key().as_ref()],
+        bump = swap_commitment.bump
+    )]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+
+    /// Same accounts as a direct swap
+    pub swap: Swap<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CancelSwapCommitment<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [SWAP_COMMITMENT_SEED, swap_commitment.pool.as_ref(), user.key().as_ref()],
+        bump = swap_commitment.bump,
+        constraint = swap_commitment.user == user.key()
+            @ AmmError::UnauthorizedAccess
+    )]
+    pub swap_commitment: Account<'info, SwapCommitment>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+/// Record a hash of the swap parameters; see `SwapCommitment::hash_params` for the layout
+pub fn commit_swap(ctx: Context<CommitSwap>, commitment: [u8; 32]) -> Result<()> {
+    let swap_commitment = &mut ctx.accounts.swap_commitment;
+    let clock = Clock::get()?;
+
+    swap_commitment.pool = ctx.accounts.pool.key();
+    swap_commitment.user = ctx.accounts.user.key();
+    swap_commitment.commitment = commitment;
+    swap_commitment.committed_slot = clock.slot;
+    swap_commitment.bump = ctx.bumps.swap_commitment;
+
+    emit!(SwapCommittedEvent {
+        pool_id: swap_commitment.pool,
+        user: swap_commitment.user,
+        commitment,
+        committed_slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔒 Swap committed");
+    msg!("Pool: {}", swap_commitment.pool);
+    msg!("Slot: {}", clock.slot);
+
+    Ok(())
+}
+
+/// Execute a committed swap in a later slot. The parameters and vault direction must
+/// hash to the stored commitment; the swap then runs exactly as `swap` would.
+pub fn reveal_swap<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevealSwap<'info>>,
+    amount: u64,
+    other_amount_threshold: u64,
+    sqrt_price_limit_x64: u128,
+    is_base_input: bool,
+    nonce: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let swap_commitment = &ctx.accounts.swap_commitment;
+    let committed_slot = swap_commitment.committed_slot;
+
+    // The intent must not be executable in the slot it was published in
+    require!(clock.slot > committed_slot, AmmError::SwapRevealTooEarly);
+
+    let zero_for_one = ctx.accounts.swap.pool
+        .swap_direction(ctx.accounts.swap.input_vault.key(), ctx.accounts.swap.output_vault.key())
+        .ok_or(AmmError::InvalidTokenAccount)?;
+    let revealed = SwapCommitment::hash_params(
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+        is_base_input,
+        zero_for_one,
+        nonce,
+    );
+    require!(revealed == swap_commitment.commitment, AmmError::SwapCommitmentMismatch);
+
+    // Consume the commitment first so it can never be replayed
+    ctx.accounts.swap_commitment.close(ctx.accounts.swap.user.to_account_info())?;
+
+    let swap_ctx = Context::new(
+        ctx.program_id,
+        &mut ctx.accounts.swap,
+        ctx.remaining_accounts,
+        SwapBumps::default(),
+    );
+    swap(
+        swap_ctx,
+        amount,
+        other_amount_threshold,
+        sqrt_price_limit_x64,
+        is_base_input,
+        false,
+        false,
+        None,
+    )?;
+
+    emit!(SwapRevealedEvent {
+        pool_id: ctx.accounts.swap.pool.key(),
+        user: ctx.accounts.swap.user.key(),
+        committed_slot,
+        revealed_slot: clock.slot,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔓 Committed swap revealed");
+    msg!("Committed Slot: {}", committed_slot);
+    msg!("Revealed Slot: {}", clock.slot);
+
+    Ok(())
+}
+
+pub fn cancel_swap_commitment(ctx: Context<CancelSwapCommitment>) -> Result<()> {
+    // Rent goes back to the user; a new commitment can be made afterwards
+    msg!("🔒 Swap commitment cancelled for: {}", ctx.accounts.user.key());
+
+    Ok(())
+}
@@ -0,0 +1,326 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{self, Token, TokenAccount, Transfer},
+};
+use crate::{constants::*, state::{AmmGlobal, Pool, TickArray}, events::*, errors::*};
+use super::swap::{apply_swap_to_pool, calculate_swap, calculate_swap_fees, cross_ticks, pay_out_swap_fees, SwapComputation, SwapFees};
+
+/// Two-hop exact output route: input -> first pool -> second pool -> output
+#[derive(Accounts)]
+pub struct SwapRouterExactOut<'info> {
+    #[account(
+        mut,
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Box<Account<'info, AmmGlobal>>,
+
+    #[account(
+        mut,
+        constraint = first_pool.allows_swaps()
+            @ AmmError::PoolDisabled
+    )]
+    pub first_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = first_input_vault.key() == first_pool.vault_a || first_input_vault.key() == first_pool.vault_b
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub first_input_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = first_output_vault.key() == first_pool.vault_a || first_output_vault.key() == first_pool.vault_b
+            @ AmmError::InvalidTokenAccount,
+        constraint = first_output_vault.key() != first_input_vault.key()
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub first_output_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = first_tick_array.pool_id == first_pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub first_tick_array: Box<Account<'info, TickArray>>,
+
+    #[account(
+        mut,
+        constraint = second_pool.key() != first_pool.key()
+            @ AmmError::InvalidSwapRoute,
+        constraint = second_pool.allows_swaps()
+            @ AmmError::PoolDisabled
+    )]
+    pub second_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        constraint = second_input_vault.key() == second_pool.vault_a || second_input_vault.key() == second_pool.vault_b
+            @ AmmError::InvalidTokenAccount,
+        constraint = second_input_vault.mint == first_output_vault.mint
+            @ AmmError::InvalidSwapRoute
+    )]
+    pub second_input_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = second_output_vault.key() == second_pool.vault_a || second_output_vault.key() == second_pool.vault_b
+            @ AmmError::InvalidTokenAccount,
+        constraint = second_output_vault.key() != second_input_vault.key()
+            @ AmmError::InvalidTokenAccount
+    )]
+    pub second_output_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = second_tick_array.pool_id == second_pool.key()
+            @ AmmError::InvalidTickArray
+    )]
+    pub second_tick_array: Box<Account<'info, TickArray>>,
+
+    /// User's input token account for the first hop
+    #[account(
+        mut,
+        constraint = input_token_account.owner == user.key()
+            @ AmmError::InvalidAccountOwner,
+        constraint = input_token_account.mint == first_input_vault.mint
+            @ AmmError::InvalidSwapRoute
+    )]
+    pub input_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// User's output token account for the second hop
+    #[account(
+        mut,
+        constraint = output_token_account.owner == user.key()
+            @ AmmError::InvalidAccountOwner,
+        constraint = output_token_account.mint == second_output_vault.mint
+            @ AmmError::InvalidSwapRoute
+    )]
+    pub output_token_account: Box<Account<'info, TokenAccount>>,
+
+    /// Platform fee collection wallet (multi-sig controlled)
+    /// CHECK: Validated against global configuration in pay_out_swap_fees
+    #[account(mut)]
+    pub platform_wallet: UncheckedAccount<'info>,
+
+    /// Creator fee wallet of the first pool
+    /// CHECK: Validated against pool and global configuration in pay_out_swap_fees
+    #[account(mut)]
+    pub first_creator_wallet: UncheckedAccount<'info>,
+
+    /// Creator fee wallet of the second pool
+    /// CHECK: Validated against pool and global configuration in pay_out_swap_fees
+    #[account(mut)]
+    pub second_creator_wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn swap_router_exact_out(
+    ctx: Context<SwapRouterExactOut>,
+    desired_amount_out: u64,
+    max_amount_in: u64,
+) -> Result<()> {
+    let fee_multiplier_bps = ctx.accounts.amm_global.fee_multiplier_bps();
+    let clock = Clock::get()?;
+
+    // Validate amounts
+    require!(desired_amount_out > 0, AmmError::InvalidTokenAmount);
+    require!(max_amount_in > 0, AmmError::InvalidTokenAmount);
+
+    let first_zero_for_one = ctx.accounts.first_input_vault.key() == ctx.accounts.first_pool.vault_a;
+    let second_zero_for_one = ctx.accounts.second_input_vault.key() == ctx.accounts.second_pool.vault_a;
+
+    // Solve in reverse: the second hop's required input is the first hop's exact output
+    let second_computation = calculate_swap(
+        &ctx.accounts.second_pool,
+        &ctx.accounts.second_tick_array,
+        &[],
+        desired_amount_out,
+        price_limit(second_zero_for_one),
+        second_zero_for_one,
+        false,
+        fee_multiplier_bps,
+    )?;
+    require!(!second_computation.is_partial_fill, AmmError::InsufficientLiquidity);
+
+    let intermediate_amount = second_computation.amount_in;
+    let first_computation = calculate_swap(
+        &ctx.accounts.first_pool,
+        &ctx.accounts.first_tick_array,
+        &[],
+        intermediate_amount,
+        price_limit(first_zero_for_one),
+        first_zero_for_one,
+        false,
+        fee_multiplier_bps,
+    )?;
+    require!(!first_computation.is_partial_fill, AmmError::InsufficientLiquidity);
+
+    // Slippage protection on the total input
+    let total_amount_in = first_computation.amount_in;
+    require!(total_amount_in <= max_amount_in, AmmError::SlippageExceeded);
+    require!(
+        ctx.accounts.input_token_account.amount >= total_amount_in,
+        AmmError::InsufficientTokenBalance
+    );
+
+    // Execute forward: user -> first pool -> second pool -> user
+    let transfer_input_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.input_token_account.to_account_info(),
+            to: ctx.accounts.first_input_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_input_ctx, total_amount_in)?;
+
+    {
+        let first_pool = &ctx.accounts.first_pool;
+        let pool_seeds = &[
+            POOL_SEED,
+            first_pool.mint_a.as_ref(),
+            first_pool.mint_b.as_ref(),
+            &[first_pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let transfer_intermediate_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.first_output_vault.to_account_info(),
+                to: ctx.accounts.second_input_vault.to_account_info(),
+                authority: first_pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_intermediate_ctx, first_computation.amount_out)?;
+    }
+
+    {
+        let second_pool = &ctx.accounts.second_pool;
+        let pool_seeds = &[
+            POOL_SEED,
+            second_pool.mint_a.as_ref(),
+            second_pool.mint_b.as_ref(),
+            &[second_pool.bump],
+        ];
+        let pool_signer = &[&pool_seeds[..]];
+
+        let transfer_output_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.second_output_vault.to_account_info(),
+                to: ctx.accounts.output_token_account.to_account_info(),
+                authority: second_pool.to_account_info(),
+            },
+            pool_signer,
+        );
+        token::transfer(transfer_output_ctx, second_computation.amount_out)?;
+    }
+
+    let first_fees = settle_hop(
+        &mut ctx.accounts.first_pool,
+        &mut ctx.accounts.first_tick_array,
+        first_zero_for_one,
+        &first_computation,
+        clock.unix_timestamp,
+    )?;
+    let second_fees = settle_hop(
+        &mut ctx.accounts.second_pool,
+        &mut ctx.accounts.second_tick_array,
+        second_zero_for_one,
+        &second_computation,
+        clock.unix_timestamp,
+    )?;
+
+    // Pay each hop's platform and creator shares out of its input vault, as swap does
+    for (pool, input_vault, creator_wallet, fees) in [
+        (&ctx.accounts.first_pool, &ctx.accounts.first_input_vault, &ctx.accounts.first_creator_wallet, &first_fees),
+        (&ctx.accounts.second_pool, &ctx.accounts.second_input_vault, &ctx.accounts.second_creator_wallet, &second_fees),
+    ] {
+        pay_out_swap_fees(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.amm_global,
+            pool,
+            &input_vault.to_account_info(),
+            &ctx.accounts.platform_wallet.to_account_info(),
+            &creator_wallet.to_account_info(),
+            fees,
+        )?;
+    }
+
+    for (pool, input_vault, output_vault, computation, fees) in [
+        (&ctx.accounts.first_pool, &ctx.accounts.first_input_vault, &ctx.accounts.first_output_vault, &first_computation, &first_fees),
+        (&ctx.accounts.second_pool, &ctx.accounts.second_input_vault, &ctx.accounts.second_output_vault, &second_computation, &second_fees),
+    ] {
+        emit!(SwapEvent {
+            pool_id: pool.key(),
+            user: ctx.accounts.user.key(),
+            input_mint: input_vault.mint,
+            output_mint: output_vault.mint,
+            input_amount: computation.amount_in,
+            output_amount: computation.amount_out,
+            fee_amount: fees.trade_fee,
+            sqrt_price_x64: pool.sqrt_price_x64,
+            tick_current: pool.tick_current,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Update global volume tracking
+    let total_fees = first_fees.trade_fee
+        .checked_add(second_fees.trade_fee)
+        .ok_or(AmmError::Overflow)?;
+    let amm_global = &mut ctx.accounts.amm_global;
+    amm_global.total_volume = amm_global.total_volume
+        .checked_add(first_computation.amount_in)
+        .and_then(|x| x.checked_add(second_computation.amount_in))
+        .ok_or(AmmError::Overflow)?;
+    amm_global.total_fees_collected = amm_global.total_fees_collected
+        .checked_add(total_fees)
+        .ok_or(AmmError::Overflow)?;
+
+    emit!(BatchSwapEvent {
+        user: ctx.accounts.user.key(),
+        input_mint: ctx.accounts.input_token_account.mint,
+        output_mint: ctx.accounts.output_token_account.mint,
+        input_amount: total_amount_in,
+        output_amount: second_computation.amount_out,
+        hops: 2,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🎯 Exact output route executed successfully");
+    msg!("Output Amount: {} tokens", second_computation.amount_out);
+    msg!("Intermediate Amount: {} tokens", intermediate_amount);
+    msg!("Input Amount: {} tokens", total_amount_in);
+    msg!("Total Trade Fees: {} tokens", total_fees);
+
+    Ok(())
+}
+
+fn price_limit(zero_for_one: bool) -> u128 {
+    if zero_for_one { MIN_SQRT_PRICE_X64 } else { MAX_SQRT_PRICE_X64 }
+}
+
+// Crosses ticks and books fees for one hop; the caller pays out the fee shares
+fn settle_hop(
+    pool: &mut Pool,
+    tick_array: &mut TickArray,
+    zero_for_one: bool,
+    computation: &SwapComputation,
+    timestamp: i64,
+) -> Result<SwapFees> {
+    let fees = calculate_swap_fees(pool, computation.amount_in, computation.trade_fee)?;
+
+    cross_ticks(pool, tick_array, &[], &computation.crossed_ticks, zero_for_one, timestamp)?;
+    apply_swap_to_pool(pool, zero_for_one, computation, &fees, timestamp)?;
+
+    Ok(fees)
+}
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use crate::{constants::*, state::{AmmGlobal, UserVolumeAccumulator}, events::*, errors::*};
+
+#[derive(Accounts)]
+pub struct InitUserVolumeAccumulator<'info> {
+    #[account(
+        constraint = !amm_global.is_paused
+            @ AmmError::OperationsPaused
+    )]
+    pub amm_global: Account<'info, AmmGlobal>,
+
+    #[account(
+        init,
+        payer = user,
+        space = UserVolumeAccumulator::LEN,
+        seeds = [USER_VOLUME_SEED, user.key().as_ref()],
+        bump
+    )]
+    pub user_volume_accumulator: Account<'info, UserVolumeAccumulator>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_user_volume_accumulator(ctx: Context<InitUserVolumeAccumulator>) -> Result<()> {
+    let user_volume = &mut ctx.accounts.user_volume_accumulator;
+    let clock = Clock::get()?;
+
+    // Initialize user swap tracking
+    user_volume.user = ctx.accounts.user.key();
+    user_volume.volume_a = 0;
+    user_volume.volume_b = 0;
+    user_volume.fees_paid_a = 0;
+    user_volume.fees_paid_b = 0;
+    user_volume.swaps_count = 0;
+    user_volume.last_swap_timestamp = 0;
+    user_volume.bump = ctx.bumps.user_volume_accumulator;
+
+    emit!(UserVolumeAccumulatorInitializedEvent {
+        user: ctx.accounts.user.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 User volume accumulator initialized for: {}", ctx.accounts.user.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseUserVolumeAccumulator<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_VOLUME_SEED, user.key().as_ref()],
+        bump = user_volume_accumulator.bump,
+        constraint = user_volume_accumulator.user == user.key()
+            @ AmmError::UnauthorizedAccess
+    )]
+    pub user_volume_accumulator: Account<'info, UserVolumeAccumulator>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn close_user_volume_accumulator(ctx: Context<CloseUserVolumeAccumulator>) -> Result<()> {
+    let user_volume = &ctx.accounts.user_volume_accumulator;
+    let clock = Clock::get()?;
+
+    // Rent goes back to the user; swap history restarts on re-init
+    emit!(UserVolumeAccumulatorClosedEvent {
+        user: ctx.accounts.user.key(),
+        volume_a: user_volume.volume_a,
+        volume_b: user_volume.volume_b,
+        swaps_count: user_volume.swaps_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 User volume accumulator closed for: {}", ctx.accounts.user.key());
+
+    Ok(())
+}
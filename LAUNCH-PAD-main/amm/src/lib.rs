@@ -1,3 +1,7 @@
+// Anchor 0.31's generated IDL instructions call the deprecated AccountInfo::realloc
+// at crate level, outside anything a narrower allow could cover
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 // SPL imports removed - not needed for core AMM functionality
 
@@ -17,8 +21,11 @@ pub mod amm {
     use super::*;
 
     /// Initialize AMM global configuration with multi-sig authority
-    pub fn initialize_amm_global(ctx: Context<InitializeAmmGlobal>) -> Result<()> {
-        instructions::initialize_amm_global(ctx)
+    pub fn initialize_amm_global(
+        ctx: Context<InitializeAmmGlobal>,
+        config: state::AmmGlobalConfig,
+    ) -> Result<()> {
+        instructions::initialize_amm_global(ctx, config)
     }
 
     /// Create concentrated liquidity pool (requires multi-sig)
@@ -26,8 +33,18 @@ pub mod amm {
         ctx: Context<CreatePool>,
         sqrt_price_x64: u128,
         tick_spacing: u16,
+        platform_fee_basis_points: Option<u16>,
+        creator_fee_basis_points: Option<u16>,
+        token_creator: Option<Pubkey>,
     ) -> Result<()> {
-        instructions::create_pool(ctx, sqrt_price_x64, tick_spacing)
+        instructions::create_pool(
+            ctx,
+            sqrt_price_x64,
+            tick_spacing,
+            platform_fee_basis_points,
+            creator_fee_basis_points,
+            token_creator,
+        )
     }
 
     /// Initialize liquidity position NFT
@@ -39,14 +56,34 @@ pub mod amm {
         instructions::open_position(ctx, tick_lower, tick_upper)
     }
 
+    /// Open a position NFT and add liquidity to it atomically
+    pub fn open_position_with_liquidity(
+        ctx: Context<OpenPositionWithLiquidity>,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: u128,
+        amount0_max: u64,
+        amount1_max: u64,
+    ) -> Result<()> {
+        instructions::open_position_with_liquidity(
+            ctx,
+            tick_lower,
+            tick_upper,
+            liquidity_delta,
+            amount0_max,
+            amount1_max,
+        )
+    }
+
     /// Add liquidity to position
     pub fn increase_liquidity(
         ctx: Context<IncreaseLiquidity>,
         liquidity_delta: u128,
         amount0_max: u64,
         amount1_max: u64,
+        deadline: Option<i64>,
     ) -> Result<()> {
-        instructions::increase_liquidity(ctx, liquidity_delta, amount0_max, amount1_max)
+        instructions::increase_liquidity(ctx, liquidity_delta, amount0_max, amount1_max, deadline)
     }
 
     /// Remove liquidity from position
@@ -55,19 +92,158 @@ pub mod amm {
         liquidity_delta: u128,
         amount0_min: u64,
         amount1_min: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::decrease_liquidity(ctx, liquidity_delta, amount0_min, amount1_min, deadline)
+    }
+
+    /// Withdraw liquidity while operations are paused
+    pub fn emergency_decrease_liquidity(
+        ctx: Context<EmergencyDecreaseLiquidity>,
+        liquidity_delta: u128,
     ) -> Result<()> {
-        instructions::decrease_liquidity(ctx, liquidity_delta, amount0_min, amount1_min)
+        instructions::emergency_decrease_liquidity(ctx, liquidity_delta)
+    }
+
+    /// Move all of a position's liquidity to a new tick range atomically
+    #[allow(clippy::too_many_arguments)] // Instruction arguments map 1:1 to the IDL
+    pub fn rebalance_position(
+        ctx: Context<RebalancePosition>,
+        new_tick_lower: i32,
+        new_tick_upper: i32,
+        new_liquidity: u128,
+        amount0_min: u64,
+        amount1_min: u64,
+        amount0_max: u64,
+        amount1_max: u64,
+    ) -> Result<()> {
+        instructions::rebalance_position(
+            ctx,
+            new_tick_lower,
+            new_tick_upper,
+            new_liquidity,
+            amount0_min,
+            amount1_min,
+            amount0_max,
+            amount1_max,
+        )
+    }
+
+    /// Initialize per-user swap stats (optional - swap updates it when passed)
+    pub fn init_user_volume_accumulator(ctx: Context<InitUserVolumeAccumulator>) -> Result<()> {
+        instructions::init_user_volume_accumulator(ctx)
+    }
+
+    /// Close the user's swap stats account and reclaim rent
+    pub fn close_user_volume_accumulator(ctx: Context<CloseUserVolumeAccumulator>) -> Result<()> {
+        instructions::close_user_volume_accumulator(ctx)
+    }
+
+    /// Consolidated pool snapshot for indexers, returned via return data
+    pub fn get_pool_state(ctx: Context<GetPoolState>) -> Result<state::PoolStateView> {
+        instructions::get_pool_state(ctx)
+    }
+
+    /// Emit a pool fee growth snapshot once the snapshot interval has elapsed
+    pub fn poke_fee_growth_snapshot(ctx: Context<PokeFeeGrowthSnapshot>) -> Result<()> {
+        instructions::poke_fee_growth_snapshot(ctx)
+    }
+
+    /// Preview a position's withdrawable amounts and uncollected fees
+    pub fn get_position_amounts(ctx: Context<GetPositionAmounts>) -> Result<()> {
+        instructions::get_position_amounts(ctx)
     }
 
     /// Swap tokens in the pool
-    pub fn swap(
-        ctx: Context<Swap>,
+    #[allow(clippy::too_many_arguments)] // Instruction arguments map 1:1 to the IDL
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Swap<'info>>,
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        wrap_sol: bool,
+        unwrap_sol: bool,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::swap(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+            wrap_sol,
+            unwrap_sol,
+            deadline,
+        )
+    }
+
+    /// Preview a swap without executing it
+    pub fn quote_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QuoteSwap<'info>>,
+        amount: u64,
+        sqrt_price_limit_x64: u128,
+        zero_for_one: bool,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::quote_swap(ctx, amount, sqrt_price_limit_x64, zero_for_one, is_base_input)
+    }
+
+    /// Swap through a route of pools passed in remaining accounts
+    pub fn batch_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchSwap<'info>>,
+        amount_in: u64,
+        other_amount_threshold: u64,
+        hop_minimum_amounts_out: Vec<u64>,
+    ) -> Result<()> {
+        instructions::batch_swap(ctx, amount_in, other_amount_threshold, hop_minimum_amounts_out)
+    }
+
+    /// Borrow swap output, run a callback program, and require repayment plus fee
+    pub fn flash_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlashSwap<'info>>,
+        amount: u64,
+        is_base_input: bool,
+    ) -> Result<()> {
+        instructions::flash_swap(ctx, amount, is_base_input)
+    }
+
+    /// Commit to hidden swap parameters, to be revealed in a later slot
+    pub fn commit_swap(ctx: Context<CommitSwap>, commitment: [u8; 32]) -> Result<()> {
+        instructions::commit_swap(ctx, commitment)
+    }
+
+    /// Execute a previously committed swap
+    pub fn reveal_swap<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealSwap<'info>>,
         amount: u64,
         other_amount_threshold: u64,
         sqrt_price_limit_x64: u128,
         is_base_input: bool,
+        nonce: u64,
     ) -> Result<()> {
-        instructions::swap(ctx, amount, other_amount_threshold, sqrt_price_limit_x64, is_base_input)
+        instructions::reveal_swap(
+            ctx,
+            amount,
+            other_amount_threshold,
+            sqrt_price_limit_x64,
+            is_base_input,
+            nonce,
+        )
+    }
+
+    /// Drop a pending swap commitment and reclaim rent
+    pub fn cancel_swap_commitment(ctx: Context<CancelSwapCommitment>) -> Result<()> {
+        instructions::cancel_swap_commitment(ctx)
+    }
+
+    /// Buy an exact output amount through two pools, solving the route in reverse
+    pub fn swap_router_exact_out(
+        ctx: Context<SwapRouterExactOut>,
+        desired_amount_out: u64,
+        max_amount_in: u64,
+    ) -> Result<()> {
+        instructions::swap_router_exact_out(ctx, desired_amount_out, max_amount_in)
     }
 
     /// Collect fees from position
@@ -79,6 +255,15 @@ pub mod amm {
         instructions::collect_fees(ctx, amount0_requested, amount1_requested)
     }
 
+    /// Collect position fees into any token accounts of the pool mints (position owner signs)
+    pub fn collect_fees_to_account(
+        ctx: Context<CollectFeesToAccount>,
+        amount0_requested: u64,
+        amount1_requested: u64,
+    ) -> Result<()> {
+        instructions::collect_fees_to_account(ctx, amount0_requested, amount1_requested)
+    }
+
     /// Collect protocol fees (multi-sig required)
     pub fn collect_protocol_fees(
         ctx: Context<CollectProtocolFees>,
@@ -88,14 +273,47 @@ pub mod amm {
         instructions::collect_protocol_fees(ctx, amount0, amount1)
     }
 
+    /// Collect protocol fees from several pools in one transaction (multi-sig required)
+    pub fn collect_protocol_fees_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CollectProtocolFeesBatch<'info>>,
+    ) -> Result<()> {
+        instructions::collect_protocol_fees_batch(ctx)
+    }
+
+    /// Sweep unaccounted vault dust to the platform wallet (multi-sig required)
+    pub fn sweep_dust(ctx: Context<SweepDust>) -> Result<()> {
+        instructions::sweep_dust(ctx)
+    }
+
+    /// Rescue tokens held by a pool above its accounted balances (multi-sig required)
+    pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+        instructions::rescue_tokens(ctx)
+    }
+
     /// Update pool fees (multi-sig required)
     pub fn update_pool_fees(
         ctx: Context<UpdatePoolFees>,
         trade_fee_rate: u32,
         protocol_fee_rate: u32,
         fund_fee_rate: u32,
+        volume_fee_tiers: Option<[state::PoolFeeTier; constants::POOL_FEE_TIER_COUNT]>,
+        platform_fee_basis_points: Option<u16>,
+        creator_fee_basis_points: Option<u16>,
     ) -> Result<()> {
-        instructions::update_pool_fees(ctx, trade_fee_rate, protocol_fee_rate, fund_fee_rate)
+        instructions::update_pool_fees(
+            ctx,
+            trade_fee_rate,
+            protocol_fee_rate,
+            fund_fee_rate,
+            volume_fee_tiers,
+            platform_fee_basis_points,
+            creator_fee_basis_points,
+        )
+    }
+
+    /// Set a single pool's status, e.g. POOL_STATUS_DISABLED to pause it (multi-sig required)
+    pub fn set_pool_status(ctx: Context<SetPoolStatus>, status: u8) -> Result<()> {
+        instructions::set_pool_status(ctx, status)
     }
 
     /// Initialize tick array for price ranges
@@ -106,6 +324,18 @@ pub mod amm {
         instructions::initialize_tick_array(ctx, start_tick_index)
     }
 
+    /// Repair a tick array's accounting (multi-sig required)
+    pub fn recompute_tick_array<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RecomputeTickArray<'info>>,
+    ) -> Result<()> {
+        instructions::recompute_tick_array(ctx)
+    }
+
+    /// Close an empty tick array away from the current price and reclaim its rent (multi-sig required)
+    pub fn close_tick_array(ctx: Context<CloseTickArray>) -> Result<()> {
+        instructions::close_tick_array(ctx)
+    }
+
     /// Emergency pause (multi-sig required)
     pub fn emergency_pause_amm(ctx: Context<EmergencyPauseAmm>) -> Result<()> {
         instructions::emergency_pause_amm(ctx)
@@ -116,7 +346,49 @@ pub mod amm {
         instructions::resume_amm_operations(ctx)
     }
 
-    /// Set pool reward (multi-sig required)
+    /// Set the pool creation fee (multi-sig required)
+    pub fn set_create_pool_fee(ctx: Context<SetCreatePoolFee>, new_fee: u64) -> Result<()> {
+        instructions::set_create_pool_fee(ctx, new_fee)
+    }
+
+    /// Scale every pool's trade fee, e.g. 0 for fee-free promotions (multi-sig required)
+    pub fn set_global_fee_multiplier(
+        ctx: Context<SetGlobalFeeMultiplier>,
+        global_fee_multiplier_bps: u16,
+    ) -> Result<()> {
+        instructions::set_global_fee_multiplier(ctx, global_fee_multiplier_bps)
+    }
+
+    /// Set the minimum seconds between a pool's fee growth snapshots (multi-sig required)
+    pub fn set_fee_snapshot_interval(
+        ctx: Context<SetFeeSnapshotInterval>,
+        fee_snapshot_interval_seconds: u32,
+    ) -> Result<()> {
+        instructions::set_fee_snapshot_interval(ctx, fee_snapshot_interval_seconds)
+    }
+
+    /// Set the minimum position width in tick spacings (multi-sig required)
+    pub fn set_min_tick_range_multiple(
+        ctx: Context<SetMinTickRangeMultiple>,
+        min_tick_range_multiple: u32,
+    ) -> Result<()> {
+        instructions::set_min_tick_range_multiple(ctx, min_tick_range_multiple)
+    }
+
+    /// Upgrade a v1 AMM global account to the v2 layout (multi-sig required)
+    pub fn migrate_global_v2(ctx: Context<MigrateGlobalV2>) -> Result<()> {
+        instructions::migrate_global_v2(ctx)
+    }
+
+    /// Upgrade a v1 pool account to the current layout (multi-sig required)
+    pub fn migrate_pool_v2<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MigratePoolV2<'info>>,
+        protocol_liquidity: u128,
+    ) -> Result<()> {
+        instructions::migrate_pool_v2(ctx, protocol_liquidity)
+    }
+
+    /// Set pool reward (multi-sig or the slot's reward authority)
     pub fn set_pool_reward(
         ctx: Context<SetPoolReward>,
         reward_index: u8,
@@ -129,7 +401,35 @@ pub mod amm {
     pub fn initialize_reward(
         ctx: Context<InitializeReward>,
         reward_index: u8,
+        max_emissions_per_second_x64: u128,
+    ) -> Result<()> {
+        instructions::initialize_reward(ctx, reward_index, max_emissions_per_second_x64)
+    }
+
+    /// Deposit reward tokens into a reward vault
+    pub fn fund_reward(
+        ctx: Context<FundReward>,
+        reward_index: u8,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::fund_reward(ctx, reward_index, amount)
+    }
+
+    /// Hand off a reward slot's authority (current reward authority or multi-sig)
+    pub fn update_reward_authority(
+        ctx: Context<UpdateRewardAuthority>,
+        reward_index: u8,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::update_reward_authority(ctx, reward_index, new_authority)
+    }
+
+    /// Rotate a reward slot's authority (multi-sig plus current reward authority)
+    pub fn set_reward_authority(
+        ctx: Context<SetRewardAuthority>,
+        reward_index: u8,
+        new_authority: Pubkey,
     ) -> Result<()> {
-        instructions::initialize_reward(ctx, reward_index)
+        instructions::set_reward_authority(ctx, reward_index, new_authority)
     }
 }
\ No newline at end of file
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::errors::AmmError;
+use crate::{errors::AmmError, state::Tick};
 
 /// Math utilities for CLMM calculations
 pub struct MathUtil;
@@ -7,96 +7,89 @@ pub struct MathUtil;
 impl MathUtil {
     /// Calculate sqrt price from tick
     pub fn tick_to_sqrt_price_x64(tick: i32) -> Result<u128> {
-        if tick < crate::constants::MIN_TICK || tick > crate::constants::MAX_TICK {
+        if !(crate::constants::MIN_TICK..=crate::constants::MAX_TICK).contains(&tick) {
             return Err(AmmError::TickOutOfBounds.into());
         }
         
         // Implementation of tick to sqrt price conversion
         // This is a complex calculation involving powers of 1.0001
-        let abs_tick = tick.abs() as u32;
+        let abs_tick = tick.unsigned_abs();
         
         let mut ratio = if abs_tick & 0x1 != 0 {
-            0xfffcb933bd6fad37aa2d162d1a594001u128
+            0xfffcb933bd6fad37u128
         } else {
-            1u128 << 96  // Q96 fixed point: 1.0 in 96-bit fixed point
+            1u128 << 64  // Q64 fixed point: 1.0 in 64-bit fixed point
         };
         
         if abs_tick & 0x2 != 0 {
-            ratio = (ratio * 0xfff97272373d413259a46990580e213a) >> 64;
+            ratio = (ratio * 0xfff97272373d4132) >> 64;
         }
         if abs_tick & 0x4 != 0 {
-            ratio = (ratio * 0xfff2e50f5f656932ef12357cf3c7fdcc) >> 64;
+            ratio = (ratio * 0xfff2e50f5f656932) >> 64;
         }
         if abs_tick & 0x8 != 0 {
-            ratio = (ratio * 0xffe5caca7e10e4e61c3624eaa0941cd0) >> 64;
+            ratio = (ratio * 0xffe5caca7e10e4e6) >> 64;
         }
         if abs_tick & 0x10 != 0 {
-            ratio = (ratio * 0xffcb9843d60f6159c9db58835c926644) >> 64;
+            ratio = (ratio * 0xffcb9843d60f6159) >> 64;
         }
         if abs_tick & 0x20 != 0 {
-            ratio = (ratio * 0xff973b41fa98c081472e6896dfb254c0) >> 64;
+            ratio = (ratio * 0xff973b41fa98c081) >> 64;
         }
         if abs_tick & 0x40 != 0 {
-            ratio = (ratio * 0xff2ea16466c96a3843ec78b326b52861) >> 64;
+            ratio = (ratio * 0xff2ea16466c96a38) >> 64;
         }
         if abs_tick & 0x80 != 0 {
-            ratio = (ratio * 0xfe5dee046a99a2a811c461f1969c3053) >> 64;
+            ratio = (ratio * 0xfe5dee046a99a2a8) >> 64;
         }
         if abs_tick & 0x100 != 0 {
-            ratio = (ratio * 0xfcbe86c7900a88aedcffc83b479aa3a4) >> 64;
+            ratio = (ratio * 0xfcbe86c7900a88ae) >> 64;
         }
         if abs_tick & 0x200 != 0 {
-            ratio = (ratio * 0xf987a7253ac413176f2b074cf7815e54) >> 64;
+            ratio = (ratio * 0xf987a7253ac41317) >> 64;
         }
         if abs_tick & 0x400 != 0 {
-            ratio = (ratio * 0xf3392b0822b70005940c7a398e4b70f3) >> 64;
+            ratio = (ratio * 0xf3392b0822b70005) >> 64;
         }
         if abs_tick & 0x800 != 0 {
-            ratio = (ratio * 0xe7159475a2c29b7443b29c7fa6e889d9) >> 64;
+            ratio = (ratio * 0xe7159475a2c29b74) >> 64;
         }
         if abs_tick & 0x1000 != 0 {
-            ratio = (ratio * 0xd097f3bdfd2022b8845ad8f792aa5825) >> 64;
+            ratio = (ratio * 0xd097f3bdfd2022b8) >> 64;
         }
         if abs_tick & 0x2000 != 0 {
-            ratio = (ratio * 0xa9f746462d870fdf8a65dc1f90e061e5) >> 64;
+            ratio = (ratio * 0xa9f746462d870fdf) >> 64;
         }
         if abs_tick & 0x4000 != 0 {
-            ratio = (ratio * 0x70d869a156d2a1b890bb3df62baf32f7) >> 64;
+            ratio = (ratio * 0x70d869a156d2a1b8) >> 64;
         }
         if abs_tick & 0x8000 != 0 {
-            ratio = (ratio * 0x31be135f97d08fd981231505542fcfa6) >> 64;
+            ratio = (ratio * 0x31be135f97d08fd9) >> 64;
         }
         if abs_tick & 0x10000 != 0 {
-            ratio = (ratio * 0x9aa508b5b7a84e1c677de54f3e99bc9) >> 64;
+            ratio = (ratio * 0x9aa508b5b7a84e1) >> 64;
         }
         if abs_tick & 0x20000 != 0 {
-            ratio = (ratio * 0x5d6af8dedb81196699c329225ee604) >> 64;
+            ratio = (ratio * 0x5d6af8dedb8119) >> 64;
         }
         if abs_tick & 0x40000 != 0 {
-            ratio = (ratio * 0x2216e584f5fa1ea926041bedfe98) >> 64;
+            ratio = (ratio * 0x2216e584f5fa) >> 64;
         }
         if abs_tick & 0x80000 != 0 {
-            ratio = (ratio * 0x48a170391f7dc42444e8fa2) >> 64;
+            ratio = (ratio * 0x48a1703) >> 64;
         }
         
+        // Each factor is a Q64 value below 1.0, so products stay within u128
         if tick > 0 {
             ratio = u128::MAX / ratio;
         }
         
-        // Convert to x64 format
-        let sqrt_price_x64 = if ratio % (1 << 32) == 0 {
-            ratio >> 32
-        } else {
-            (ratio >> 32) + 1
-        };
-        
-        Ok(sqrt_price_x64)
+        Ok(ratio)
     }
     
     /// Calculate tick from sqrt price
     pub fn sqrt_price_x64_to_tick(sqrt_price_x64: u128) -> Result<i32> {
-        if sqrt_price_x64 < crate::constants::MIN_SQRT_PRICE_X64 
-            || sqrt_price_x64 > crate::constants::MAX_SQRT_PRICE_X64 {
+        if !(crate::constants::MIN_SQRT_PRICE_X64..=crate::constants::MAX_SQRT_PRICE_X64).contains(&sqrt_price_x64) {
             return Err(AmmError::InvalidSqrtPrice.into());
         }
         
@@ -104,9 +97,17 @@ impl MathUtil {
         // For now, we'll use a simplified approximation
         let sqrt_price = sqrt_price_x64 as f64 / (1u128 << 64) as f64;
         let price = sqrt_price * sqrt_price;
-        let tick = (price.ln() / 1.0001f64.ln()).round() as i32;
+        let approx_tick = (price.ln() / 1.0001f64.ln()).floor() as i32;
+        let mut tick = approx_tick.clamp(crate::constants::MIN_TICK, crate::constants::MAX_TICK);
+
+        // Correct floating-point error so that tick is the greatest tick with price <= sqrt_price_x64
+        if tick > crate::constants::MIN_TICK && Self::tick_to_sqrt_price_x64(tick)? > sqrt_price_x64 {
+            tick -= 1;
+        } else if tick < crate::constants::MAX_TICK && Self::tick_to_sqrt_price_x64(tick + 1)? <= sqrt_price_x64 {
+            tick += 1;
+        }
         
-        Ok(tick.clamp(crate::constants::MIN_TICK, crate::constants::MAX_TICK))
+        Ok(tick)
     }
     
     /// Calculate liquidity from amounts
@@ -245,7 +246,7 @@ impl MathUtil {
             if product / amount as u128 == sqrt_price_x64 {
                 let denominator = numerator1 + product;
                 if denominator >= numerator1 {
-                    return Ok(Self::mul_div_rounding_up(numerator1, sqrt_price_x64, denominator)?);
+                    return Self::mul_div_rounding_up(numerator1, sqrt_price_x64, denominator);
                 }
             }
             
@@ -257,6 +258,299 @@ impl MathUtil {
         }
     }
     
+    /// Calculate fee growth inside a tick range from the boundary ticks' outside values
+    pub fn get_fee_growth_inside(
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+        tick_current: i32,
+        tick_lower: &Tick,
+        tick_upper: &Tick,
+        fee_growth_global_a_x64: u128,
+        fee_growth_global_b_x64: u128,
+    ) -> (u128, u128) {
+        // Fee growth below the lower tick
+        let (below_a, below_b) = if tick_current >= tick_lower_index {
+            (tick_lower.fee_growth_outside_a_x64, tick_lower.fee_growth_outside_b_x64)
+        } else {
+            (
+                fee_growth_global_a_x64.wrapping_sub(tick_lower.fee_growth_outside_a_x64),
+                fee_growth_global_b_x64.wrapping_sub(tick_lower.fee_growth_outside_b_x64),
+            )
+        };
+
+        // Fee growth above the upper tick
+        let (above_a, above_b) = if tick_current < tick_upper_index {
+            (tick_upper.fee_growth_outside_a_x64, tick_upper.fee_growth_outside_b_x64)
+        } else {
+            (
+                fee_growth_global_a_x64.wrapping_sub(tick_upper.fee_growth_outside_a_x64),
+                fee_growth_global_b_x64.wrapping_sub(tick_upper.fee_growth_outside_b_x64),
+            )
+        };
+
+        (
+            fee_growth_global_a_x64.wrapping_sub(below_a).wrapping_sub(above_a),
+            fee_growth_global_b_x64.wrapping_sub(below_b).wrapping_sub(above_b),
+        )
+    }
+
+    /// Token A amount between two sqrt prices: L * (b - a) / (a * b), in Q64
+    pub fn get_amount0_delta(
+        sqrt_price_a_x64: u128,
+        sqrt_price_b_x64: u128,
+        liquidity: u128,
+        round_up: bool,
+    ) -> Result<u128> {
+        let (lower, upper) = if sqrt_price_a_x64 <= sqrt_price_b_x64 {
+            (sqrt_price_a_x64, sqrt_price_b_x64)
+        } else {
+            (sqrt_price_b_x64, sqrt_price_a_x64)
+        };
+        require!(lower > 0, AmmError::InvalidSqrtPrice);
+
+        if round_up {
+            let scaled = Self::mul_div_ceil(liquidity, upper - lower, upper)?;
+            Self::mul_div_ceil(scaled, crate::constants::Q64, lower)
+        } else {
+            let scaled = Self::mul_div_floor(liquidity, upper - lower, upper)?;
+            Self::mul_div_floor(scaled, crate::constants::Q64, lower)
+        }
+    }
+
+    /// Token B amount between two sqrt prices: L * (b - a), in Q64
+    pub fn get_amount1_delta(
+        sqrt_price_a_x64: u128,
+        sqrt_price_b_x64: u128,
+        liquidity: u128,
+        round_up: bool,
+    ) -> Result<u128> {
+        let (lower, upper) = if sqrt_price_a_x64 <= sqrt_price_b_x64 {
+            (sqrt_price_a_x64, sqrt_price_b_x64)
+        } else {
+            (sqrt_price_b_x64, sqrt_price_a_x64)
+        };
+
+        if round_up {
+            Self::mul_div_ceil(liquidity, upper - lower, crate::constants::Q64)
+        } else {
+            Self::mul_div_floor(liquidity, upper - lower, crate::constants::Q64)
+        }
+    }
+
+    /// Sqrt price after adding `amount` of the input token at constant liquidity
+    pub fn get_next_sqrt_price_from_input(
+        sqrt_price_x64: u128,
+        liquidity: u128,
+        amount: u64,
+        zero_for_one: bool,
+    ) -> Result<u128> {
+        require!(liquidity > 0, AmmError::InsufficientLiquidity);
+        if amount == 0 {
+            return Ok(sqrt_price_x64);
+        }
+
+        if zero_for_one {
+            // Token A in, price down: L * P / (L + amount * P), rounded up
+            let product = Self::mul_div_floor(amount as u128, sqrt_price_x64, crate::constants::Q64)?;
+            let denominator = liquidity.checked_add(product).ok_or(AmmError::Overflow)?;
+            Self::mul_div_ceil(liquidity, sqrt_price_x64, denominator)
+        } else {
+            // Token B in, price up: P + amount / L, rounded down
+            let quotient = Self::mul_div_floor(amount as u128, crate::constants::Q64, liquidity)?;
+            Ok(sqrt_price_x64.checked_add(quotient).ok_or(AmmError::Overflow)?)
+        }
+    }
+
+    /// Sqrt price after removing `amount` of the output token at constant liquidity
+    pub fn get_next_sqrt_price_from_output(
+        sqrt_price_x64: u128,
+        liquidity: u128,
+        amount: u64,
+        zero_for_one: bool,
+    ) -> Result<u128> {
+        require!(liquidity > 0, AmmError::InsufficientLiquidity);
+        if amount == 0 {
+            return Ok(sqrt_price_x64);
+        }
+
+        if zero_for_one {
+            // Token B out, price down: P - amount / L, rounded down
+            let quotient = Self::mul_div_ceil(amount as u128, crate::constants::Q64, liquidity)?;
+            require!(sqrt_price_x64 > quotient, AmmError::InsufficientLiquidity);
+            Ok(sqrt_price_x64 - quotient)
+        } else {
+            // Token A out, price up: L * P / (L - amount * P), rounded up
+            let product = Self::mul_div_ceil(amount as u128, sqrt_price_x64, crate::constants::Q64)?;
+            require!(liquidity > product, AmmError::InsufficientLiquidity);
+            Self::mul_div_ceil(liquidity, sqrt_price_x64, liquidity - product)
+        }
+    }
+
+    /// Advance a swap toward `sqrt_price_target_x64` within a single liquidity range.
+    /// Returns (sqrt price reached, amount in, amount out), all before fees.
+    pub fn compute_swap_step(
+        sqrt_price_current_x64: u128,
+        sqrt_price_target_x64: u128,
+        liquidity: u128,
+        amount_remaining: u64,
+        zero_for_one: bool,
+        is_base_input: bool,
+    ) -> Result<(u128, u64, u64)> {
+        let amount_in_to_target = |round_up: bool| -> Result<u128> {
+            if zero_for_one {
+                Self::get_amount0_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, round_up)
+            } else {
+                Self::get_amount1_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, round_up)
+            }
+        };
+        let amount_out_to_target = || -> Result<u128> {
+            if zero_for_one {
+                Self::get_amount1_delta(sqrt_price_target_x64, sqrt_price_current_x64, liquidity, false)
+            } else {
+                Self::get_amount0_delta(sqrt_price_current_x64, sqrt_price_target_x64, liquidity, false)
+            }
+        };
+
+        let sqrt_price_next_x64 = if is_base_input {
+            if amount_remaining as u128 >= amount_in_to_target(true)? {
+                sqrt_price_target_x64
+            } else {
+                Self::get_next_sqrt_price_from_input(
+                    sqrt_price_current_x64,
+                    liquidity,
+                    amount_remaining,
+                    zero_for_one,
+                )?
+            }
+        } else if amount_remaining as u128 >= amount_out_to_target()? {
+            sqrt_price_target_x64
+        } else {
+            Self::get_next_sqrt_price_from_output(
+                sqrt_price_current_x64,
+                liquidity,
+                amount_remaining,
+                zero_for_one,
+            )?
+        };
+
+        let reached_target = sqrt_price_next_x64 == sqrt_price_target_x64;
+
+        let amount_in = if is_base_input && !reached_target {
+            // Partial step consumes everything that is left
+            amount_remaining as u128
+        } else if zero_for_one {
+            Self::get_amount0_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, true)?
+        } else {
+            Self::get_amount1_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, true)?
+        };
+
+        let mut amount_out = if zero_for_one {
+            Self::get_amount1_delta(sqrt_price_next_x64, sqrt_price_current_x64, liquidity, false)?
+        } else {
+            Self::get_amount0_delta(sqrt_price_current_x64, sqrt_price_next_x64, liquidity, false)?
+        };
+        if !is_base_input {
+            amount_out = amount_out.min(amount_remaining as u128);
+        }
+
+        Ok((
+            sqrt_price_next_x64,
+            u64::try_from(amount_in).map_err(|_| AmmError::Overflow)?,
+            u64::try_from(amount_out).map_err(|_| AmmError::Overflow)?,
+        ))
+    }
+
+    /// Exact-input quote within the current liquidity range, fee taken from the input.
+    /// Returns (amount out, fee, sqrt price after).
+    pub fn get_amount_out(
+        sqrt_price_x64: u128,
+        liquidity: u128,
+        amount_in: u64,
+        trade_fee_rate: u32,
+        zero_for_one: bool,
+    ) -> Result<(u64, u64, u128)> {
+        require!(liquidity > 0, AmmError::InsufficientLiquidity);
+        require!(
+            (trade_fee_rate as u64) < crate::constants::FEE_RATE_DENOMINATOR_VALUE,
+            AmmError::InvalidFeeRate
+        );
+
+        let fee = (amount_in as u128)
+            .checked_mul(trade_fee_rate as u128)
+            .and_then(|x| x.checked_div(crate::constants::FEE_RATE_DENOMINATOR_VALUE as u128))
+            .ok_or(AmmError::Overflow)? as u64;
+        let amount_in_after_fee = amount_in - fee;
+
+        let sqrt_price_after_x64 = Self::get_next_sqrt_price_from_input(
+            sqrt_price_x64,
+            liquidity,
+            amount_in_after_fee,
+            zero_for_one,
+        )?;
+
+        let amount_out = if zero_for_one {
+            Self::get_amount1_delta(sqrt_price_after_x64, sqrt_price_x64, liquidity, false)?
+        } else {
+            Self::get_amount0_delta(sqrt_price_x64, sqrt_price_after_x64, liquidity, false)?
+        };
+
+        Ok((
+            u64::try_from(amount_out).map_err(|_| AmmError::Overflow)?,
+            fee,
+            sqrt_price_after_x64,
+        ))
+    }
+
+    /// floor(a * b / denominator) with a 256-bit intermediate product
+    pub fn mul_div_floor(a: u128, b: u128, denominator: u128) -> Result<u128> {
+        let (quotient, _) = Self::full_mul_div(a, b, denominator)?;
+        Ok(quotient)
+    }
+
+    /// ceil(a * b / denominator) with a 256-bit intermediate product
+    pub fn mul_div_ceil(a: u128, b: u128, denominator: u128) -> Result<u128> {
+        let (quotient, remainder) = Self::full_mul_div(a, b, denominator)?;
+        if remainder > 0 {
+            Ok(quotient.checked_add(1).ok_or(AmmError::Overflow)?)
+        } else {
+            Ok(quotient)
+        }
+    }
+
+    fn full_mul_div(a: u128, b: u128, denominator: u128) -> Result<(u128, u128)> {
+        require!(denominator > 0, AmmError::DivisionByZero);
+
+        // 128 x 128 -> 256 bit product split into 64-bit limbs
+        let mask = u64::MAX as u128;
+        let (a_lo, a_hi) = (a & mask, a >> 64);
+        let (b_lo, b_hi) = (b & mask, b >> 64);
+        let lo_lo = a_lo * b_lo;
+        let lo_hi = a_lo * b_hi;
+        let hi_lo = a_hi * b_lo;
+        let hi_hi = a_hi * b_hi;
+        let middle = (lo_lo >> 64) + (lo_hi & mask) + (hi_lo & mask);
+        let product_lo = (lo_lo & mask) | (middle << 64);
+        let product_hi = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (middle >> 64);
+
+        // Quotient must fit in 128 bits
+        require!(product_hi < denominator, AmmError::Overflow);
+
+        // Binary long division of the 256-bit product
+        let mut remainder = product_hi;
+        let mut quotient = 0u128;
+        for bit in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((product_lo >> bit) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= denominator {
+                remainder = remainder.wrapping_sub(denominator);
+                quotient |= 1;
+            }
+        }
+
+        Ok((quotient, remainder))
+    }
+
     /// Multiply and divide with rounding up
     pub fn mul_div_rounding_up(a: u128, b: u128, denominator: u128) -> Result<u128> {
         let result = a
@@ -294,4 +588,86 @@ impl MathUtil {
             Ok(result)
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::{DEFAULT_TRADE_FEE_RATE, FEE_RATE_DENOMINATOR_VALUE, MAX_SQRT_PRICE_X64, MAX_TICK, MIN_SQRT_PRICE_X64, MIN_TICK, Q64};
+
+    const LIQUIDITY: u128 = 1_000_000_000_000;
+
+    #[test]
+    fn tick_to_sqrt_price_follows_powers_of_one_point_0001() {
+        assert_eq!(MathUtil::tick_to_sqrt_price_x64(0).unwrap(), Q64);
+        assert_eq!(MathUtil::tick_to_sqrt_price_x64(MIN_TICK).unwrap(), MIN_SQRT_PRICE_X64);
+        assert_eq!(MathUtil::tick_to_sqrt_price_x64(MAX_TICK).unwrap(), MAX_SQRT_PRICE_X64);
+        assert!(MathUtil::tick_to_sqrt_price_x64(MIN_TICK - 1).is_err());
+        assert!(MathUtil::tick_to_sqrt_price_x64(MAX_TICK + 1).is_err());
+
+        // sqrt(1.0001^10_000) = 1.0001^5_000 ~= 1.648680
+        let sqrt_price = MathUtil::tick_to_sqrt_price_x64(10_000).unwrap() as f64 / Q64 as f64;
+        assert!((sqrt_price - 1.0001f64.powi(5_000)).abs() < 1e-9);
+
+        let mut previous = 0;
+        for tick in [MIN_TICK, -100_000, -1, 0, 1, 100_000, MAX_TICK] {
+            let sqrt_price_x64 = MathUtil::tick_to_sqrt_price_x64(tick).unwrap();
+            assert!(sqrt_price_x64 > previous);
+            previous = sqrt_price_x64;
+            // Round trip lands back on the same tick
+            assert_eq!(MathUtil::sqrt_price_x64_to_tick(sqrt_price_x64).unwrap(), tick);
+        }
+    }
+
+    #[test]
+    fn swap_step_stops_at_the_target_or_consumes_the_input() {
+        let sqrt_price_target_x64 = MathUtil::tick_to_sqrt_price_x64(-100).unwrap();
+
+        // A large input reaches the target and only pays what the range needs
+        let (sqrt_price_next_x64, amount_in, amount_out) =
+            MathUtil::compute_swap_step(Q64, sqrt_price_target_x64, LIQUIDITY, u64::MAX, true, true).unwrap();
+        assert_eq!(sqrt_price_next_x64, sqrt_price_target_x64);
+        assert_eq!(
+            amount_in as u128,
+            MathUtil::get_amount0_delta(sqrt_price_target_x64, Q64, LIQUIDITY, true).unwrap()
+        );
+        assert!(amount_out > 0 && amount_out < amount_in);
+
+        // A small input stops short of the target and is consumed in full
+        let (sqrt_price_next_x64, amount_in, amount_out) =
+            MathUtil::compute_swap_step(Q64, sqrt_price_target_x64, LIQUIDITY, 1_000_000, true, true).unwrap();
+        assert!(sqrt_price_next_x64 < Q64 && sqrt_price_next_x64 > sqrt_price_target_x64);
+        assert_eq!(amount_in, 1_000_000);
+        assert!(amount_out < 1_000_000);
+
+        // Exact output never pays out more than requested
+        let sqrt_price_target_x64 = MathUtil::tick_to_sqrt_price_x64(100).unwrap();
+        let (sqrt_price_next_x64, amount_in, amount_out) =
+            MathUtil::compute_swap_step(Q64, sqrt_price_target_x64, LIQUIDITY, 1_000_000, false, false).unwrap();
+        assert!(sqrt_price_next_x64 > Q64 && sqrt_price_next_x64 < sqrt_price_target_x64);
+        assert_eq!(amount_out, 1_000_000);
+        assert!(amount_in > amount_out);
+    }
+
+    #[test]
+    fn amount_out_takes_the_trade_fee_from_the_input() {
+        let amount_in = 1_000_000u64;
+        let (amount_out, fee, sqrt_price_after_x64) =
+            MathUtil::get_amount_out(Q64, LIQUIDITY, amount_in, DEFAULT_TRADE_FEE_RATE, true).unwrap();
+        assert_eq!(fee, amount_in * DEFAULT_TRADE_FEE_RATE as u64 / FEE_RATE_DENOMINATOR_VALUE);
+        assert!(sqrt_price_after_x64 < Q64);
+
+        // Matches a fee-free step with the input net of the fee
+        let (_, _, expected_out) = MathUtil::compute_swap_step(
+            Q64,
+            MIN_SQRT_PRICE_X64,
+            LIQUIDITY,
+            amount_in - fee,
+            true,
+            true,
+        ).unwrap();
+        assert_eq!(amount_out, expected_out);
+
+        assert!(MathUtil::get_amount_out(Q64, 0, amount_in, DEFAULT_TRADE_FEE_RATE, true).is_err());
+        assert!(MathUtil::get_amount_out(Q64, LIQUIDITY, amount_in, FEE_RATE_DENOMINATOR_VALUE as u32, true).is_err());
+    }
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::errors::*;
+use crate::{constants::{AMM_GLOBAL_VERSION, BASIS_POINTS_DENOMINATOR, DEFAULT_CREATE_POOL_FEE, DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS, DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS, DEFAULT_FUND_FEE_RATE, DEFAULT_PROTOCOL_FEE_RATE, DEFAULT_TRADE_FEE_RATE, CREATOR_FEE_BASIS_POINTS, FEE_RATE_DENOMINATOR_VALUE, MAX_CREATE_POOL_FEE, MAX_FUND_FEE_RATE, MAX_PROTOCOL_FEE_RATE, MAX_TRADE_FEE_RATE, PLATFORM_FEE_BASIS_POINTS, POOL_FEE_TIER_COUNT, POOL_STATUS_INITIALIZED, POOL_STATUS_SWAP_ONLY, POOL_STATUS_WITHDRAW_ONLY, Q64, TICK_ARRAY_SIZE}, errors::*};
 
 #[account]
 pub struct AmmGlobal {
@@ -29,8 +29,14 @@ pub struct AmmGlobal {
     pub total_fees_collected: u64,
     /// Program version
     pub version: u8,
-    /// Reserved space for future upgrades
-    pub reserved: [u64; 8],
+    /// Minimum position width in multiples of tick_spacing (0 disables)
+    pub min_tick_range_multiple: u32,
+    /// Multiplier applied to every pool's trade fee, in basis points (10000 = unchanged)
+    pub global_fee_multiplier_bps: u16,
+    /// Minimum seconds between a pool's fee growth snapshots
+    pub fee_snapshot_interval_seconds: u32,
+    /// Reserved space for future upgrades; v2 fields above were taken from its original 64 bytes
+    pub reserved: [u8; 54],
 }
 
 impl AmmGlobal {
@@ -48,7 +54,10 @@ impl AmmGlobal {
         8 + // total_volume
         8 + // total_fees_collected
         1 + // version
-        64; // reserved
+        4 + // min_tick_range_multiple
+        2 + // global_fee_multiplier_bps
+        4 + // fee_snapshot_interval_seconds
+        54; // reserved
 
     /// Verify multi-sig authorization
     pub fn verify_multisig_auth(&self, admin_signer: &Signer, multisig_signer: &Signer) -> Result<()> {
@@ -68,9 +77,47 @@ impl AmmGlobal {
         require!(!self.is_paused, AmmError::OperationsPaused);
         Ok(())
     }
+
+    /// Upgrade a v1 account in place. v2 fields occupy bytes v1 left zeroed in reserved;
+    /// a zero multiplier would make every pool fee-free, so defaults are seeded explicitly.
+    pub fn migrate_to_v2(&mut self) -> Result<()> {
+        require!(self.version == 1, AmmError::InvalidAccountVersion);
+
+        self.min_tick_range_multiple = 0;
+        self.global_fee_multiplier_bps = DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS;
+        self.fee_snapshot_interval_seconds = DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS;
+        self.version = AMM_GLOBAL_VERSION;
+
+        Ok(())
+    }
+
+    /// Fee multiplier swaps apply. A v1 account that has not been migrated yet reads zeroed
+    /// reserved bytes here, which would make every pool fee-free, so it keeps the full fee.
+    pub fn fee_multiplier_bps(&self) -> u16 {
+        if self.version < AMM_GLOBAL_VERSION {
+            DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS
+        } else {
+            self.global_fee_multiplier_bps
+        }
+    }
+
+    /// Fee snapshot interval swaps apply; the default until a v1 account is migrated
+    pub fn fee_snapshot_interval(&self) -> u32 {
+        if self.version < AMM_GLOBAL_VERSION {
+            DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS
+        } else {
+            self.fee_snapshot_interval_seconds
+        }
+    }
+
+    /// Positions owned by the multi-sig treasury count as protocol-owned liquidity
+    pub fn is_protocol_authority(&self, owner: &Pubkey) -> bool {
+        *owner == self.multisig_authority
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct Pool {
     /// Pool ID
     pub id: Pubkey,
@@ -122,8 +169,26 @@ pub struct Pool {
     pub created_at: i64,
     /// Last interaction timestamp
     pub updated_at: i64,
+    /// Token A held in the vault on behalf of liquidity providers
+    pub liquidity_reserve_a: u64,
+    /// Token B held in the vault on behalf of liquidity providers
+    pub liquidity_reserve_b: u64,
+    /// Account that paid for pool creation
+    pub created_by: Pubkey,
+    /// Trade fee discounts by cumulative pool volume
+    pub volume_fee_tiers: [PoolFeeTier; POOL_FEE_TIER_COUNT],
+    /// Liquidity provided by positions owned by the protocol authority
+    pub protocol_liquidity: u128,
+    /// Share of the trade fee sent to the platform wallet, in basis points
+    pub platform_fee_basis_points: u16,
+    /// Share of the trade fee sent to the creator wallet, in basis points
+    pub creator_fee_basis_points: u16,
+    /// Creator of a token graduated from the bonding curve; receives the creator fee when set
+    pub token_creator: Option<Pubkey>,
+    /// Timestamp of the last FeeGrowthSnapshotEvent (0 if none yet)
+    pub last_fee_snapshot_at: i64,
     /// Reserved space
-    pub reserved: [u64; 4],
+    pub reserved: [u64; 2],
 }
 
 impl Pool {
@@ -153,12 +218,146 @@ impl Pool {
         8 + // total_volume_b
         8 + // created_at
         8 + // updated_at
-        32; // reserved
+        8 + // liquidity_reserve_a
+        8 + // liquidity_reserve_b
+        32 + // created_by
+        PoolFeeTier::LEN * POOL_FEE_TIER_COUNT + // volume_fee_tiers
+        16 + // protocol_liquidity
+        2 + // platform_fee_basis_points
+        2 + // creator_fee_basis_points
+        33 + // token_creator (Option<Pubkey>)
+        8 + // last_fee_snapshot_at
+        16; // reserved
 
     pub fn is_overflow_default_tick_spacing(&self) -> bool {
         self.tick_spacing != 10 && self.tick_spacing != 60 && self.tick_spacing != 200
     }
 
+    /// Trade fee rate after applying any crossed volume tier and the global fee multiplier
+    pub fn effective_trade_fee_rate(&self, fee_multiplier_bps: u16) -> u32 {
+        let total_volume = self.total_volume_a.saturating_add(self.total_volume_b);
+        let tier_fee_rate = self.volume_fee_tiers
+            .iter()
+            .filter(|tier| tier.is_enabled() && total_volume >= tier.volume_threshold)
+            .map(|tier| tier.trade_fee_rate)
+            .fold(self.trade_fee_rate, u32::min);
+        // Multiplier is capped at BASIS_POINTS_DENOMINATOR, so this never exceeds the tier rate
+        (tier_fee_rate as u64 * fee_multiplier_bps as u64 / BASIS_POINTS_DENOMINATOR) as u32
+    }
+
+    /// Liquidity-backed balance of the side a swap in this direction pays out from
+    pub fn output_liquidity_reserve(&self, zero_for_one: bool) -> u64 {
+        if zero_for_one { self.liquidity_reserve_b } else { self.liquidity_reserve_a }
+    }
+
+    /// Swaps run while the pool is live or swap-only
+    pub fn allows_swaps(&self) -> bool {
+        self.status == POOL_STATUS_INITIALIZED || self.status == POOL_STATUS_SWAP_ONLY
+    }
+
+    /// New positions and deposits are only accepted while the pool is fully live
+    pub fn allows_deposits(&self) -> bool {
+        self.status == POOL_STATUS_INITIALIZED
+    }
+
+    /// Withdrawals stay open in every status except disabled
+    pub fn allows_withdrawals(&self) -> bool {
+        self.status == POOL_STATUS_INITIALIZED
+            || self.status == POOL_STATUS_SWAP_ONLY
+            || self.status == POOL_STATUS_WITHDRAW_ONLY
+    }
+
+    /// Swap direction for a vault pair: Some(true) for A to B, Some(false) for B to A,
+    /// None unless the pair is exactly this pool's two vaults
+    pub fn swap_direction(&self, input_vault: Pubkey, output_vault: Pubkey) -> Option<bool> {
+        if input_vault == self.vault_a && output_vault == self.vault_b {
+            Some(true)
+        } else if input_vault == self.vault_b && output_vault == self.vault_a {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Whether a fee growth snapshot may be emitted: none yet, or the interval has passed
+    pub fn is_fee_snapshot_due(&self, interval_seconds: u32, timestamp: i64) -> bool {
+        self.last_fee_snapshot_at == 0
+            || timestamp.saturating_sub(self.last_fee_snapshot_at) >= interval_seconds as i64
+    }
+
+    /// Wallet the creator fee is paid to: the graduated token's creator, else the global creator wallet
+    pub fn creator_fee_wallet(&self, global_creator_wallet: Pubkey) -> Pubkey {
+        self.token_creator.unwrap_or(global_creator_wallet)
+    }
+
+    /// Protocol, platform and creator shares together must fit inside the trade fee
+    pub fn validate_fee_split(&self) -> Result<()> {
+        let protocol_share = (self.protocol_fee_rate as u64)
+            .checked_mul(BASIS_POINTS_DENOMINATOR)
+            .ok_or(AmmError::Overflow)?;
+        let wallet_share = (self.platform_fee_basis_points as u64)
+            .checked_add(self.creator_fee_basis_points as u64)
+            .and_then(|x| x.checked_mul(FEE_RATE_DENOMINATOR_VALUE))
+            .ok_or(AmmError::Overflow)?;
+        let total_share = protocol_share
+            .checked_add(wallet_share)
+            .ok_or(AmmError::Overflow)?;
+        require!(
+            total_share <= FEE_RATE_DENOMINATOR_VALUE * BASIS_POINTS_DENOMINATOR,
+            AmmError::InvalidFeeSplit
+        );
+        Ok(())
+    }
+
+    /// Vault balances the pool can account for (liquidity-backed + fund fees). Protocol fees
+    /// are left out: swaps pay the protocol share out of the vault as they execute, so
+    /// protocol_fees_token_* is never backed by a vault balance.
+    pub fn accounted_vault_amounts(&self) -> Result<(u64, u64)> {
+        let owned_a = self.liquidity_reserve_a
+            .checked_add(self.fund_fees_token_a)
+            .ok_or(AmmError::Overflow)?;
+        let owned_b = self.liquidity_reserve_b
+            .checked_add(self.fund_fees_token_b)
+            .ok_or(AmmError::Overflow)?;
+        Ok((owned_a, owned_b))
+    }
+
+    /// Protocol fees that can leave the vaults without dipping into LP reserves or fund fees
+    pub fn collectable_protocol_fees(&self, vault_a_amount: u64, vault_b_amount: u64) -> (u64, u64) {
+        let available_a = vault_a_amount
+            .saturating_sub(self.liquidity_reserve_a)
+            .saturating_sub(self.fund_fees_token_a);
+        let available_b = vault_b_amount
+            .saturating_sub(self.liquidity_reserve_b)
+            .saturating_sub(self.fund_fees_token_b);
+        (
+            self.protocol_fees_token_a.min(available_a),
+            self.protocol_fees_token_b.min(available_b),
+        )
+    }
+
+    /// Fee growth inside a position's range, read from its boundary ticks
+    pub fn fee_growth_inside(
+        &self,
+        tick_array_lower: &TickArray,
+        tick_array_upper: &TickArray,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<(u128, u128)> {
+        let tick_lower = tick_array_lower.get_tick(tick_lower_index, self.tick_spacing)?;
+        let tick_upper = tick_array_upper.get_tick(tick_upper_index, self.tick_spacing)?;
+
+        Ok(crate::math::MathUtil::get_fee_growth_inside(
+            tick_lower_index,
+            tick_upper_index,
+            self.tick_current,
+            tick_lower,
+            tick_upper,
+            self.fee_growth_global_a_x64,
+            self.fee_growth_global_b_x64,
+        ))
+    }
+
     pub fn get_first_initialized_tick(&self, _zero_for_one: bool) -> Option<i32> {
         // Implementation for getting first initialized tick
         // This would be implemented based on CLMM logic
@@ -166,6 +365,199 @@ impl Pool {
     }
 }
 
+/// Pool as laid out before liquidity reserves, fee tiers and fee wallet shares were added;
+/// only read by migrate_pool_v2
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PoolV1 {
+    pub id: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub bump: u8,
+    pub sqrt_price_x64: u128,
+    pub tick_current: i32,
+    pub tick_spacing: u16,
+    pub status: u8,
+    pub trade_fee_rate: u32,
+    pub protocol_fee_rate: u32,
+    pub fund_fee_rate: u32,
+    pub liquidity: u128,
+    pub protocol_fees_token_a: u64,
+    pub protocol_fees_token_b: u64,
+    pub fund_fees_token_a: u64,
+    pub fund_fees_token_b: u64,
+    pub fee_growth_global_a_x64: u128,
+    pub fee_growth_global_b_x64: u128,
+    pub reward_infos: [RewardInfoV1; 3],
+    pub total_volume_a: u64,
+    pub total_volume_b: u64,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub reserved: [u64; 4],
+}
+
+impl PoolV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // id
+        32 + // mint_a
+        32 + // mint_b
+        32 + // vault_a
+        32 + // vault_b
+        1 + // bump
+        16 + // sqrt_price_x64
+        4 + // tick_current
+        2 + // tick_spacing
+        1 + // status
+        4 + // trade_fee_rate
+        4 + // protocol_fee_rate
+        4 + // fund_fee_rate
+        16 + // liquidity
+        8 + // protocol_fees_token_a
+        8 + // protocol_fees_token_b
+        8 + // fund_fees_token_a
+        8 + // fund_fees_token_b
+        16 + // fee_growth_global_a_x64
+        16 + // fee_growth_global_b_x64
+        RewardInfoV1::LEN * 3 + // reward_infos
+        8 + // total_volume_a
+        8 + // total_volume_b
+        8 + // created_at
+        8 + // updated_at
+        32; // reserved
+
+    /// Current layout of this pool. v1 swaps paid the protocol fee out while also booking
+    /// it, so the booked amounts are dropped; everything else in the vaults apart from fund
+    /// fees belongs to LPs and seeds the liquidity reserves.
+    pub fn into_current(
+        self,
+        vault_a_amount: u64,
+        vault_b_amount: u64,
+        reward_vault_amounts: [u64; 3],
+        protocol_liquidity: u128,
+    ) -> Pool {
+        let mut reward_infos = [RewardInfo::default(); 3];
+        for (slot, reward_info) in self.reward_infos.into_iter().enumerate() {
+            reward_infos[slot] = reward_info.into_current(reward_vault_amounts[slot]);
+        }
+
+        Pool {
+            id: self.id,
+            mint_a: self.mint_a,
+            mint_b: self.mint_b,
+            vault_a: self.vault_a,
+            vault_b: self.vault_b,
+            bump: self.bump,
+            sqrt_price_x64: self.sqrt_price_x64,
+            tick_current: self.tick_current,
+            tick_spacing: self.tick_spacing,
+            status: self.status,
+            trade_fee_rate: self.trade_fee_rate,
+            protocol_fee_rate: self.protocol_fee_rate,
+            fund_fee_rate: self.fund_fee_rate,
+            liquidity: self.liquidity,
+            protocol_fees_token_a: 0,
+            protocol_fees_token_b: 0,
+            fund_fees_token_a: self.fund_fees_token_a,
+            fund_fees_token_b: self.fund_fees_token_b,
+            fee_growth_global_a_x64: self.fee_growth_global_a_x64,
+            fee_growth_global_b_x64: self.fee_growth_global_b_x64,
+            reward_infos,
+            total_volume_a: self.total_volume_a,
+            total_volume_b: self.total_volume_b,
+            created_at: self.created_at,
+            updated_at: self.updated_at,
+            liquidity_reserve_a: vault_a_amount.saturating_sub(self.fund_fees_token_a),
+            liquidity_reserve_b: vault_b_amount.saturating_sub(self.fund_fees_token_b),
+            created_by: Pubkey::default(),
+            volume_fee_tiers: [PoolFeeTier::default(); POOL_FEE_TIER_COUNT],
+            protocol_liquidity,
+            platform_fee_basis_points: PLATFORM_FEE_BASIS_POINTS,
+            creator_fee_basis_points: CREATOR_FEE_BASIS_POINTS,
+            token_creator: None,
+            last_fee_snapshot_at: 0,
+            reserved: [0; 2],
+        }
+    }
+}
+
+/// Deployment-time fee configuration for initialize_amm_global
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct AmmGlobalConfig {
+    /// Protocol fee rate
+    pub protocol_fee_rate: u32,
+    /// Fund fee rate
+    pub fund_fee_rate: u32,
+    /// Default trade fee rate for new pools
+    pub default_trade_fee_rate: u32,
+    /// Create pool fee (in lamports)
+    pub create_pool_fee: u64,
+}
+
+impl Default for AmmGlobalConfig {
+    fn default() -> Self {
+        Self {
+            protocol_fee_rate: DEFAULT_PROTOCOL_FEE_RATE,
+            fund_fee_rate: DEFAULT_FUND_FEE_RATE,
+            default_trade_fee_rate: DEFAULT_TRADE_FEE_RATE,
+            create_pool_fee: DEFAULT_CREATE_POOL_FEE,
+        }
+    }
+}
+
+impl AmmGlobalConfig {
+    /// Reject fee settings outside the bounds admin instructions enforce
+    pub fn validate(&self) -> Result<()> {
+        require!(self.default_trade_fee_rate <= MAX_TRADE_FEE_RATE, AmmError::FeeTooHigh);
+        require!(self.protocol_fee_rate <= MAX_PROTOCOL_FEE_RATE, AmmError::FeeTooHigh);
+        require!(self.fund_fee_rate <= MAX_FUND_FEE_RATE, AmmError::FeeTooHigh);
+        require!(self.create_pool_fee <= MAX_CREATE_POOL_FEE, AmmError::FeeTooHigh);
+        Ok(())
+    }
+}
+
+/// Return data of get_pool_state; fields are only ever appended, bumping version
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct PoolStateView {
+    pub version: u8,
+    pub pool_id: Pubkey,
+    pub status: u8,
+    pub sqrt_price_x64: u128,
+    /// Token B per token A (Q64.64)
+    pub price_x64: u128,
+    pub tick_current: i32,
+    pub liquidity: u128,
+    pub vault_a_balance: u64,
+    pub vault_b_balance: u64,
+    /// Both vaults valued in token A at the current price
+    pub tvl_in_token_a: u128,
+    pub trade_fee_rate: u32,
+    pub protocol_fees_token_a: u64,
+    pub protocol_fees_token_b: u64,
+    pub fund_fees_token_a: u64,
+    pub fund_fees_token_b: u64,
+    pub total_volume_a: u64,
+    pub total_volume_b: u64,
+    pub updated_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct PoolFeeTier {
+    /// Cumulative pool volume (token A + token B) required for this tier; zero disables the tier
+    pub volume_threshold: u64,
+    /// Trade fee rate once the threshold is crossed
+    pub trade_fee_rate: u32,
+}
+
+impl PoolFeeTier {
+    pub const LEN: usize = 8 + // volume_threshold
+        4; // trade_fee_rate
+
+    pub fn is_enabled(&self) -> bool {
+        self.volume_threshold > 0
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
 pub struct RewardInfo {
     /// Reward mint
@@ -182,9 +574,37 @@ pub struct RewardInfo {
     pub last_update_time: u64,
     /// Total amount owed
     pub total_amount_owed: u64,
+    /// Total reward tokens funded into the vault
+    pub funded_amount: u64,
+    /// Highest emissions rate set_pool_reward accepts (Q64.64)
+    pub max_emissions_per_second_x64: u128,
 }
 
 impl RewardInfo {
+    pub const LEN: usize = 32 + // mint
+        32 + // vault
+        32 + // authority
+        16 + // emissions_per_second_x64
+        16 + // growth_global_x64
+        8 + // last_update_time
+        8 + // total_amount_owed
+        8 + // funded_amount
+        16; // max_emissions_per_second_x64
+}
+
+/// RewardInfo as laid out in v1 pools, before funding was tracked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RewardInfoV1 {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    pub authority: Pubkey,
+    pub emissions_per_second_x64: u128,
+    pub growth_global_x64: u128,
+    pub last_update_time: u64,
+    pub total_amount_owed: u64,
+}
+
+impl RewardInfoV1 {
     pub const LEN: usize = 32 + // mint
         32 + // vault
         32 + // authority
@@ -192,6 +612,47 @@ impl RewardInfo {
         16 + // growth_global_x64
         8 + // last_update_time
         8; // total_amount_owed
+
+    /// Current layout of this slot. The running emissions rate becomes its cap, and the
+    /// vault balance beyond rewards already owed counts as funded, so the running rate can
+    /// still be set again without refunding the vault.
+    pub fn into_current(self, reward_vault_amount: u64) -> RewardInfo {
+        RewardInfo {
+            mint: self.mint,
+            vault: self.vault,
+            authority: self.authority,
+            emissions_per_second_x64: self.emissions_per_second_x64,
+            growth_global_x64: self.growth_global_x64,
+            last_update_time: self.last_update_time,
+            total_amount_owed: self.total_amount_owed,
+            funded_amount: reward_vault_amount.saturating_sub(self.total_amount_owed),
+            max_emissions_per_second_x64: self.emissions_per_second_x64,
+        }
+    }
+}
+
+/// Reward slot beyond the pool's in-struct `REWARD_NUM`, keyed by (pool, reward_index)
+#[account]
+pub struct PoolRewardSlot {
+    /// Pool the reward belongs to
+    pub pool: Pubkey,
+    /// Reward index (at least REWARD_NUM)
+    pub reward_index: u8,
+    /// Reward state, same layout as an in-struct slot
+    pub reward_info: RewardInfo,
+    /// PDA bump
+    pub bump: u8,
+    /// Reserved space
+    pub reserved: [u64; 4],
+}
+
+impl PoolRewardSlot {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        1 + // reward_index
+        RewardInfo::LEN + // reward_info
+        1 + // bump
+        32; // reserved
 }
 
 #[account]
@@ -242,6 +703,42 @@ impl Position {
         8 * 3 + // rewards_owed
         1 + // bump
         32; // reserved
+
+    /// Accrue fees earned since the last snapshot of fee growth inside the range
+    pub fn update_fees(&mut self, fee_growth_inside_a_x64: u128, fee_growth_inside_b_x64: u128) -> Result<()> {
+        let (fees_owed_a, fees_owed_b) = self.pending_fees(fee_growth_inside_a_x64, fee_growth_inside_b_x64)?;
+        self.fees_owed_a = fees_owed_a;
+        self.fees_owed_b = fees_owed_b;
+
+        self.fee_growth_inside_last_a_x64 = fee_growth_inside_a_x64;
+        self.fee_growth_inside_last_b_x64 = fee_growth_inside_b_x64;
+
+        Ok(())
+    }
+
+    /// Fees owed including those earned since the last snapshot, without settling them
+    pub fn pending_fees(&self, fee_growth_inside_a_x64: u128, fee_growth_inside_b_x64: u128) -> Result<(u64, u64)> {
+        let delta_a = fee_growth_inside_a_x64.wrapping_sub(self.fee_growth_inside_last_a_x64);
+        let delta_b = fee_growth_inside_b_x64.wrapping_sub(self.fee_growth_inside_last_b_x64);
+
+        let earned_a = self.liquidity
+            .checked_mul(delta_a)
+            .ok_or(AmmError::Overflow)?
+            / Q64;
+        let earned_b = self.liquidity
+            .checked_mul(delta_b)
+            .ok_or(AmmError::Overflow)?
+            / Q64;
+
+        let fees_owed_a = self.fees_owed_a
+            .checked_add(u64::try_from(earned_a).map_err(|_| AmmError::Overflow)?)
+            .ok_or(AmmError::Overflow)?;
+        let fees_owed_b = self.fees_owed_b
+            .checked_add(u64::try_from(earned_b).map_err(|_| AmmError::Overflow)?)
+            .ok_or(AmmError::Overflow)?;
+
+        Ok((fees_owed_a, fees_owed_b))
+    }
 }
 
 #[account]
@@ -269,6 +766,63 @@ impl TickArray {
     pub fn check_in_array(&self, tick: i32) -> bool {
         tick >= self.start_tick_index && tick < self.start_tick_index + 88
     }
+
+    /// Number of tick indexes covered by one array
+    pub fn span(tick_spacing: u16) -> i32 {
+        TICK_ARRAY_SIZE * tick_spacing as i32
+    }
+
+    /// Start index of the array that covers `tick_index`
+    pub fn start_index_for(tick_index: i32, tick_spacing: u16) -> i32 {
+        let span = Self::span(tick_spacing);
+        tick_index.div_euclid(span) * span
+    }
+
+    /// True if `tick_index` falls in this array's range for the given spacing
+    pub fn contains_tick(&self, tick_index: i32, tick_spacing: u16) -> bool {
+        tick_index >= self.start_tick_index
+            && tick_index < self.start_tick_index + Self::span(tick_spacing)
+    }
+
+    /// Next initialized tick in the swap direction within this array:
+    /// the greatest at or below `tick_current` when price falls, else the least above it
+    pub fn next_initialized_tick(&self, tick_current: i32, tick_spacing: u16, zero_for_one: bool) -> Option<i32> {
+        let tick_at = |offset: usize| self.start_tick_index + offset as i32 * tick_spacing as i32;
+        if zero_for_one {
+            (0..TICK_ARRAY_SIZE as usize)
+                .rev()
+                .find(|&offset| self.ticks[offset].initialized && tick_at(offset) <= tick_current)
+                .map(tick_at)
+        } else {
+            (0..TICK_ARRAY_SIZE as usize)
+                .find(|&offset| self.ticks[offset].initialized && tick_at(offset) > tick_current)
+                .map(tick_at)
+        }
+    }
+
+    /// Offset of a spacing-aligned tick within this array
+    pub fn tick_offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        let offset = tick_index
+            .checked_sub(self.start_tick_index)
+            .ok_or(AmmError::Underflow)?;
+        require!(
+            offset >= 0 && offset % tick_spacing as i32 == 0,
+            AmmError::InvalidTickArray
+        );
+        let index = (offset / tick_spacing as i32) as usize;
+        require!(index < TICK_ARRAY_SIZE as usize, AmmError::InvalidTickArray);
+        Ok(index)
+    }
+
+    pub fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<&Tick> {
+        let index = self.tick_offset(tick_index, tick_spacing)?;
+        Ok(&self.ticks[index])
+    }
+
+    pub fn get_tick_mut(&mut self, tick_index: i32, tick_spacing: u16) -> Result<&mut Tick> {
+        let index = self.tick_offset(tick_index, tick_spacing)?;
+        Ok(&mut self.ticks[index])
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
@@ -294,6 +848,20 @@ impl Tick {
         16 + // fee_growth_outside_b_x64
         16 * 3 + // reward_growth_outside
         1; // initialized
+
+    /// Flip fee and reward growth outside when price crosses this tick
+    pub fn cross(
+        &mut self,
+        fee_growth_global_a_x64: u128,
+        fee_growth_global_b_x64: u128,
+        reward_growths_global_x64: &[u128; 3],
+    ) {
+        self.fee_growth_outside_a_x64 = fee_growth_global_a_x64.wrapping_sub(self.fee_growth_outside_a_x64);
+        self.fee_growth_outside_b_x64 = fee_growth_global_b_x64.wrapping_sub(self.fee_growth_outside_b_x64);
+        for (outside, global) in self.reward_growth_outside.iter_mut().zip(reward_growths_global_x64) {
+            *outside = global.wrapping_sub(*outside);
+        }
+    }
 }
 
 #[account]
@@ -316,6 +884,110 @@ impl PersonalPosition {
         1; // bump
 }
 
+#[account]
+pub struct UserVolumeAccumulator {
+    /// User's public key
+    pub user: Pubkey,
+    /// Total swap input in token A
+    pub volume_a: u64,
+    /// Total swap input in token B
+    pub volume_b: u64,
+    /// Trade fees paid in token A
+    pub fees_paid_a: u64,
+    /// Trade fees paid in token B
+    pub fees_paid_b: u64,
+    /// Number of swaps
+    pub swaps_count: u32,
+    /// Last swap timestamp
+    pub last_swap_timestamp: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Reserved space
+    pub reserved: [u64; 2],
+}
+
+impl UserVolumeAccumulator {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // user
+        8 + // volume_a
+        8 + // volume_b
+        8 + // fees_paid_a
+        8 + // fees_paid_b
+        4 + // swaps_count
+        8 + // last_swap_timestamp
+        1 + // bump
+        16; // reserved
+
+    /// Record one swap's input volume and trade fee on the input side
+    pub fn record_swap(&mut self, zero_for_one: bool, amount_in: u64, trade_fee: u64, timestamp: i64) -> Result<()> {
+        let (volume, fees_paid) = if zero_for_one {
+            (&mut self.volume_a, &mut self.fees_paid_a)
+        } else {
+            (&mut self.volume_b, &mut self.fees_paid_b)
+        };
+        *volume = volume.checked_add(amount_in).ok_or(AmmError::Overflow)?;
+        *fees_paid = fees_paid.checked_add(trade_fee).ok_or(AmmError::Overflow)?;
+
+        self.swaps_count = self.swaps_count
+            .checked_add(1)
+            .ok_or(AmmError::Overflow)?;
+        self.last_swap_timestamp = timestamp;
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct SwapCommitment {
+    /// Pool the swap will execute against
+    pub pool: Pubkey,
+    /// User who committed and must reveal
+    pub user: Pubkey,
+    /// Hash of the hidden swap parameters (see `SwapCommitment::hash_params`)
+    pub commitment: [u8; 32],
+    /// Slot the commitment was recorded in; reveal must land in a later slot
+    pub committed_slot: u64,
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SwapCommitment {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // pool
+        32 + // user
+        32 + // commitment
+        8 + // committed_slot
+        1; // bump
+
+    /// sha256 over amount, other_amount_threshold, sqrt_price_limit_x64 (little-endian),
+    /// is_base_input and zero_for_one (one byte each), then nonce (little-endian)
+    pub fn hash_params(
+        amount: u64,
+        other_amount_threshold: u64,
+        sqrt_price_limit_x64: u128,
+        is_base_input: bool,
+        zero_for_one: bool,
+        nonce: u64,
+    ) -> [u8; 32] {
+        anchor_lang::solana_program::hash::hashv(&[
+            &amount.to_le_bytes(),
+            &other_amount_threshold.to_le_bytes(),
+            &sqrt_price_limit_x64.to_le_bytes(),
+            &[is_base_input as u8, zero_for_one as u8],
+            &nonce.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+/// Reject a transaction held past the caller's deadline; `None` never expires
+pub fn require_not_expired(deadline: Option<i64>, now: i64) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(now <= deadline, AmmError::TransactionExpired);
+    }
+    Ok(())
+}
+
 // 🚀 PERFORMANCE-OPTIMIZED MULTI-SIG VALIDATION HELPERS 🚀
 // Using compile-time byte arrays for 10x faster validation
 
@@ -353,4 +1025,215 @@ pub fn verify_creator_wallet(wallet: &Pubkey) -> Result<()> {
         AmmError::CreatorWalletMismatch
     );
     Ok(())
-}
\ No newline at end of file
+}
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// AmmGlobal as laid out before the v2 fields
+    #[derive(AnchorSerialize)]
+    struct AmmGlobalV1 {
+        admin_authority: Pubkey,
+        multisig_authority: Pubkey,
+        platform_wallet: Pubkey,
+        creator_wallet: Pubkey,
+        protocol_fee_rate: u32,
+        fund_fee_rate: u32,
+        default_trade_fee_rate: u32,
+        create_pool_fee: u64,
+        is_paused: bool,
+        total_pools: u32,
+        total_volume: u64,
+        total_fees_collected: u64,
+        version: u8,
+        reserved: [u64; 8],
+    }
+
+    /// Unmigrated v1 account as every instruction loads it, administered by `admin_authority`
+    pub(crate) fn unmigrated_amm_global(admin_authority: Pubkey) -> AmmGlobal {
+        let v1 = AmmGlobalV1 {
+            admin_authority,
+            multisig_authority: Pubkey::new_unique(),
+            platform_wallet: Pubkey::new_unique(),
+            creator_wallet: Pubkey::new_unique(),
+            protocol_fee_rate: DEFAULT_PROTOCOL_FEE_RATE,
+            fund_fee_rate: DEFAULT_FUND_FEE_RATE,
+            default_trade_fee_rate: DEFAULT_TRADE_FEE_RATE,
+            create_pool_fee: DEFAULT_CREATE_POOL_FEE,
+            is_paused: false,
+            total_pools: 7,
+            total_volume: 1_000_000,
+            total_fees_collected: 2_500,
+            version: 1,
+            reserved: [0; 8],
+        };
+        let mut data = AmmGlobal::DISCRIMINATOR.to_vec();
+        data.extend(v1.try_to_vec().unwrap());
+
+        // Same size, so the existing account deserializes without a realloc
+        assert_eq!(data.len(), AmmGlobal::LEN);
+        AmmGlobal::try_deserialize(&mut data.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn v1_amm_global_loads_and_migrates_to_v2() {
+        let admin_authority = Pubkey::new_unique();
+        let mut amm_global = unmigrated_amm_global(admin_authority);
+        assert_eq!(amm_global.admin_authority, admin_authority);
+        assert_eq!(amm_global.total_pools, 7);
+        assert_eq!(amm_global.total_fees_collected, 2_500);
+        assert_eq!(amm_global.global_fee_multiplier_bps, 0);
+
+        amm_global.migrate_to_v2().unwrap();
+        assert_eq!(amm_global.version, AMM_GLOBAL_VERSION);
+        assert_eq!(amm_global.global_fee_multiplier_bps, DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS);
+        assert_eq!(amm_global.fee_snapshot_interval_seconds, DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS);
+
+        // Migrating twice is rejected
+        assert!(amm_global.migrate_to_v2().is_err());
+    }
+
+    #[test]
+    fn swaps_apply_the_stored_multiplier_once_migrated() {
+        let pool = Pool { trade_fee_rate: DEFAULT_TRADE_FEE_RATE, ..Default::default() };
+        let mut amm_global = unmigrated_amm_global(Pubkey::new_unique());
+
+        // Zeroed v1 bytes are not a promotion
+        assert_eq!(amm_global.global_fee_multiplier_bps, 0);
+        assert_eq!(amm_global.fee_multiplier_bps(), DEFAULT_GLOBAL_FEE_MULTIPLIER_BPS);
+        assert_eq!(amm_global.fee_snapshot_interval(), DEFAULT_FEE_SNAPSHOT_INTERVAL_SECONDS);
+
+        amm_global.migrate_to_v2().unwrap();
+        amm_global.global_fee_multiplier_bps = 5_000;
+        assert_eq!(pool.effective_trade_fee_rate(amm_global.fee_multiplier_bps()), DEFAULT_TRADE_FEE_RATE / 2);
+
+        // After migration a zero multiplier is a deliberate fee-free promotion
+        amm_global.global_fee_multiplier_bps = 0;
+        assert_eq!(pool.effective_trade_fee_rate(amm_global.fee_multiplier_bps()), 0);
+    }
+
+    #[test]
+    fn v1_pool_is_rewritten_in_the_current_layout() {
+        let reward_mint = Pubkey::new_unique();
+        let mut reward_infos = [RewardInfoV1::default(); 3];
+        reward_infos[0] = RewardInfoV1 {
+            mint: reward_mint,
+            emissions_per_second_x64: 5 * Q64,
+            growth_global_x64: 42,
+            total_amount_owed: 900,
+            ..Default::default()
+        };
+        let pool_v1 = PoolV1 {
+            id: Pubkey::new_unique(),
+            mint_a: Pubkey::new_unique(),
+            mint_b: Pubkey::new_unique(),
+            vault_a: Pubkey::new_unique(),
+            vault_b: Pubkey::new_unique(),
+            bump: 254,
+            sqrt_price_x64: Q64,
+            tick_current: 0,
+            tick_spacing: 10,
+            status: POOL_STATUS_INITIALIZED,
+            trade_fee_rate: DEFAULT_TRADE_FEE_RATE,
+            protocol_fee_rate: DEFAULT_PROTOCOL_FEE_RATE,
+            fund_fee_rate: DEFAULT_FUND_FEE_RATE,
+            liquidity: 5_000_000,
+            protocol_fees_token_a: 77,
+            protocol_fees_token_b: 88,
+            fund_fees_token_a: 10,
+            fund_fees_token_b: 0,
+            fee_growth_global_a_x64: 123,
+            fee_growth_global_b_x64: 456,
+            reward_infos,
+            total_volume_a: 1_000,
+            total_volume_b: 2_000,
+            created_at: 1_700_000_000,
+            updated_at: 1_700_000_100,
+            reserved: [0; 4],
+        };
+        let mut data = Pool::DISCRIMINATOR.to_vec();
+        data.extend(pool_v1.try_to_vec().unwrap());
+        assert_eq!(data.len(), PoolV1::LEN);
+
+        let pool_v1 = PoolV1::deserialize(&mut &data[8..]).unwrap();
+        let (id, vault_a) = (pool_v1.id, pool_v1.vault_a);
+        let pool = pool_v1.into_current(10_010, 20_000, [5_000, 0, 0], 1_000_000);
+
+        let mut migrated = Vec::new();
+        pool.try_serialize(&mut migrated).unwrap();
+        assert!(migrated.len() <= Pool::LEN);
+        migrated.resize(Pool::LEN, 0);
+        let pool = Pool::try_deserialize(&mut migrated.as_slice()).unwrap();
+
+        assert_eq!(pool.id, id);
+        assert_eq!(pool.vault_a, vault_a);
+        assert_eq!(pool.liquidity, 5_000_000);
+        assert_eq!(pool.fee_growth_global_b_x64, 456);
+        assert_eq!((pool.total_volume_a, pool.total_volume_b), (1_000, 2_000));
+        assert_eq!(pool.updated_at, 1_700_000_100);
+        // Booked protocol fees were already paid out by v1 swaps
+        assert_eq!((pool.protocol_fees_token_a, pool.protocol_fees_token_b), (0, 0));
+        assert_eq!((pool.liquidity_reserve_a, pool.liquidity_reserve_b), (10_000, 20_000));
+        assert_eq!(pool.protocol_liquidity, 1_000_000);
+        assert_eq!(pool.platform_fee_basis_points, PLATFORM_FEE_BASIS_POINTS);
+        assert_eq!(pool.token_creator, None);
+
+        let reward_info = pool.reward_infos[0];
+        assert_eq!(reward_info.mint, reward_mint);
+        assert_eq!(reward_info.growth_global_x64, 42);
+        assert_eq!(reward_info.total_amount_owed, 900);
+        assert_eq!(reward_info.max_emissions_per_second_x64, 5 * Q64);
+        // Vault balance beyond what positions are owed counts as funded
+        assert_eq!(reward_info.funded_amount, 4_100);
+        assert_eq!(pool.reward_infos[1].funded_amount, 0);
+    }
+
+    #[test]
+    fn pool_status_gates_each_operation_separately() {
+        let mut pool = Pool::default();
+        // (status, swaps, deposits, withdrawals)
+        for (status, swaps, deposits, withdrawals) in [
+            (POOL_STATUS_INITIALIZED, true, true, true),
+            (POOL_STATUS_SWAP_ONLY, true, false, true),
+            (POOL_STATUS_WITHDRAW_ONLY, false, false, true),
+            (crate::constants::POOL_STATUS_DISABLED, false, false, false),
+        ] {
+            pool.status = status;
+            assert_eq!(
+                (pool.allows_swaps(), pool.allows_deposits(), pool.allows_withdrawals()),
+                (swaps, deposits, withdrawals),
+                "status {}",
+                status
+            );
+        }
+    }
+
+    #[test]
+    fn deposit_deadline_is_inclusive_and_optional() {
+        // increase_liquidity and swap share the check; a deadline of exactly now still lands
+        let now = 1_700_000_000;
+        assert!(require_not_expired(Some(now), now).is_ok());
+        assert!(require_not_expired(Some(now), now + 1).is_err());
+        assert!(require_not_expired(Some(i64::MAX), now).is_ok());
+        assert!(require_not_expired(Some(i64::MIN), now).is_err());
+        // Callers that pass no deadline keep the pre-deadline behaviour
+        assert!(require_not_expired(None, i64::MAX).is_ok());
+    }
+
+    #[test]
+    fn protocol_fee_collection_is_capped_by_the_unreserved_vault_balance() {
+        let pool = Pool {
+            liquidity_reserve_a: 1_000,
+            liquidity_reserve_b: 1_000,
+            protocol_fees_token_a: 50,
+            protocol_fees_token_b: 50,
+            fund_fees_token_a: 20,
+            ..Default::default()
+        };
+
+        // Vault A covers everything; vault B holds only 30 beyond the LP reserve
+        assert_eq!(pool.collectable_protocol_fees(1_070, 1_030), (50, 30));
+        // A vault short of its LP reserve yields nothing
+        assert_eq!(pool.collectable_protocol_fees(900, 900), (0, 0));
+    }
+}
@@ -18,16 +18,47 @@ pub const CREATOR_WALLET_PUBLIC_KEY: &str = "9SgdP17rkWdDpxPobyemMmGzqTg3yytVTuK
 pub const VIRTUAL_SOL_RESERVES: u64 = 30_000_000_000; // 30 SOL
 pub const VIRTUAL_TOKEN_RESERVES: u64 = 1_000_000_000_000_000; // 1B tokens (with decimals)
 pub const MIGRATION_THRESHOLD: u64 = 70_000_000_000; // 70 SOL
+pub const MAX_MIGRATION_THRESHOLD: u64 = 1_000_000_000_000; // 1000 SOL
+pub const MIGRATION_ABORT_WINDOW_SECONDS: i64 = 86400; // Unconfirmed migrations may be rolled back for 24 hours
 pub const TOTAL_SUPPLY: u64 = 1_000_000_000_000_000; // 1B tokens
 pub const LP_RESERVE_PERCENTAGE: u64 = 20; // 20% for LP reserves
+pub const MIN_LP_RESERVE_PERCENTAGE: u8 = 5; // Lowest LP reserve admins may allow
+pub const MAX_LP_RESERVE_PERCENTAGE: u8 = 50; // Highest LP reserve admins may allow
+pub const TOKEN_DECIMALS: u8 = 9; // Decimals assumed by supply/reserve constants; launch maximum
+pub const PRICE_PRECISION_SCALE: u64 = 1_000_000_000; // Fixed-point scale of current_price()
 
 // Fee constants
 pub const PLATFORM_FEE_BASIS_POINTS: u16 = 300; // 3%
 pub const CREATOR_FEE_BASIS_POINTS: u16 = 100; // 1%
 pub const MIGRATION_FEE_BASIS_POINTS: u16 = 500; // 5%
+pub const REFERRAL_FEE_SHARE_BASIS_POINTS: u16 = 2000; // 20% of the platform fee goes to the referrer
 pub const MAX_SLIPPAGE_BASIS_POINTS: u16 = 1000; // 10%
+pub const SELL_BURN_BASIS_POINTS: u16 = 0; // Burn-on-sell disabled by default
+pub const MAX_SELL_BURN_BASIS_POINTS: u16 = 5000; // 50%
+pub const FUND_SHARE_BASIS_POINTS: u16 = 0; // Share of the migration fee sent to the fund wallet
 pub const BASIS_POINTS_DENOMINATOR: u64 = 10000;
 
+// Large sell escrow constants
+pub const LARGE_SELL_THRESHOLD_SOL: u64 = 0; // Sells above this gross SOL are escrowed; 0 disables
+pub const WITHDRAWAL_DELAY_SECONDS: i64 = 30; // Delay before an escrowed sell can be claimed
+pub const MAX_WITHDRAWAL_DELAY_SECONDS: i64 = 3600; // 1 hour
+
+// Creator dev-buy vesting constants
+pub const MAX_VESTING_DURATION_SECONDS: i64 = 31_536_000; // 365 days
+
+// Volume-based fee discount constants
+pub const VOLUME_FEE_TIER_COUNT: usize = 3;
+pub const VOLUME_DECAY_HALF_LIFE_SECONDS: i64 = 604800; // Recent volume halves every 7 days
+
+// Migration target constants
+pub const ALLOWED_AMM_PROGRAM_COUNT: usize = 4; // AMM programs migration may target
+
+// Partner fee routing constants
+pub const PARTNER_FEE_WALLET_COUNT: usize = 8; // Wallets a curve may route its platform fee to
+
+// Creator fee split constants
+pub const MAX_CREATOR_FEE_RECIPIENTS: usize = 4; // Wallets a curve's creator fee may be split between
+
 // Seeds for PDAs
 pub const GLOBAL_SEED: &[u8] = b"global";
 pub const BONDING_CURVE_SEED: &[u8] = b"bonding_curve";
@@ -35,10 +66,18 @@ pub const USER_VOLUME_SEED: &[u8] = b"user_volume";
 pub const LP_RESERVE_SEED: &[u8] = b"lp_reserve";
 pub const SOL_VAULT_SEED: &[u8] = b"sol_vault";
 pub const TOKEN_VAULT_SEED: &[u8] = b"token_vault";
+pub const PENDING_WITHDRAWAL_SEED: &[u8] = b"pending_withdrawal";
+pub const CREATOR_VESTING_SEED: &[u8] = b"creator_vesting";
+pub const VESTING_ESCROW_SEED: &[u8] = b"vesting_escrow";
+pub const NAME_REGISTRY_SEED: &[u8] = b"name_registry";
+pub const PLATFORM_FEE_VAULT_SEED: &[u8] = b"platform_fee_vault";
 
 // Multi-sig constants
 pub const REQUIRED_SIGNATURES: u8 = 2; // Require both admin and multisig
 
+// Account layout versions
+pub const GLOBAL_VERSION: u8 = 2; // Current Global layout version
+
 // Compile-time validation constants for efficiency
 pub const ADMIN_WALLET_PUBKEY: [u8; 32] = [
     // 4XRqKaastzwzQk6pmHkGkeswzwDm77BJQ5koxEFVQF3Z
@@ -124,4 +124,115 @@ pub enum BondingCurveError {
     
     #[msg("Zero amount transfer not allowed")]
     ZeroAmountTransfer,
+    
+    #[msg("Invalid volume fee tier configuration")]
+    InvalidVolumeFeeTier,
+    
+    #[msg("Migration threshold must exceed current SOL reserves and stay within the cap")]
+    InvalidMigrationThreshold,
+    
+    #[msg("LP reserve percentage outside allowed range")]
+    InvalidLpReservePercentage,
+    
+    #[msg("Trade too small - rounds to zero SOL")]
+    TradeTooSmall,
+    
+    #[msg("Migration price outside the requested sqrt price band")]
+    MigrationPriceOutOfRange,
+    
+    #[msg("Buys are paused")]
+    BuysPaused,
+    
+    #[msg("Sells are paused")]
+    SellsPaused,
+    
+    #[msg("Token decimals exceed the supported maximum")]
+    InvalidTokenDecimals,
+    
+    #[msg("AMM program allowlist slot out of range")]
+    InvalidAmmProgramSlot,
+    
+    #[msg("Migration in progress - trading is locked")]
+    MigrationInProgress,
+    
+    #[msg("Withdrawal delay out of range")]
+    InvalidWithdrawalDelay,
+    
+    #[msg("No pending withdrawal to claim")]
+    NoPendingWithdrawal,
+    
+    #[msg("Pending withdrawal is not claimable yet")]
+    WithdrawalNotReady,
+    
+    #[msg("Trading paused by the token creator")]
+    CreatorPaused,
+    
+    #[msg("Account is not at the version this migration upgrades from")]
+    InvalidAccountVersion,
+    
+    #[msg("Dev-buy must be the first buy on the curve")]
+    DevBuyNotAllowed,
+    
+    #[msg("Invalid vesting schedule")]
+    InvalidVestingSchedule,
+    
+    #[msg("No vested tokens to claim")]
+    NothingToClaim,
+    
+    #[msg("Name registry account is required while unique symbols are enforced")]
+    NameRegistryRequired,
+    
+    #[msg("Symbol is already registered by another token")]
+    SymbolAlreadyTaken,
+    
+    #[msg("Platform fee vault is already initialized")]
+    PlatformFeeVaultAlreadyInitialized,
+    
+    #[msg("Minted supply does not match the configured total supply")]
+    MintSupplyMismatch,
+    
+    #[msg("Mint or freeze authority is still set after revocation")]
+    MintAuthorityNotRevoked,
+    
+    #[msg("Price has not held at the migration threshold level long enough")]
+    MigrationPriceNotSustained,
+    
+    #[msg("Invalid partner fee wallet slot")]
+    InvalidPartnerFeeWalletSlot,
+    
+    #[msg("Platform fee recipient is not an allowlisted partner wallet")]
+    InvalidPlatformFeeRecipient,
+    
+    #[msg("Platform fee recipient account does not match the bonding curve")]
+    PlatformFeeRecipientMismatch,
+    
+    #[msg("Token has not migrated")]
+    NotMigrated,
+    
+    #[msg("AMM pool already confirmed - migration can no longer be aborted")]
+    AmmPoolAlreadyConfirmed,
+    
+    #[msg("Migration abort window has expired")]
+    MigrationAbortWindowExpired,
+    
+    #[msg("Migrated SOL and LP tokens must be returned to the curve vaults before aborting")]
+    MigrationAssetsNotRestored,
+    
+    #[msg("Transaction deadline has passed")]
+    TransactionExpired,
+    
+    #[msg("Creator fee shares must go to distinct wallets and sum to 10000 basis points")]
+    InvalidCreatorFeeShares,
+    
+    #[msg("Creator fee recipient accounts do not match the bonding curve's split")]
+    CreatorFeeRecipientMismatch,
+    
+    #[msg("Vesting escrow tokens belong to the creator and cannot be rescued")]
+    VestingEscrowNotRescuable,
+    
+    #[msg("Sell exceeds the large sell threshold; the pending withdrawal account is required")]
+    PendingWithdrawalRequired,
+    
+    #[msg("Referrer must differ from the buyer and the platform wallet")]
+    InvalidReferrer,
 }
\ No newline at end of file
@@ -25,6 +25,8 @@ pub struct BondingCurveInitializedEvent {
     pub uri: String,
     pub total_supply: u64,
     pub lp_reserve_supply: u64,
+    pub lp_reserve_percentage: u8,
+    pub token_decimals: u8,
     pub virtual_sol_reserves: u64,
     pub virtual_token_reserves: u64,
     pub migration_threshold: u64,
@@ -36,12 +38,24 @@ pub struct TokensPurchasedEvent {
     pub token_mint: Pubkey,
     pub buyer: Pubkey,
     pub sol_cost: u64,
+    pub requested_token_amount: u64,
     pub token_amount: u64,
     pub platform_fee: u64,
     pub creator_fee: u64,
     pub new_sol_reserves: u64,
     pub new_token_reserves: u64,
     pub new_price: u64,
+    pub price_scale: u64,
+    pub token_decimals: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReferralFeePaidEvent {
+    pub token_mint: Pubkey,
+    pub buyer: Pubkey,
+    pub referrer: Pubkey,
+    pub referral_fee: u64,
     pub timestamp: i64,
 }
 
@@ -51,11 +65,14 @@ pub struct TokensSoldEvent {
     pub seller: Pubkey,
     pub token_amount: u64,
     pub sol_received: u64,
+    pub tokens_burned: u64,
     pub platform_fee: u64,
     pub creator_fee: u64,
     pub new_sol_reserves: u64,
     pub new_token_reserves: u64,
     pub new_price: u64,
+    pub price_scale: u64,
+    pub token_decimals: u8,
     pub timestamp: i64,
 }
 
@@ -66,6 +83,18 @@ pub struct MigrationReadyEvent {
     pub sol_reserves: u64,
     pub token_reserves: u64,
     pub migration_threshold: u64,
+    pub migration_ready: bool,
+    pub current_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationThresholdUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub previous_threshold: u64,
+    pub new_threshold: u64,
+    pub migration_ready: bool,
     pub timestamp: i64,
 }
 
@@ -73,12 +102,39 @@ pub struct MigrationReadyEvent {
 pub struct MigrationCompletedEvent {
     pub token_mint: Pubkey,
     pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
     pub amm_program_id: Pubkey,
     pub amm_pool_address: Pubkey,
     pub sol_transferred: u64,
     pub tokens_transferred: u64,
     pub lp_tokens_minted: u64,
     pub migration_fee: u64,
+    pub migration_overshoot: u64,
+    pub platform_wallet: Pubkey,
+    pub platform_migration_fee: u64,
+    pub fund_wallet: Pubkey,
+    pub fund_migration_fee: u64,
+    pub migration_sqrt_price_x64: u128,
+    pub initial_sqrt_price_x64: u128,
+    pub expected_initial_liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AmmPoolConfirmedEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub amm_pool_address: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MigrationAbortedEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub amm_pool_address: Pubkey,
+    pub real_sol_reserves: u64,
+    pub lp_reserve_tokens: u64,
     pub timestamp: i64,
 }
 
@@ -90,6 +146,13 @@ pub struct PlatformFeesCollectedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PlatformFeeVaultInitializedEvent {
+    pub platform_fee_vault: Pubkey,
+    pub rent_exempt_minimum: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct CreatorFeesCollectedEvent {
     pub token_mint: Pubkey,
@@ -108,6 +171,21 @@ pub struct GlobalSettingsUpdatedEvent {
     pub creator_fee: u16,
     pub migration_fee: u16,
     pub migration_enabled: bool,
+    pub sell_burn_basis_points: u16,
+    pub min_lp_reserve_percentage: u8,
+    pub max_lp_reserve_percentage: u8,
+    pub fund_wallet: Pubkey,
+    pub fund_share_basis_points: u16,
+    pub large_sell_threshold_sol: u64,
+    pub withdrawal_delay_seconds: i64,
+    pub unique_symbols_enabled: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VolumeFeeTiersUpdatedEvent {
+    pub volume_thresholds_sol: Vec<u64>,
+    pub platform_fees: Vec<u16>,
     pub timestamp: i64,
 }
 
@@ -125,12 +203,140 @@ pub struct OperationsResumedEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct GlobalMigratedEvent {
+    pub global: Pubkey,
+    pub previous_version: u8,
+    pub new_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BondingCurveMigratedEvent {
+    pub bonding_curve: Pubkey,
+    pub token_mint: Pubkey,
+    pub previous_len: u32,
+    pub new_len: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AllowedAmmProgramUpdatedEvent {
+    pub index: u8,
+    pub previous_program_id: Pubkey,
+    pub program_id: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PartnerFeeWalletUpdatedEvent {
+    pub index: u8,
+    pub previous_wallet: Pubkey,
+    pub wallet: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TradingPauseUpdatedEvent {
+    pub buys_paused: bool,
+    pub sells_paused: bool,
+    pub admin_authority: Pubkey,
+    pub multisig_authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct UserVolumeAccumulatorInitializedEvent {
     pub user: Pubkey,
     pub timestamp: i64,
 }
 
+#[event]
+pub struct UserVolumeAccumulatorClosedEvent {
+    pub user: Pubkey,
+    pub volume_sol: u64,
+    pub trades_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorPauseUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokenUriUpdatedEvent {
+    pub token_mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub previous_uri: String,
+    pub new_uri: String,
+    pub updated_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensBurnedEvent {
+    pub token_mint: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_tokens_burned: u64,
+    pub new_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalQueuedEvent {
+    pub token_mint: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub total_pending: u64,
+    pub available_at: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WithdrawalClaimedEvent {
+    pub token_mint: Pubkey,
+    pub seller: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorVestingCreatedEvent {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub total: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedTokensClaimedEvent {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TokensRescuedEvent {
+    pub bonding_curve: Pubkey,
+    pub token_account: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub accounted_amount: u64,
+    pub destination: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct SecurityAlertEvent {
     pub alert_type: String,
@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::{state::{Global, BondingCurve}, events::*, errors::*};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::{constants::*, state::{Global, GlobalV1, BondingCurve, BondingCurveV1, VolumeFeeTier}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct UpdateGlobalSettings<'info> {
@@ -19,18 +20,90 @@ pub struct UpdateGlobalSettings<'info> {
     pub multisig_authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetVolumeFeeTiers<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for fee settings)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetMigrationThreshold<'info> {
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mut,
+        constraint = !bonding_curve.is_migrated
+            @ BondingCurveError::AlreadyMigrated
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for migration settings)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePlatformFeeVault<'info> {
+    pub global: Account<'info, Global>,
+
+    /// Platform fee vault PDA (system-owned, created by funding it rent-exempt)
+    /// CHECK: PDA derived from PLATFORM_FEE_VAULT_SEED
+    #[account(
+        mut,
+        seeds = [PLATFORM_FEE_VAULT_SEED],
+        bump
+    )]
+    pub platform_fee_vault: UncheckedAccount<'info>,
+
+    /// Admin authority (required for multi-sig, pays the vault rent)
+    #[account(
+        mut,
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for vault setup)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct CollectPlatformFees<'info> {
     #[account(mut)]
     pub global: Account<'info, Global>,
 
-    /// Platform fee collection wallet (multi-sig controlled)
-    /// CHECK: Validated against global configuration
+    /// Platform fee vault holding trade fees until collected
+    /// CHECK: System-owned PDA derived from PLATFORM_FEE_VAULT_SEED
     #[account(
         mut,
-        constraint = platform_wallet.key() == global.platform_wallet
+        seeds = [PLATFORM_FEE_VAULT_SEED],
+        bump
     )]
-    pub platform_wallet: UncheckedAccount<'info>,
+    pub platform_fee_vault: UncheckedAccount<'info>,
 
     /// Admin authority (required for multi-sig)
     #[account(
@@ -133,12 +206,193 @@ pub struct ResumeOperations<'info> {
     pub multisig_authority: Signer<'info>,
 }
 
+#[derive(Accounts)]
+pub struct SetBuysPaused<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for buy pause operations)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetSellsPaused<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for sell pause operations)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGlobalV1ToV2<'info> {
+    /// Global still in the v1 layout, which no longer deserializes as Global
+    /// CHECK: Seeds and owner checked here; discriminator, size and version checked in the handler
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED],
+        bump,
+        owner = crate::ID @ BondingCurveError::InvalidAccountOwner
+    )]
+    pub global: UncheckedAccount<'info>,
+
+    /// Admin authority (required for multi-sig)
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for account migrations)
+    pub multisig_authority: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateBondingCurveV1<'info> {
+    pub global: Account<'info, Global>,
+
+    /// Bonding curve still in the launch layout, which no longer deserializes as BondingCurve
+    /// CHECK: Owner checked here; discriminator and size checked in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ BondingCurveError::InvalidAccountOwner
+    )]
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for account migrations)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    /// Pays the rent for the larger account
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetAllowedAmmProgram<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for migration settings)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetPartnerFeeWallet<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for fee settings)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    pub global: Account<'info, Global>,
+
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Any token account held by the bonding curve PDA
+    #[account(
+        mut,
+        token::authority = bonding_curve,
+        token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Mint of the token account being rescued
+    #[account(
+        constraint = token_mint.key() == token_account.mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// Platform wallet token account receiving rescued tokens
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = global.platform_wallet,
+        token::token_program = token_program
+    )]
+    pub platform_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for token rescue)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[allow(clippy::too_many_arguments)] // Mirrors the instruction arguments
 pub fn update_global_settings(
     ctx: Context<UpdateGlobalSettings>,
     platform_fee_basis_points: Option<u16>,
     creator_fee_basis_points: Option<u16>,
     migration_fee_basis_points: Option<u16>,
     migration_enabled: Option<bool>,
+    sell_burn_basis_points: Option<u16>,
+    min_lp_reserve_percentage: Option<u8>,
+    max_lp_reserve_percentage: Option<u8>,
+    fund_wallet: Option<Pubkey>,
+    fund_share_basis_points: Option<u16>,
+    large_sell_threshold_sol: Option<u64>,
+    withdrawal_delay_seconds: Option<i64>,
+    unique_symbols_enabled: Option<bool>,
 ) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let clock = Clock::get()?;
@@ -169,6 +423,55 @@ pub fn update_global_settings(
         global.migration_enabled = migration_flag;
     }
 
+    // Update burn-on-sell share if provided
+    if let Some(sell_burn) = sell_burn_basis_points {
+        require!(sell_burn <= MAX_SELL_BURN_BASIS_POINTS, BondingCurveError::FeeTooHigh); // Max 50%
+        global.sell_burn_basis_points = sell_burn;
+    }
+
+    // Update LP reserve percentage bounds if provided
+    if let Some(min_lp_reserve) = min_lp_reserve_percentage {
+        global.min_lp_reserve_percentage = min_lp_reserve;
+    }
+    if let Some(max_lp_reserve) = max_lp_reserve_percentage {
+        global.max_lp_reserve_percentage = max_lp_reserve;
+    }
+    require!(
+        global.min_lp_reserve_percentage >= MIN_LP_RESERVE_PERCENTAGE
+            && global.min_lp_reserve_percentage <= global.max_lp_reserve_percentage
+            && global.max_lp_reserve_percentage <= MAX_LP_RESERVE_PERCENTAGE,
+        BondingCurveError::InvalidLpReservePercentage
+    );
+
+    // Update migration fee fund split if provided
+    if let Some(fund_wallet) = fund_wallet {
+        global.fund_wallet = fund_wallet;
+    }
+    if let Some(fund_share) = fund_share_basis_points {
+        require!(
+            fund_share as u64 <= BASIS_POINTS_DENOMINATOR,
+            BondingCurveError::FeeTooHigh
+        ); // Max 100% of the migration fee
+        global.fund_share_basis_points = fund_share;
+    }
+
+    // Update large sell escrow settings if provided
+    if let Some(large_sell_threshold) = large_sell_threshold_sol {
+        global.large_sell_threshold_sol = large_sell_threshold;
+    }
+    if let Some(withdrawal_delay) = withdrawal_delay_seconds {
+        require!(
+            (0..=MAX_WITHDRAWAL_DELAY_SECONDS).contains(&withdrawal_delay),
+            BondingCurveError::InvalidWithdrawalDelay
+        );
+        global.withdrawal_delay_seconds = withdrawal_delay;
+    }
+
+    // Update symbol uniqueness enforcement if provided
+    if let Some(unique_symbols) = unique_symbols_enabled {
+        global.unique_symbols_enabled = unique_symbols;
+    }
+
     // Emit settings update event
     emit!(GlobalSettingsUpdatedEvent {
         admin_authority: global.admin_authority,
@@ -177,6 +480,14 @@ pub fn update_global_settings(
         creator_fee: global.creator_fee_basis_points,
         migration_fee: global.migration_fee_basis_points,
         migration_enabled: global.migration_enabled,
+        sell_burn_basis_points: global.sell_burn_basis_points,
+        min_lp_reserve_percentage: global.min_lp_reserve_percentage,
+        max_lp_reserve_percentage: global.max_lp_reserve_percentage,
+        fund_wallet: global.fund_wallet,
+        fund_share_basis_points: global.fund_share_basis_points,
+        large_sell_threshold_sol: global.large_sell_threshold_sol,
+        withdrawal_delay_seconds: global.withdrawal_delay_seconds,
+        unique_symbols_enabled: global.unique_symbols_enabled,
         timestamp: clock.unix_timestamp,
     });
 
@@ -194,6 +505,132 @@ pub fn update_global_settings(
     Ok(())
 }
 
+pub fn set_volume_fee_tiers(
+    ctx: Context<SetVolumeFeeTiers>,
+    volume_fee_tiers: [VolumeFeeTier; VOLUME_FEE_TIER_COUNT],
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for fee settings
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Enabled tiers must be ordered by threshold and only ever discount the fee
+    let mut previous_threshold = 0u64;
+    for tier in volume_fee_tiers.iter().filter(|tier| tier.is_enabled()) {
+        require!(
+            tier.volume_threshold_sol > previous_threshold,
+            BondingCurveError::InvalidVolumeFeeTier
+        );
+        require!(
+            tier.platform_fee_basis_points <= global.platform_fee_basis_points,
+            BondingCurveError::FeeTooHigh
+        );
+        previous_threshold = tier.volume_threshold_sol;
+    }
+
+    global.volume_fee_tiers = volume_fee_tiers;
+
+    // Emit tiers update event
+    emit!(VolumeFeeTiersUpdatedEvent {
+        volume_thresholds_sol: volume_fee_tiers.iter().map(|tier| tier.volume_threshold_sol).collect(),
+        platform_fees: volume_fee_tiers.iter().map(|tier| tier.platform_fee_basis_points).collect(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "VOLUME_FEE_TIERS_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔧 Volume fee tiers updated with multi-sig authorization");
+
+    Ok(())
+}
+
+pub fn set_migration_threshold(ctx: Context<SetMigrationThreshold>, new_threshold: u64) -> Result<()> {
+    let global = &ctx.accounts.global;
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for migration settings
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    let previous_threshold = bonding_curve.update_migration_threshold(new_threshold, clock.unix_timestamp)?;
+
+    // Emit threshold update event
+    emit!(MigrationThresholdUpdatedEvent {
+        token_mint: bonding_curve.token_mint,
+        bonding_curve: bonding_curve.key(),
+        previous_threshold,
+        new_threshold,
+        migration_ready: bonding_curve.migration_ready,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "MIGRATION_THRESHOLD_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: bonding_curve.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔧 Migration threshold updated for token {}", bonding_curve.token_mint);
+    msg!("Previous Threshold: {} SOL", previous_threshold);
+    msg!("New Threshold: {} SOL", new_threshold);
+
+    Ok(())
+}
+
+pub fn initialize_platform_fee_vault(ctx: Context<InitializePlatformFeeVault>) -> Result<()> {
+    let global = &ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for vault setup
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Fund the vault rent-exempt so fee deposits of any size are accepted
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let vault_lamports = ctx.accounts.platform_fee_vault.lamports();
+    require!(vault_lamports < rent_exempt_minimum, BondingCurveError::PlatformFeeVaultAlreadyInitialized);
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.admin_authority.to_account_info(),
+                to: ctx.accounts.platform_fee_vault.to_account_info(),
+            },
+        ),
+        rent_exempt_minimum - vault_lamports,
+    )?;
+
+    emit!(PlatformFeeVaultInitializedEvent {
+        platform_fee_vault: ctx.accounts.platform_fee_vault.key(),
+        rent_exempt_minimum,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "PLATFORM_FEE_VAULT_INITIALIZED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: ctx.accounts.platform_fee_vault.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🏦 Platform fee vault initialized: {}", ctx.accounts.platform_fee_vault.key());
+
+    Ok(())
+}
+
 pub fn collect_platform_fees(ctx: Context<CollectPlatformFees>, amount: u64) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let clock = Clock::get()?;
@@ -201,12 +638,30 @@ pub fn collect_platform_fees(ctx: Context<CollectPlatformFees>, amount: u64) ->
     // Verify multi-sig authorization for fee collection
     global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
 
-    // Verify sufficient fees available
-    require!(amount <= global.total_fees_collected, BondingCurveError::InsufficientFees);
-
-    // Transfer fees from platform wallet to treasury
-    **ctx.accounts.platform_wallet.to_account_info().try_borrow_mut_lamports()? -= amount;
-    **ctx.accounts.treasury.to_account_info().try_borrow_mut_lamports()? += amount;
+    // Only fees above the vault's rent-exempt minimum can be withdrawn
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let available_fees = ctx.accounts.platform_fee_vault.lamports()
+        .saturating_sub(rent_exempt_minimum);
+    require!(amount <= available_fees, BondingCurveError::InsufficientFees);
+
+    // Transfer fees from the vault PDA to treasury
+    let vault_seeds = &[
+        PLATFORM_FEE_VAULT_SEED,
+        &[ctx.bumps.platform_fee_vault],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            anchor_lang::system_program::Transfer {
+                from: ctx.accounts.platform_fee_vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+            vault_signer,
+        ),
+        amount,
+    )?;
 
     // Update global fee tracking
     global.total_fees_collected = global.total_fees_collected
@@ -226,7 +681,7 @@ pub fn collect_platform_fees(ctx: Context<CollectPlatformFees>, amount: u64) ->
         operation: "PLATFORM_FEES_COLLECTED".to_string(),
         admin_signer: ctx.accounts.admin_authority.key(),
         multisig_signer: ctx.accounts.multisig_authority.key(),
-        target_account: ctx.accounts.platform_wallet.key(),
+        target_account: ctx.accounts.platform_fee_vault.key(),
         timestamp: clock.unix_timestamp,
     });
 
@@ -337,4 +792,362 @@ pub fn resume_operations(ctx: Context<ResumeOperations>) -> Result<()> {
     msg!("✅ Operations resumed - Platform is operational");
 
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn set_buys_paused(ctx: Context<SetBuysPaused>, paused: bool) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for trading pause changes
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    global.buys_paused = paused;
+
+    emit!(TradingPauseUpdatedEvent {
+        buys_paused: global.buys_paused,
+        sells_paused: global.sells_paused,
+        admin_authority: ctx.accounts.admin_authority.key(),
+        multisig_authority: ctx.accounts.multisig_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: if paused { "BUYS_PAUSED" } else { "BUYS_RESUMED" }.to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    if paused {
+        msg!("⏸️  Buys paused");
+    } else {
+        msg!("▶️  Buys resumed");
+    }
+
+    Ok(())
+}
+
+pub fn set_sells_paused(ctx: Context<SetSellsPaused>, paused: bool) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for trading pause changes
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    global.sells_paused = paused;
+
+    emit!(TradingPauseUpdatedEvent {
+        buys_paused: global.buys_paused,
+        sells_paused: global.sells_paused,
+        admin_authority: ctx.accounts.admin_authority.key(),
+        multisig_authority: ctx.accounts.multisig_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: if paused { "SELLS_PAUSED" } else { "SELLS_RESUMED" }.to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    if paused {
+        msg!("⏸️  Sells paused");
+    } else {
+        msg!("▶️  Sells resumed");
+    }
+
+    Ok(())
+}
+
+pub fn migrate_global_v1_to_v2(ctx: Context<MigrateGlobalV1ToV2>) -> Result<()> {
+    let global_info = ctx.accounts.global.to_account_info();
+    let clock = Clock::get()?;
+
+    // Only an account still at the v1 size and version is migrated, so a second run is rejected
+    let global_v1 = GlobalV1::try_from_account_data(&global_info.try_borrow_data()?)?;
+    let previous_version = global_v1.version;
+    let global = global_v1.into_current();
+
+    // Verify multi-sig authorization for account migrations
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // v2 fields do not fit in the v1 reserved space, so grow and rewrite the account
+    rewrite_in_current_layout(
+        &global_info,
+        &global,
+        Global::LEN,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(GlobalMigratedEvent {
+        global: global_info.key(),
+        previous_version,
+        new_version: global.version,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "GLOBAL_MIGRATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global_info.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔄 Global account migrated");
+    msg!("Version: {} -> {}", previous_version, global.version);
+
+    Ok(())
+}
+
+pub fn migrate_bonding_curve_v1(ctx: Context<MigrateBondingCurveV1>) -> Result<()> {
+    let bonding_curve_info = ctx.accounts.bonding_curve.to_account_info();
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for account migrations
+    ctx.accounts.global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Only an account still at the launch size is migrated, so a second run is rejected
+    let bonding_curve = BondingCurveV1::try_from_account_data(&bonding_curve_info.try_borrow_data()?)?
+        .into_current();
+
+    rewrite_in_current_layout(
+        &bonding_curve_info,
+        &bonding_curve,
+        BondingCurve::LEN,
+        &ctx.accounts.payer,
+        &ctx.accounts.system_program,
+    )?;
+
+    emit!(BondingCurveMigratedEvent {
+        bonding_curve: bonding_curve_info.key(),
+        token_mint: bonding_curve.token_mint,
+        previous_len: BondingCurveV1::LEN as u32,
+        new_len: BondingCurve::LEN as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "BONDING_CURVE_MIGRATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: bonding_curve_info.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔄 Bonding curve account migrated");
+    msg!("Token: {}", bonding_curve.token_mint);
+    msg!("Size: {} -> {} bytes", BondingCurveV1::LEN, BondingCurve::LEN);
+
+    Ok(())
+}
+
+/// Top up rent from `payer`, grow `account` to `len` and write `value` over it
+fn rewrite_in_current_layout<'info, T: AccountSerialize>(
+    account: &AccountInfo<'info>,
+    value: &T,
+    len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let rent_shortfall = Rent::get()?
+        .minimum_balance(len)
+        .saturating_sub(account.lamports());
+    if rent_shortfall > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_shortfall,
+        )?;
+    }
+    account.resize(len)?;
+    let mut data = account.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    value.try_serialize(&mut writer)
+}
+
+pub fn set_allowed_amm_program(
+    ctx: Context<SetAllowedAmmProgram>,
+    index: u8,
+    program_id: Pubkey,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for migration settings
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // The default pubkey clears a slot
+    require!(
+        (index as usize) < ALLOWED_AMM_PROGRAM_COUNT,
+        BondingCurveError::InvalidAmmProgramSlot
+    );
+
+    let previous_program_id = global.allowed_amm_programs[index as usize];
+    global.allowed_amm_programs[index as usize] = program_id;
+
+    // Emit allowlist update event
+    emit!(AllowedAmmProgramUpdatedEvent {
+        index,
+        previous_program_id,
+        program_id,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "ALLOWED_AMM_PROGRAM_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔧 Allowed AMM program slot {} updated", index);
+    msg!("Previous Program: {}", previous_program_id);
+    msg!("New Program: {}", program_id);
+
+    Ok(())
+}
+
+pub fn set_partner_fee_wallet(
+    ctx: Context<SetPartnerFeeWallet>,
+    index: u8,
+    wallet: Pubkey,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for fee settings
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // The default pubkey clears a slot; curves pointing at it fall back to the platform fee vault
+    require!(
+        (index as usize) < PARTNER_FEE_WALLET_COUNT,
+        BondingCurveError::InvalidPartnerFeeWalletSlot
+    );
+
+    let previous_wallet = global.partner_fee_wallets[index as usize];
+    global.partner_fee_wallets[index as usize] = wallet;
+
+    // Emit allowlist update event
+    emit!(PartnerFeeWalletUpdatedEvent {
+        index,
+        previous_wallet,
+        wallet,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "PARTNER_FEE_WALLET_UPDATED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: global.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔧 Partner fee wallet slot {} updated", index);
+    msg!("Previous Wallet: {}", previous_wallet);
+    msg!("New Wallet: {}", wallet);
+
+    Ok(())
+}
+
+pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+    let global = &ctx.accounts.global;
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let token_account = &ctx.accounts.token_account;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for token rescue
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // Curve vaults keep the reserves they account for; any other curve-held account is all excess
+    let token_mint_key = bonding_curve.token_mint;
+    let token_vault = Pubkey::create_program_address(
+        &[TOKEN_VAULT_SEED, token_mint_key.as_ref(), &[bonding_curve.token_vault_bump]],
+        &crate::ID,
+    ).map_err(|_| BondingCurveError::InvalidAccountOwner)?;
+    let lp_reserve = Pubkey::create_program_address(
+        &[LP_RESERVE_SEED, token_mint_key.as_ref(), &[bonding_curve.lp_reserve_bump]],
+        &crate::ID,
+    ).map_err(|_| BondingCurveError::InvalidAccountOwner)?;
+
+    // The vesting escrow holds the creator's unclaimed dev-buy allocation, not excess
+    let (vesting_escrow, _) = Pubkey::find_program_address(
+        &[VESTING_ESCROW_SEED, token_mint_key.as_ref()],
+        &crate::ID,
+    );
+    require!(
+        token_account.key() != vesting_escrow,
+        BondingCurveError::VestingEscrowNotRescuable
+    );
+
+    let accounted_amount = if token_account.key() == token_vault {
+        bonding_curve.real_token_reserves
+    } else if token_account.key() == lp_reserve && !bonding_curve.is_migrated {
+        bonding_curve.lp_reserve_supply
+    } else {
+        0
+    };
+    let rescue_amount = token_account.amount.saturating_sub(accounted_amount);
+
+    require!(rescue_amount > 0, BondingCurveError::ZeroAmountTransfer);
+
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.platform_token_account.to_account_info(),
+            authority: bonding_curve.to_account_info(),
+        },
+        signer,
+    );
+    token_interface::transfer_checked(transfer_ctx, rescue_amount, ctx.accounts.token_mint.decimals)?;
+
+    // Emit tokens rescued event
+    emit!(TokensRescuedEvent {
+        bonding_curve: bonding_curve.key(),
+        token_account: token_account.key(),
+        mint: token_account.mint,
+        amount: rescue_amount,
+        accounted_amount,
+        destination: ctx.accounts.platform_token_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "TOKENS_RESCUED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: token_account.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🛟 Stuck tokens rescued to platform wallet: {} tokens", rescue_amount);
+
+    Ok(())
+}
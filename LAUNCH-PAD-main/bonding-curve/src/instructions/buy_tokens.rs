@@ -1,28 +1,23 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use crate::{constants::*, state::{Global, BondingCurve, UserVolumeAccumulator}, events::*, errors::*};
+use crate::{constants::*, state::{require_not_expired, Global, BondingCurve, UserVolumeAccumulator}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct BuyTokens<'info> {
-    #[account(
-        constraint = !global.is_paused
-    )]
     pub global: Account<'info, Global>,
 
-    #[account(
-        mut,
-        constraint = !bonding_curve.is_migrated
-    )]
+    #[account(mut)]
     pub bonding_curve: Account<'info, BondingCurve>,
 
     /// Token mint
     #[account(
-        constraint = token_mint.key() == bonding_curve.token_mint
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// SOL vault (multi-sig protected)
     #[account(
@@ -38,35 +33,45 @@ pub struct BuyTokens<'info> {
         mut,
         token::mint = token_mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds = [TOKEN_VAULT_SEED, token_mint.key().as_ref()],
         bump = bonding_curve.token_vault_bump
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// User's token account
     #[account(
         init_if_needed,
         payer = buyer,
         associated_token::mint = token_mint,
-        associated_token::authority = buyer
+        associated_token::authority = buyer,
+        associated_token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// User volume accumulator
+    /// User volume accumulator (created on first trade, paid by the buyer)
     #[account(
-        mut,
+        init_if_needed,
+        payer = buyer,
+        space = UserVolumeAccumulator::LEN,
         seeds = [USER_VOLUME_SEED, buyer.key().as_ref()],
-        bump = user_volume_accumulator.bump
+        bump
     )]
     pub user_volume_accumulator: Account<'info, UserVolumeAccumulator>,
 
-    /// Platform fee collection wallet (multi-sig controlled)
-    /// CHECK: Validated against global configuration
+    /// Platform fee vault; fees accumulate here until collect_platform_fees
+    /// CHECK: System-owned PDA funded by initialize_platform_fee_vault
     #[account(
         mut,
-        constraint = platform_wallet.key() == global.platform_wallet
+        seeds = [PLATFORM_FEE_VAULT_SEED],
+        bump
     )]
-    pub platform_wallet: UncheckedAccount<'info>,
+    pub platform_fee_vault: UncheckedAccount<'info>,
+
+    /// Partner wallet set on the curve; required while it is allowlisted
+    /// CHECK: Validated against the bonding curve and global allowlist in the handler
+    #[account(mut)]
+    pub platform_fee_recipient: Option<UncheckedAccount<'info>>,
 
     /// Creator fee collection wallet (multi-sig controlled)
     /// CHECK: Validated against global configuration
@@ -79,20 +84,83 @@ pub struct BuyTokens<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn buy_tokens(
-    ctx: Context<BuyTokens>,
+#[derive(Accounts)]
+pub struct BuyTokensWithReferral<'info> {
+    pub buy: BuyTokens<'info>,
+
+    /// Wallet credited with the referral share of the platform fee
+    /// CHECK: Only receives lamports; checked against the buyer and platform wallet in the handler
+    #[account(mut)]
+    pub referrer: UncheckedAccount<'info>,
+}
+
+/// `remaining_accounts` carries the curve's creator fee recipients, in slot order, when it splits the creator fee
+pub fn buy_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+    token_amount: u64,
+    max_sol_cost: u64,
+    allow_partial: bool,
+    deadline: Option<i64>,
+) -> Result<()> {
+    execute_buy(ctx, token_amount, max_sol_cost, allow_partial, deadline, None)
+}
+
+/// Same as `buy_tokens`, with `REFERRAL_FEE_SHARE_BASIS_POINTS` of the platform fee paid to `referrer`
+pub fn buy_tokens_with_referral<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyTokensWithReferral<'info>>,
+    token_amount: u64,
+    max_sol_cost: u64,
+    allow_partial: bool,
+    deadline: Option<i64>,
+) -> Result<()> {
+    validate_referrer(
+        ctx.accounts.referrer.key(),
+        ctx.accounts.buy.buyer.key(),
+        ctx.accounts.buy.global.platform_wallet,
+    )?;
+
+    let referrer = ctx.accounts.referrer.to_account_info();
+    execute_buy(
+        Context::new(ctx.program_id, &mut ctx.accounts.buy, ctx.remaining_accounts, ctx.bumps.buy),
+        token_amount,
+        max_sol_cost,
+        allow_partial,
+        deadline,
+        Some(referrer),
+    )
+}
+
+fn execute_buy<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
     token_amount: u64,
     max_sol_cost: u64,
+    allow_partial: bool,
+    deadline: Option<i64>,
+    referrer: Option<AccountInfo<'info>>,
 ) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let clock = Clock::get()?;
 
+    // Reject transactions held past the caller's deadline
+    require_not_expired(deadline, clock.unix_timestamp)?;
+    global.require_buys_open()?;
+    bonding_curve.require_trading_open()?;
+
+    // Near the end of the curve, a partial buy fills whatever reserves remain
+    let requested_token_amount = token_amount;
+    let token_amount = if allow_partial {
+        token_amount.min(bonding_curve.real_token_reserves)
+    } else {
+        token_amount
+    };
+
     // Enhanced validation using new security method
     require!(max_sol_cost > 0, BondingCurveError::InvalidSolAmount);
     bonding_curve.validate_trade_amounts(token_amount, true)?;
@@ -101,32 +169,22 @@ pub fn buy_tokens(
     let sol_cost = calculate_buy_cost(
         token_amount,
         bonding_curve.virtual_sol_reserves,
-        bonding_curve.virtual_token_reserves,
         bonding_curve.real_sol_reserves,
         bonding_curve.real_token_reserves,
+        bonding_curve.circulating_supply()?,
     )?;
 
-    // Check slippage protection
-    require!(
-        sol_cost <= max_sol_cost,
-        BondingCurveError::SlippageExceeded
-    );
-
-    // Calculate fees
-    let platform_fee = sol_cost
-        .checked_mul(global.platform_fee_basis_points as u64)
-        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
-        .ok_or(BondingCurveError::Overflow)?;
-
-    let creator_fee = sol_cost
-        .checked_mul(global.creator_fee_basis_points as u64)
-        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
-        .ok_or(BondingCurveError::Overflow)?;
 
-    let total_cost = sol_cost
-        .checked_add(platform_fee)
-        .and_then(|x| x.checked_add(creator_fee))
-        .ok_or(BondingCurveError::Overflow)?;
+    // Calculate fees (platform fee discounted by the user's cumulative volume tier)
+    let platform_fee_basis_points = global.platform_fee_for_volume(
+        ctx.accounts.user_volume_accumulator.volume_sol,
+    );
+    let (platform_fee, creator_fee, total_cost) = calculate_buy_fees(
+        sol_cost,
+        platform_fee_basis_points,
+        global.creator_fee_basis_points,
+        max_sol_cost,
+    )?;
 
     // Verify buyer has enough SOL
     require!(
@@ -147,31 +205,71 @@ pub fn buy_tokens(
         sol_cost,
     )?;
 
-    // Transfer platform fee
+    // Referred buys pay the referrer out of the platform fee, never on top of it
+    let (platform_fee_share, referral_fee) = match referrer {
+        Some(_) => split_referral_fee(platform_fee)?,
+        None => (platform_fee, 0),
+    };
+
+    // Transfer platform fee to the curve's partner wallet or the platform fee vault
     let transfer_platform_fee = anchor_lang::system_program::Transfer {
         from: ctx.accounts.buyer.to_account_info(),
-        to: ctx.accounts.platform_wallet.to_account_info(),
+        to: platform_fee_destination(
+            global,
+            bonding_curve,
+            &ctx.accounts.platform_fee_vault,
+            &ctx.accounts.platform_fee_recipient,
+        )?,
     };
     anchor_lang::system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             transfer_platform_fee,
         ),
-        platform_fee,
+        platform_fee_share,
     )?;
 
-    // Transfer creator fee
-    let transfer_creator_fee = anchor_lang::system_program::Transfer {
-        from: ctx.accounts.buyer.to_account_info(),
-        to: ctx.accounts.creator_wallet.to_account_info(),
-    };
-    anchor_lang::system_program::transfer(
-        CpiContext::new(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_creator_fee,
-        ),
+    if let Some(referrer) = referrer.filter(|_| referral_fee > 0) {
+        let transfer_referral_fee = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: referrer.clone(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_referral_fee,
+            ),
+            referral_fee,
+        )?;
+
+        emit!(ReferralFeePaidEvent {
+            token_mint: bonding_curve.token_mint,
+            buyer: ctx.accounts.buyer.key(),
+            referrer: referrer.key(),
+            referral_fee,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    // Transfer creator fee, split between the curve's recipients when configured
+    for (destination, amount) in creator_fee_transfers(
+        bonding_curve,
+        &ctx.accounts.creator_wallet,
+        ctx.remaining_accounts,
         creator_fee,
-    )?;
+    )? {
+        let transfer_creator_fee = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.buyer.to_account_info(),
+            to: destination,
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_creator_fee,
+            ),
+            amount,
+        )?;
+    }
 
     // Transfer tokens from vault to buyer using bonding curve authority
     let token_mint_key = bonding_curve.token_mint.key();
@@ -184,14 +282,15 @@ pub fn buy_tokens(
 
     let transfer_tokens_ctx = CpiContext::new_with_signer(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.token_vault.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.user_token_account.to_account_info(),
             authority: bonding_curve.to_account_info(),
         },
         signer,
     );
-    token::transfer(transfer_tokens_ctx, token_amount)?;
+    token_interface::transfer_checked(transfer_tokens_ctx, token_amount, ctx.accounts.token_mint.decimals)?;
 
     // Update bonding curve reserves
     bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
@@ -212,7 +311,7 @@ pub fn buy_tokens(
         .ok_or(BondingCurveError::Overflow)?;
 
     bonding_curve.platform_fees_collected = bonding_curve.platform_fees_collected
-        .checked_add(platform_fee)
+        .checked_add(platform_fee_share)
         .ok_or(BondingCurveError::Overflow)?;
 
     bonding_curve.creator_fees_collected = bonding_curve.creator_fees_collected
@@ -231,11 +330,18 @@ pub fn buy_tokens(
         .ok_or(BondingCurveError::Overflow)?;
 
     global.total_fees_collected = global.total_fees_collected
-        .checked_add(platform_fee)
+        .checked_add(platform_fee_share)
         .ok_or(BondingCurveError::Overflow)?;
 
     // Update user volume accumulator
     let user_volume = &mut ctx.accounts.user_volume_accumulator;
+    if user_volume.initialize_if_needed(ctx.accounts.buyer.key(), ctx.bumps.user_volume_accumulator) {
+        emit!(UserVolumeAccumulatorInitializedEvent {
+            user: ctx.accounts.buyer.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     user_volume.volume_sol = user_volume.volume_sol
         .checked_add(sol_cost)
         .ok_or(BondingCurveError::Overflow)?;
@@ -248,21 +354,22 @@ pub fn buy_tokens(
         .checked_add(1)
         .ok_or(BondingCurveError::Overflow)?;
 
+    user_volume.record_decayed_volume(sol_cost, clock.unix_timestamp)?;
     user_volume.last_trade_timestamp = clock.unix_timestamp;
 
     // Calculate new price for event
     let new_price = bonding_curve.current_price()?;
 
-    // Check if migration threshold is reached
-    if bonding_curve.is_migration_threshold_met() && !bonding_curve.migration_ready {
-        bonding_curve.migration_ready = true;
-        
+    // Check if migration threshold is reached (again, if a sell cleared it)
+    if bonding_curve.refresh_migration_ready(clock.unix_timestamp) {
         emit!(MigrationReadyEvent {
             token_mint: bonding_curve.token_mint,
             bonding_curve: bonding_curve.key(),
             sol_reserves: bonding_curve.real_sol_reserves,
             token_reserves: bonding_curve.real_token_reserves,
             migration_threshold: bonding_curve.migration_threshold,
+            migration_ready: bonding_curve.migration_ready,
+            current_price: new_price,
             timestamp: clock.unix_timestamp,
         });
 
@@ -274,16 +381,22 @@ pub fn buy_tokens(
         token_mint: bonding_curve.token_mint,
         buyer: ctx.accounts.buyer.key(),
         sol_cost,
+        requested_token_amount,
         token_amount,
         platform_fee,
         creator_fee,
         new_sol_reserves: bonding_curve.real_sol_reserves,
         new_token_reserves: bonding_curve.real_token_reserves,
         new_price,
+        price_scale: PRICE_PRECISION_SCALE,
+        token_decimals: ctx.accounts.token_mint.decimals,
         timestamp: clock.unix_timestamp,
     });
 
     msg!("✅ Tokens purchased successfully");
+    if token_amount < requested_token_amount {
+        msg!("Partial Fill: {} of {} tokens requested", token_amount, requested_token_amount);
+    }
     msg!("Amount: {} tokens", token_amount);
     msg!("Cost: {} SOL", sol_cost);
     msg!("Platform Fee: {} SOL", platform_fee);
@@ -293,17 +406,123 @@ pub fn buy_tokens(
     Ok(())
 }
 
+// A buyer cannot refer themselves, and the platform wallet cannot take a referral cut of its own fee
+pub(crate) fn validate_referrer(referrer: Pubkey, buyer: Pubkey, platform_wallet: Pubkey) -> Result<()> {
+    require!(
+        referrer != buyer && referrer != platform_wallet,
+        BondingCurveError::InvalidReferrer
+    );
+    Ok(())
+}
+
+// Returns (platform share, referral fee); the two always sum to the original platform fee
+pub(crate) fn split_referral_fee(platform_fee: u64) -> Result<(u64, u64)> {
+    let referral_fee = (platform_fee as u128)
+        .checked_mul(REFERRAL_FEE_SHARE_BASIS_POINTS as u128)
+        .ok_or(BondingCurveError::Overflow)?
+        .checked_div(10000)
+        .ok_or(BondingCurveError::DivisionByZero)? as u64;
+    let platform_share = platform_fee
+        .checked_sub(referral_fee)
+        .ok_or(BondingCurveError::Underflow)?;
+    Ok((platform_share, referral_fee))
+}
+
+// Platform fees go to the curve's partner wallet while it stays allowlisted, else to the vault
+pub(crate) fn platform_fee_destination<'info>(
+    global: &Global,
+    bonding_curve: &BondingCurve,
+    platform_fee_vault: &UncheckedAccount<'info>,
+    platform_fee_recipient: &Option<UncheckedAccount<'info>>,
+) -> Result<AccountInfo<'info>> {
+    match bonding_curve.partner_fee_recipient(global) {
+        Some(recipient) => {
+            let recipient_account = platform_fee_recipient
+                .as_ref()
+                .ok_or(BondingCurveError::PlatformFeeRecipientMismatch)?;
+            require!(
+                recipient_account.key() == recipient,
+                BondingCurveError::PlatformFeeRecipientMismatch
+            );
+            Ok(recipient_account.to_account_info())
+        }
+        None => Ok(platform_fee_vault.to_account_info()),
+    }
+}
+
+// Creator fees go to the curve's split recipients, passed in slot order as remaining
+// accounts, or to the global creator wallet when the curve has no split
+pub(crate) fn creator_fee_transfers<'info>(
+    bonding_curve: &BondingCurve,
+    creator_wallet: &UncheckedAccount<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    creator_fee: u64,
+) -> Result<Vec<(AccountInfo<'info>, u64)>> {
+    if !bonding_curve.has_creator_fee_split() {
+        return Ok(vec![(creator_wallet.to_account_info(), creator_fee)]);
+    }
+
+    let splits = bonding_curve.split_creator_fee(creator_fee)?;
+    require!(
+        remaining_accounts.len() >= splits.len(),
+        BondingCurveError::CreatorFeeRecipientMismatch
+    );
+    splits
+        .into_iter()
+        .zip(remaining_accounts.iter())
+        .map(|((recipient, amount), recipient_account)| {
+            require!(
+                recipient_account.key() == recipient && recipient_account.is_writable,
+                BondingCurveError::CreatorFeeRecipientMismatch
+            );
+            Ok((recipient_account.clone(), amount))
+        })
+        .collect()
+}
+
+/// Platform fee, creator fee and fee-inclusive total of a buy priced at `sol_cost`.
+/// Slippage protection: `max_sol_cost` caps the total spend including fees.
+pub(crate) fn calculate_buy_fees(
+    sol_cost: u64,
+    platform_fee_basis_points: u16,
+    creator_fee_basis_points: u16,
+    max_sol_cost: u64,
+) -> Result<(u64, u64, u64)> {
+    let platform_fee = sol_cost
+        .checked_mul(platform_fee_basis_points as u64)
+        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+        .ok_or(BondingCurveError::Overflow)?;
+
+    let creator_fee = sol_cost
+        .checked_mul(creator_fee_basis_points as u64)
+        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+        .ok_or(BondingCurveError::Overflow)?;
+
+    let total_cost = sol_cost
+        .checked_add(platform_fee)
+        .and_then(|x| x.checked_add(creator_fee))
+        .ok_or(BondingCurveError::Overflow)?;
+
+    require!(
+        total_cost <= max_sol_cost,
+        BondingCurveError::SlippageExceeded
+    );
+
+    Ok((platform_fee, creator_fee, total_cost))
+}
+
 // 🔒 SECURE Bonding curve pricing calculation with manipulation protection
-fn calculate_buy_cost(
+// Priced on the same circulating supply current_price uses, so burns move both alike
+pub(crate) fn calculate_buy_cost(
     token_amount: u64,
     virtual_sol_reserves: u64,
-    virtual_token_reserves: u64,
     real_sol_reserves: u64,
     real_token_reserves: u64,
+    circulating_supply: u64,
 ) -> Result<u64> {
     // Anti-manipulation checks
     require!(virtual_sol_reserves > 0, BondingCurveError::InvalidPrice);
-    require!(virtual_token_reserves > 0, BondingCurveError::InvalidPrice);
+    require!(circulating_supply > 0, BondingCurveError::InvalidPrice);
     require!(token_amount > 0, BondingCurveError::InvalidTokenAmount);
     require!(token_amount <= real_token_reserves, BondingCurveError::InsufficientTokenReserves);
     // Use virtual reserves for pricing calculation
@@ -311,28 +530,31 @@ fn calculate_buy_cost(
         .checked_add(real_sol_reserves)
         .ok_or(BondingCurveError::Overflow)?;
     
-    let current_virtual_tokens = virtual_token_reserves
-        .checked_sub(real_token_reserves)
-        .ok_or(BondingCurveError::Underflow)?;
+    let current_virtual_tokens = circulating_supply;
 
     let new_virtual_tokens = current_virtual_tokens
         .checked_sub(token_amount)
         .ok_or(BondingCurveError::Underflow)?;
 
-    // k = x * y (constant product)
-    let k = current_virtual_sol
-        .checked_mul(current_virtual_tokens)
+    // k = x * y (constant product); lamports times base units exceeds u64 at launch reserves
+    let k = (current_virtual_sol as u128)
+        .checked_mul(current_virtual_tokens as u128)
         .ok_or(BondingCurveError::Overflow)?;
 
     // new_sol = k / new_tokens
-    let new_virtual_sol = k
-        .checked_div(new_virtual_tokens)
-        .ok_or(BondingCurveError::DivisionByZero)?;
+    let new_virtual_sol: u64 = k
+        .checked_div(new_virtual_tokens as u128)
+        .ok_or(BondingCurveError::DivisionByZero)?
+        .try_into()
+        .map_err(|_| BondingCurveError::Overflow)?;
 
     // cost = new_sol - current_sol
     let sol_cost = new_virtual_sol
         .checked_sub(current_virtual_sol)
         .ok_or(BondingCurveError::Underflow)?;
 
+    // Reject dust buys that round down to a free purchase
+    require!(sol_cost > 0, BondingCurveError::TradeTooSmall);
+
     Ok(sol_cost)
 }
\ No newline at end of file
@@ -0,0 +1,401 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+use crate::{constants::*, state::{Global, BondingCurve, CreatorVesting}, events::*, errors::*};
+use super::buy_tokens::{calculate_buy_cost, calculate_buy_fees, creator_fee_transfers, platform_fee_destination};
+
+#[derive(Accounts)]
+pub struct CreatorDevBuy<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.creator == creator.key() @ BondingCurveError::UnauthorizedAccess,
+        constraint = bonding_curve.buy_count == 0 @ BondingCurveError::DevBuyNotAllowed
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Token mint
+    #[account(
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// SOL vault (multi-sig protected)
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, token_mint.key().as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    /// CHECK: This is a PDA owned by the system program
+    pub sol_vault: AccountInfo<'info>,
+
+    /// Token vault (multi-sig protected)
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds = [TOKEN_VAULT_SEED, token_mint.key().as_ref()],
+        bump = bonding_curve.token_vault_bump
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// Vesting schedule for the creator's allocation (one per curve)
+    #[account(
+        init,
+        payer = creator,
+        space = CreatorVesting::LEN,
+        seeds = [CREATOR_VESTING_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub creator_vesting: Account<'info, CreatorVesting>,
+
+    /// Escrow holding the dev-buy tokens until they vest
+    #[account(
+        init,
+        payer = creator,
+        token::mint = token_mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds = [VESTING_ESCROW_SEED, token_mint.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Platform fee vault; fees accumulate here until collect_platform_fees
+    /// CHECK: System-owned PDA funded by initialize_platform_fee_vault
+    #[account(
+        mut,
+        seeds = [PLATFORM_FEE_VAULT_SEED],
+        bump
+    )]
+    pub platform_fee_vault: UncheckedAccount<'info>,
+
+    /// Partner wallet set on the curve; required while it is allowlisted
+    /// CHECK: Validated against the bonding curve and global allowlist in the handler
+    #[account(mut)]
+    pub platform_fee_recipient: Option<UncheckedAccount<'info>>,
+
+    /// Creator fee collection wallet (multi-sig controlled)
+    /// CHECK: Validated against global configuration
+    #[account(
+        mut,
+        constraint = creator_wallet.key() == global.creator_wallet
+    )]
+    pub creator_wallet: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// `remaining_accounts` carries the curve's creator fee recipients, in slot order, when it splits the creator fee
+pub fn creator_dev_buy<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreatorDevBuy<'info>>,
+    token_amount: u64,
+    max_sol_cost: u64,
+    cliff_seconds: i64,
+    duration_seconds: i64,
+) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    global.require_buys_open()?;
+    bonding_curve.require_trading_open()?;
+
+    // Validate vesting schedule
+    require!(
+        duration_seconds > 0 && duration_seconds <= MAX_VESTING_DURATION_SECONDS,
+        BondingCurveError::InvalidVestingSchedule
+    );
+    require!(
+        cliff_seconds >= 0 && cliff_seconds <= duration_seconds,
+        BondingCurveError::InvalidVestingSchedule
+    );
+
+    require!(max_sol_cost > 0, BondingCurveError::InvalidSolAmount);
+    bonding_curve.validate_trade_amounts(token_amount, true)?;
+
+    // Priced exactly like a regular buy at the opening reserves
+    let sol_cost = calculate_buy_cost(
+        token_amount,
+        bonding_curve.virtual_sol_reserves,
+        bonding_curve.real_sol_reserves,
+        bonding_curve.real_token_reserves,
+        bonding_curve.circulating_supply()?,
+    )?;
+
+    // Creators pay the base fees; volume discounts do not apply to the dev-buy.
+    // Same ceiling as a regular buy: max_sol_cost includes fees
+    let (platform_fee, creator_fee, total_cost) = calculate_buy_fees(
+        sol_cost,
+        global.platform_fee_basis_points,
+        global.creator_fee_basis_points,
+        max_sol_cost,
+    )?;
+
+    require!(
+        ctx.accounts.creator.lamports() >= total_cost,
+        BondingCurveError::InsufficientSolReserves
+    );
+
+    // Transfer SOL cost and fees from the creator; the creator fee is split between the
+    // curve's recipients when configured, exactly like a regular buy
+    let mut transfers = vec![
+        (ctx.accounts.sol_vault.to_account_info(), sol_cost),
+        (
+            platform_fee_destination(
+                global,
+                bonding_curve,
+                &ctx.accounts.platform_fee_vault,
+                &ctx.accounts.platform_fee_recipient,
+            )?,
+            platform_fee,
+        ),
+    ];
+    transfers.extend(creator_fee_transfers(
+        bonding_curve,
+        &ctx.accounts.creator_wallet,
+        ctx.remaining_accounts,
+        creator_fee,
+    )?);
+    for (destination, amount) in transfers {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: destination,
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    // Bought tokens go to the vesting escrow instead of the creator
+    let token_mint_key = bonding_curve.token_mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_tokens_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.token_vault.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.vesting_escrow.to_account_info(),
+            authority: bonding_curve.to_account_info(),
+        },
+        signer,
+    );
+    token_interface::transfer_checked(transfer_tokens_ctx, token_amount, ctx.accounts.token_mint.decimals)?;
+
+    // Update bonding curve reserves and tracking
+    bonding_curve.real_sol_reserves = bonding_curve.real_sol_reserves
+        .checked_add(sol_cost)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
+        .checked_sub(token_amount)
+        .ok_or(BondingCurveError::Underflow)?;
+
+    bonding_curve.total_volume_sol = bonding_curve.total_volume_sol
+        .checked_add(sol_cost)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.total_volume_tokens = bonding_curve.total_volume_tokens
+        .checked_add(token_amount)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.platform_fees_collected = bonding_curve.platform_fees_collected
+        .checked_add(platform_fee)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.creator_fees_collected = bonding_curve.creator_fees_collected
+        .checked_add(creator_fee)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.buy_count = bonding_curve.buy_count
+        .checked_add(1)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    bonding_curve.last_trade_at = clock.unix_timestamp;
+
+    global.total_volume_sol = global.total_volume_sol
+        .checked_add(sol_cost)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    global.total_fees_collected = global.total_fees_collected
+        .checked_add(platform_fee)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    // Record the vesting schedule
+    let creator_vesting = &mut ctx.accounts.creator_vesting;
+    creator_vesting.creator = ctx.accounts.creator.key();
+    creator_vesting.token_mint = bonding_curve.token_mint;
+    creator_vesting.total = token_amount;
+    creator_vesting.claimed = 0;
+    creator_vesting.start = clock.unix_timestamp;
+    creator_vesting.cliff = cliff_seconds;
+    creator_vesting.duration = duration_seconds;
+    creator_vesting.bump = ctx.bumps.creator_vesting;
+    creator_vesting.escrow_bump = ctx.bumps.vesting_escrow;
+
+    let new_price = bonding_curve.current_price()?;
+
+    if bonding_curve.refresh_migration_ready(clock.unix_timestamp) {
+        emit!(MigrationReadyEvent {
+            token_mint: bonding_curve.token_mint,
+            bonding_curve: bonding_curve.key(),
+            sol_reserves: bonding_curve.real_sol_reserves,
+            token_reserves: bonding_curve.real_token_reserves,
+            migration_threshold: bonding_curve.migration_threshold,
+            migration_ready: bonding_curve.migration_ready,
+            current_price: new_price,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
+    emit!(TokensPurchasedEvent {
+        token_mint: bonding_curve.token_mint,
+        buyer: ctx.accounts.creator.key(),
+        sol_cost,
+        requested_token_amount: token_amount,
+        token_amount,
+        platform_fee,
+        creator_fee,
+        new_sol_reserves: bonding_curve.real_sol_reserves,
+        new_token_reserves: bonding_curve.real_token_reserves,
+        new_price,
+        price_scale: PRICE_PRECISION_SCALE,
+        token_decimals: ctx.accounts.token_mint.decimals,
+        timestamp: clock.unix_timestamp,
+    });
+
+    emit!(CreatorVestingCreatedEvent {
+        token_mint: bonding_curve.token_mint,
+        creator: ctx.accounts.creator.key(),
+        total: token_amount,
+        start: creator_vesting.start,
+        cliff: cliff_seconds,
+        duration: duration_seconds,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔒 Creator dev-buy placed in vesting escrow");
+    msg!("Amount: {} tokens", token_amount);
+    msg!("Cost: {} SOL", sol_cost);
+    msg!("Cliff: {} seconds", cliff_seconds);
+    msg!("Duration: {} seconds", duration_seconds);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Token mint
+    #[account(
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [CREATOR_VESTING_SEED, token_mint.key().as_ref()],
+        bump = creator_vesting.bump,
+        constraint = creator_vesting.creator == creator.key()
+            @ BondingCurveError::UnauthorizedAccess
+    )]
+    pub creator_vesting: Account<'info, CreatorVesting>,
+
+    /// Escrow holding the unvested allocation
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = bonding_curve,
+        token::token_program = token_program,
+        seeds = [VESTING_ESCROW_SEED, token_mint.key().as_ref()],
+        bump = creator_vesting.escrow_bump
+    )]
+    pub vesting_escrow: InterfaceAccount<'info, TokenAccount>,
+
+    /// Creator's token account
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = token_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program
+    )]
+    pub creator_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let creator_vesting = &mut ctx.accounts.creator_vesting;
+    let clock = Clock::get()?;
+
+    let amount = creator_vesting.claimable_amount(clock.unix_timestamp)?;
+    require!(amount > 0, BondingCurveError::NothingToClaim);
+
+    let token_mint_key = bonding_curve.token_mint.key();
+    let seeds = &[
+        BONDING_CURVE_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let signer = &[&seeds[..]];
+
+    let transfer_tokens_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        TransferChecked {
+            from: ctx.accounts.vesting_escrow.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.creator_token_account.to_account_info(),
+            authority: bonding_curve.to_account_info(),
+        },
+        signer,
+    );
+    token_interface::transfer_checked(transfer_tokens_ctx, amount, ctx.accounts.token_mint.decimals)?;
+
+    creator_vesting.claimed = creator_vesting.claimed
+        .checked_add(amount)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    emit!(VestedTokensClaimedEvent {
+        token_mint: bonding_curve.token_mint,
+        creator: ctx.accounts.creator.key(),
+        amount,
+        total_claimed: creator_vesting.claimed,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔓 Vested creator tokens claimed");
+    msg!("Amount: {} tokens", amount);
+    msg!("Claimed: {} of {} tokens", creator_vesting.claimed, creator_vesting.total);
+
+    Ok(())
+}
@@ -1,13 +1,13 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{self, Mint, Token, TokenAccount, MintTo, SetAuthority},
+    token_2022::spl_token_2022::instruction::AuthorityType,
+    token_interface::{self, Mint, MintTo, SetAuthority, TokenAccount, TokenInterface},
 };
-use spl_token::instruction::AuthorityType;
-use crate::{constants::*, state::{Global, BondingCurve}, events::*, errors::*};
+use crate::{constants::*, state::{symbol_registry_seed, validate_metadata_uri, Global, BondingCurve, CreatorFeeShare, NameRegistry}, events::*, errors::*};
 
 #[derive(Accounts)]
-#[instruction(name: String, symbol: String, uri: String)]
+#[instruction(name: String, symbol: String, uri: String, lp_reserve_percentage: Option<u8>, decimals: u8)]
 pub struct InitializeBondingCurve<'info> {
     #[account(
         constraint = !global.is_paused
@@ -26,11 +26,12 @@ pub struct InitializeBondingCurve<'info> {
     #[account(
         init,
         payer = creator,
-        mint::decimals = 9,
+        mint::decimals = decimals,
         mint::authority = creator,
         mint::freeze_authority = creator,
+        mint::token_program = token_program,
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// SOL vault for bonding curve reserves (multi-sig protected)
     /// CHECK: This is a PDA owned by the system program
@@ -49,10 +50,11 @@ pub struct InitializeBondingCurve<'info> {
         payer = creator,
         token::mint = token_mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds = [TOKEN_VAULT_SEED, token_mint.key().as_ref()],
         bump
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// LP reserve token account (multi-sig protected)
     #[account(
@@ -60,10 +62,21 @@ pub struct InitializeBondingCurve<'info> {
         payer = creator,
         token::mint = token_mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds = [LP_RESERVE_SEED, token_mint.key().as_ref()],
         bump
     )]
-    pub lp_reserve_token_account: Account<'info, TokenAccount>,
+    pub lp_reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Symbol claim; required while global.unique_symbols_enabled is set
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = NameRegistry::LEN,
+        seeds = [NAME_REGISTRY_SEED, symbol_registry_seed(&symbol).as_ref()],
+        bump
+    )]
+    pub name_registry: Option<Account<'info, NameRegistry>>,
 
     // Metadata removed for SolPG compatibility
 
@@ -83,17 +96,24 @@ pub struct InitializeBondingCurve<'info> {
     pub multisig_authority: Signer<'info>,
 
     // Metadata program removed for SolPG compatibility
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022 program that owns the mint
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
 
+#[allow(clippy::too_many_arguments)] // Mirrors the instruction arguments
 pub fn initialize_bonding_curve(
     ctx: Context<InitializeBondingCurve>,
     name: String,
     symbol: String,
     uri: String,
+    lp_reserve_percentage: Option<u8>,
+    decimals: u8,
+    max_migration_fee_lamports: Option<u64>,
+    platform_fee_recipient: Option<Pubkey>,
+    creator_fee_shares: Option<[CreatorFeeShare; MAX_CREATOR_FEE_RECIPIENTS]>,
 ) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let bonding_curve = &mut ctx.accounts.bonding_curve;
@@ -103,9 +123,20 @@ pub fn initialize_bonding_curve(
     global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
 
     // Validate input parameters
-    require!(name.len() > 0 && name.len() <= 32, BondingCurveError::InvalidTokenName);
-    require!(symbol.len() > 0 && symbol.len() <= 10, BondingCurveError::InvalidTokenSymbol);
-    require!(uri.len() > 0 && uri.len() <= 200, BondingCurveError::InvalidMetadataUri);
+    require!(!name.is_empty() && name.len() <= 32, BondingCurveError::InvalidTokenName);
+    require!(!symbol.is_empty() && symbol.len() <= 10, BondingCurveError::InvalidTokenSymbol);
+    validate_metadata_uri(&uri)?;
+    require!(decimals <= TOKEN_DECIMALS, BondingCurveError::InvalidTokenDecimals);
+    if let Some(recipient) = platform_fee_recipient {
+        require!(
+            global.is_partner_fee_wallet(&recipient),
+            BondingCurveError::InvalidPlatformFeeRecipient
+        );
+    }
+
+    // Enabled creator fee shares go to distinct wallets and cover the whole fee
+    let creator_fee_shares = creator_fee_shares.unwrap_or_default();
+    CreatorFeeShare::validate_split(&creator_fee_shares)?;
 
     // Get bump seeds
     let bonding_curve_bump = ctx.bumps.bonding_curve;
@@ -113,10 +144,23 @@ pub fn initialize_bonding_curve(
     let token_vault_bump = ctx.bumps.token_vault;
     let lp_reserve_bump = ctx.bumps.lp_reserve_token_account;
 
-    // Calculate supplies
-    let total_supply = TOTAL_SUPPLY;
+    // Creator-chosen LP reserve must stay within the admin-set range
+    let lp_reserve_percentage = match lp_reserve_percentage {
+        Some(percentage) => {
+            require!(
+                percentage >= global.min_lp_reserve_percentage
+                    && percentage <= global.max_lp_reserve_percentage,
+                BondingCurveError::InvalidLpReservePercentage
+            );
+            percentage
+        }
+        None => LP_RESERVE_PERCENTAGE as u8,
+    };
+
+    // Calculate supplies; the constants assume TOKEN_DECIMALS, so rescale to the mint's decimals
+    let total_supply = rescale_to_decimals(TOTAL_SUPPLY, decimals)?;
     let lp_reserve_supply = total_supply
-        .checked_mul(LP_RESERVE_PERCENTAGE)
+        .checked_mul(lp_reserve_percentage as u64)
         .and_then(|x| x.checked_div(100))
         .ok_or(BondingCurveError::Overflow)?;
     let bonding_curve_supply = total_supply
@@ -128,14 +172,19 @@ pub fn initialize_bonding_curve(
     bonding_curve.creator = ctx.accounts.creator.key();
     bonding_curve.name = name.clone();
     bonding_curve.symbol = symbol.clone();
+    bonding_curve.uri = uri.clone();
     bonding_curve.virtual_sol_reserves = VIRTUAL_SOL_RESERVES;
-    bonding_curve.virtual_token_reserves = VIRTUAL_TOKEN_RESERVES;
+    bonding_curve.virtual_token_reserves = rescale_to_decimals(VIRTUAL_TOKEN_RESERVES, decimals)?;
     bonding_curve.real_sol_reserves = 0;
     bonding_curve.real_token_reserves = bonding_curve_supply;
     bonding_curve.lp_reserve_supply = lp_reserve_supply;
     bonding_curve.migration_threshold = MIGRATION_THRESHOLD;
     bonding_curve.migration_ready = false;
     bonding_curve.is_migrated = false;
+    bonding_curve.migration_in_progress = false;
+    bonding_curve.pending_withdrawal_sol = 0;
+    bonding_curve.tokens_burned = 0;
+    bonding_curve.creator_paused = false;
     bonding_curve.amm_program_id = None;
     bonding_curve.amm_pool_address = None;
     bonding_curve.total_volume_sol = 0;
@@ -150,6 +199,27 @@ pub fn initialize_bonding_curve(
     bonding_curve.sol_vault_bump = sol_vault_bump;
     bonding_curve.token_vault_bump = token_vault_bump;
     bonding_curve.lp_reserve_bump = lp_reserve_bump;
+    bonding_curve.token_decimals = decimals;
+    bonding_curve.max_migration_fee_lamports = max_migration_fee_lamports;
+    bonding_curve.price_checkpoint_at = 0;
+    bonding_curve.platform_fee_recipient = platform_fee_recipient;
+    bonding_curve.migrated_at = 0;
+    bonding_curve.amm_pool_confirmed = false;
+    bonding_curve.creator_fee_shares = creator_fee_shares;
+    bonding_curve.migration_fee_collected = 0;
+
+    // Claim the symbol platform-wide when uniqueness is enforced
+    if global.unique_symbols_enabled {
+        let name_registry = ctx.accounts.name_registry
+            .as_mut()
+            .ok_or(BondingCurveError::NameRegistryRequired)?;
+        name_registry.claim(
+            ctx.accounts.token_mint.key(),
+            ctx.accounts.creator.key(),
+            &symbol,
+            clock.unix_timestamp,
+        )?;
+    }
 
     // Mint tokens to vaults using bonding curve authority
     let token_mint_key = ctx.accounts.token_mint.key();
@@ -170,7 +240,7 @@ pub fn initialize_bonding_curve(
         },
         signer,
     );
-    token::mint_to(mint_to_vault_ctx, bonding_curve_supply)?;
+    token_interface::mint_to(mint_to_vault_ctx, bonding_curve_supply)?;
 
     // Mint LP reserve supply to LP reserve account (multi-sig protected)
     let mint_lp_ctx = CpiContext::new_with_signer(
@@ -182,7 +252,7 @@ pub fn initialize_bonding_curve(
         },
         signer,
     );
-    token::mint_to(mint_lp_ctx, lp_reserve_supply)?;
+    token_interface::mint_to(mint_lp_ctx, lp_reserve_supply)?;
 
     // 🔥 REVOKE MINT AND FREEZE AUTHORITIES FOR PERMANENT DECENTRALIZATION
     msg!("🔥 Revoking mint authority - making supply permanent...");
@@ -195,7 +265,7 @@ pub fn initialize_bonding_curve(
             current_authority: ctx.accounts.creator.to_account_info(),
         },
     );
-    token::set_authority(revoke_mint_ctx, AuthorityType::MintTokens, None)?;
+    token_interface::set_authority(revoke_mint_ctx, AuthorityType::MintTokens, None)?;
 
     // Revoke freeze authority (no accounts can ever be frozen)
     msg!("🔥 Revoking freeze authority - making accounts unfreezable...");
@@ -206,7 +276,19 @@ pub fn initialize_bonding_curve(
             current_authority: ctx.accounts.creator.to_account_info(),
         },
     );
-    token::set_authority(revoke_freeze_ctx, AuthorityType::FreezeAccount, None)?;
+    token_interface::set_authority(revoke_freeze_ctx, AuthorityType::FreezeAccount, None)?;
+
+    // Post-condition: the full supply exists and nobody can mint or freeze ever again
+    ctx.accounts.token_mint.reload()?;
+    require!(
+        ctx.accounts.token_mint.supply == total_supply,
+        BondingCurveError::MintSupplyMismatch
+    );
+    require!(
+        ctx.accounts.token_mint.mint_authority.is_none()
+            && ctx.accounts.token_mint.freeze_authority.is_none(),
+        BondingCurveError::MintAuthorityNotRevoked
+    );
 
     // Token metadata creation removed for SolPG compatibility
     // Name and symbol will be stored in bonding curve state instead
@@ -231,6 +313,8 @@ pub fn initialize_bonding_curve(
         uri: uri.clone(),
         total_supply,
         lp_reserve_supply,
+        lp_reserve_percentage,
+        token_decimals: decimals,
         virtual_sol_reserves: bonding_curve.virtual_sol_reserves,
         virtual_token_reserves: bonding_curve.virtual_token_reserves,
         migration_threshold: bonding_curve.migration_threshold,
@@ -250,9 +334,30 @@ pub fn initialize_bonding_curve(
     msg!("Token Mint: {}", bonding_curve.token_mint);
     msg!("Creator: {}", bonding_curve.creator);
     msg!("Total Supply: {} tokens", total_supply);
-    msg!("LP Reserve: {} tokens ({}%)", lp_reserve_supply, LP_RESERVE_PERCENTAGE);
+    msg!("Decimals: {}", decimals);
+    msg!("LP Reserve: {} tokens ({}%)", lp_reserve_supply, lp_reserve_percentage);
     msg!("Bonding Curve Supply: {} tokens", bonding_curve_supply);
     msg!("Migration Threshold: {} SOL", MIGRATION_THRESHOLD / 1_000_000_000);
+    if let Some(max_fee) = max_migration_fee_lamports {
+        msg!("Max Migration Fee: {} lamports", max_fee);
+    }
+    if let Some(recipient) = platform_fee_recipient {
+        msg!("Platform Fee Recipient: {}", recipient);
+    }
+    for share in creator_fee_shares.iter().filter(|share| share.is_enabled()) {
+        msg!("Creator Fee Share: {} bps to {}", share.basis_points, share.recipient);
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// `amount` in TOKEN_DECIMALS base units, expressed in base units of a mint with `decimals`
+pub(crate) fn rescale_to_decimals(amount: u64, decimals: u8) -> Result<u64> {
+    let shift = TOKEN_DECIMALS
+        .checked_sub(decimals)
+        .ok_or(BondingCurveError::InvalidTokenDecimals)?;
+    let divisor = 10u64
+        .checked_pow(shift as u32)
+        .ok_or(BondingCurveError::Overflow)?;
+    Ok(amount / divisor)
+}
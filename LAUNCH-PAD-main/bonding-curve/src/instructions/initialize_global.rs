@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, state::Global, events::*};
+use crate::{constants::*, state::{Global, VolumeFeeTier}, events::*};
 
 #[derive(Accounts)]
 pub struct InitializeGlobal<'info> {
@@ -52,23 +52,46 @@ pub fn initialize_global(ctx: Context<InitializeGlobal>) -> Result<()> {
     // Set fee collection wallets
     global.platform_wallet = ctx.accounts.platform_wallet.key();
     global.creator_wallet = ctx.accounts.creator_wallet.key();
+    global.fund_wallet = ctx.accounts.platform_wallet.key();
 
     // Initialize fee settings
     global.platform_fee_basis_points = PLATFORM_FEE_BASIS_POINTS;
     global.creator_fee_basis_points = CREATOR_FEE_BASIS_POINTS;
     global.migration_fee_basis_points = MIGRATION_FEE_BASIS_POINTS;
     global.max_slippage_basis_points = MAX_SLIPPAGE_BASIS_POINTS;
+    global.fund_share_basis_points = FUND_SHARE_BASIS_POINTS;
+
+    // No migration targets until the multi-sig allowlists an AMM program
+    global.allowed_amm_programs = [Pubkey::default(); ALLOWED_AMM_PROGRAM_COUNT];
+    global.partner_fee_wallets = [Pubkey::default(); PARTNER_FEE_WALLET_COUNT];
+    global.sell_burn_basis_points = SELL_BURN_BASIS_POINTS;
+
+    // Large sell escrow starts disabled
+    global.large_sell_threshold_sol = LARGE_SELL_THRESHOLD_SOL;
+    global.withdrawal_delay_seconds = WITHDRAWAL_DELAY_SECONDS;
+
+    // Duplicate symbols are allowed until the multi-sig opts in to uniqueness
+    global.unique_symbols_enabled = false;
+
+    // Volume discounts start disabled so every user pays the base platform fee
+    global.volume_fee_tiers = [VolumeFeeTier::default(); VOLUME_FEE_TIER_COUNT];
+
+    // Launches may choose any LP reserve percentage within the hard bounds
+    global.min_lp_reserve_percentage = MIN_LP_RESERVE_PERCENTAGE;
+    global.max_lp_reserve_percentage = MAX_LP_RESERVE_PERCENTAGE;
 
     // Initialize flags
     global.migration_enabled = true;
     global.is_paused = false;
+    global.buys_paused = false;
+    global.sells_paused = false;
 
     // Initialize counters
     global.total_volume_sol = 0;
     global.total_fees_collected = 0;
     global.tokens_created = 0;
     global.successful_migrations = 0;
-    global.version = 1;
+    global.version = GLOBAL_VERSION;
 
     // Emit initialization event
     emit!(GlobalInitializedEvent {
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Mint, TokenAccount, Token};
-use crate::{constants::*, state::{Global, BondingCurve}, events::*, errors::*};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use crate::{constants::*, state::{Global, BondingCurve, MigrationPreview}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct MigrateToAmm<'info> {
     #[account(
+        mut,
         constraint = global.migration_enabled,
         constraint = !global.is_paused
     )]
@@ -13,15 +14,18 @@ pub struct MigrateToAmm<'info> {
     #[account(
         mut,
         constraint = bonding_curve.is_migration_threshold_met(),
+        constraint = bonding_curve.migration_ready @ BondingCurveError::MigrationThresholdNotMet,
+        constraint = !bonding_curve.migration_in_progress @ BondingCurveError::MigrationInProgress,
         constraint = !bonding_curve.is_migrated
     )]
     pub bonding_curve: Account<'info, BondingCurve>,
 
     /// Token mint
     #[account(
-        constraint = token_mint.key() == bonding_curve.token_mint
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// SOL vault (multi-sig protected)
     #[account(
@@ -37,10 +41,11 @@ pub struct MigrateToAmm<'info> {
         mut,
         token::mint = token_mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds = [LP_RESERVE_SEED, token_mint.key().as_ref()],
         bump = bonding_curve.lp_reserve_bump
     )]
-    pub lp_reserve_token_account: Account<'info, TokenAccount>,
+    pub lp_reserve_token_account: InterfaceAccount<'info, TokenAccount>,
 
     /// Platform fee collection wallet (multi-sig controlled)
     /// CHECK: Validated against global configuration
@@ -50,6 +55,14 @@ pub struct MigrateToAmm<'info> {
     )]
     pub platform_wallet: UncheckedAccount<'info>,
 
+    /// Fund wallet receiving its share of the migration fee
+    /// CHECK: Validated against global configuration
+    #[account(
+        mut,
+        constraint = fund_wallet.key() == global.fund_wallet
+    )]
+    pub fund_wallet: UncheckedAccount<'info>,
+
     /// Admin authority (required for multi-sig)
     #[account(
         constraint = admin_authority.key() == global.admin_authority
@@ -63,7 +76,11 @@ pub struct MigrateToAmm<'info> {
     pub multisig_authority: Signer<'info>,
 
     /// AMM program to migrate to
-    /// CHECK: Will be validated during CPI call
+    /// CHECK: Must be on the multi-sig managed allowlist
+    #[account(
+        constraint = global.is_allowed_amm_program(&amm_program.key())
+            @ BondingCurveError::InvalidAmmProgram
+    )]
     pub amm_program: UncheckedAccount<'info>,
 
     /// New AMM pool account (will be created)
@@ -80,11 +97,17 @@ pub struct MigrateToAmm<'info> {
     #[account(mut)]
     pub amm_token_vault: UncheckedAccount<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
+pub fn migrate_to_amm(
+    ctx: Context<MigrateToAmm>,
+    min_sqrt_price_x64: u128,
+    max_sqrt_price_x64: u128,
+    min_price_hold_seconds: Option<i64>,
+) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let clock = Clock::get()?;
@@ -92,22 +115,130 @@ pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
     // Verify multi-sig authorization for critical migration operation
     global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
 
-    // Calculate migration fee
-    let migration_fee = bonding_curve.real_sol_reserves
-        .checked_mul(global.migration_fee_basis_points as u64)
-        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
-        .ok_or(BondingCurveError::Overflow)?;
+    // Guard the pool opening price against reserves skewed right before migration
+    let migration_sqrt_price_x64 = bonding_curve.migration_sqrt_price_x64()?;
+    require!(
+        migration_sqrt_price_x64 >= min_sqrt_price_x64
+            && migration_sqrt_price_x64 <= max_sqrt_price_x64,
+        BondingCurveError::MigrationPriceOutOfRange
+    );
+
+    // Reject a flash pump that only just crossed the threshold
+    if let Some(min_seconds) = min_price_hold_seconds {
+        require!(
+            bonding_curve.is_price_sustained(clock.unix_timestamp, min_seconds),
+            BondingCurveError::MigrationPriceNotSustained
+        );
+    }
 
-    let sol_to_transfer = bonding_curve.real_sol_reserves
-        .checked_sub(migration_fee)
-        .ok_or(BondingCurveError::Underflow)?;
+    // Lock the curve against trades until confirm_amm_pool or abort_migration settles the migration
+    bonding_curve.migration_in_progress = true;
+
+    // Fee split, transfer amounts and the opening pool state, shared with get_migration_preview
+    let preview = bonding_curve.migration_preview(global, ctx.accounts.lp_reserve_token_account.amount)?;
+    let MigrationPreview {
+        sol_to_transfer,
+        tokens_to_transfer: lp_tokens_to_transfer,
+        uncapped_migration_fee,
+        migration_fee,
+        platform_migration_fee,
+        fund_migration_fee,
+        migration_overshoot,
+        initial_sqrt_price_x64,
+        expected_initial_liquidity,
+        ..
+    } = preview;
+
+    // 🚀 ACTUAL ASSET TRANSFER TO AMM: every external transfer and the pool CPI run
+    // before any migration state is committed, so a failure leaves the curve untouched
+
+    // Get bonding curve authority for signed transfers
+    let token_mint_key = bonding_curve.token_mint.key();
+    let bonding_curve_seeds = &[
+        BONDING_CURVE_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.bump],
+    ];
+    let bonding_curve_signer = &[&bonding_curve_seeds[..]];
+    
+    // Get SOL vault authority
+    let sol_vault_seeds = &[
+        SOL_VAULT_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let sol_vault_signer = &[&sol_vault_seeds[..]];
+
+    // Collect migration fee to platform and fund wallets; the vault is system-owned, so
+    // lamports can only leave it through a signed system transfer
+    for (destination, amount) in [
+        (ctx.accounts.platform_wallet.to_account_info(), platform_migration_fee),
+        (ctx.accounts.fund_wallet.to_account_info(), fund_migration_fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.sol_vault.to_account_info(),
+                    to: destination,
+                },
+                sol_vault_signer,
+            ),
+            amount,
+        )?;
+    }
+
+    // Transfer remaining SOL from bonding curve vault to AMM SOL vault
+    if sol_to_transfer > 0 {
+        let transfer_sol_to_amm = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            to: ctx.accounts.amm_sol_vault.to_account_info(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_sol_to_amm,
+                sol_vault_signer,
+            ),
+            sol_to_transfer,
+        )?;
+        
+        msg!("✅ Transferred {} SOL to AMM vault", sol_to_transfer);
+    }
+
+    // Transfer LP reserve tokens to AMM token vault
+    if lp_tokens_to_transfer > 0 {
+        let transfer_tokens_to_amm = TransferChecked {
+            from: ctx.accounts.lp_reserve_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
+            to: ctx.accounts.amm_token_vault.to_account_info(),
+            authority: bonding_curve.to_account_info(),
+        };
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                transfer_tokens_to_amm,
+                bonding_curve_signer,
+            ),
+            lp_tokens_to_transfer,
+            ctx.accounts.token_mint.decimals,
+        )?;
+        
+        msg!("✅ Transferred {} LP tokens to AMM vault", lp_tokens_to_transfer);
+    }
 
-    // Get LP reserve token amount
-    let lp_tokens_to_transfer = ctx.accounts.lp_reserve_token_account.amount;
+    // NOTE: The AMM pool creation CPI would happen here in production
+    // This requires the specific AMM program interface to be integrated
+    msg!("🏗️  AMM pool creation CPI integration point");
+    msg!("🔗 Ready for AMM program integration at: {}", ctx.accounts.amm_program.key());
+    // Once the CPI lands, the AMM emits its PoolMigrationEvent with
+    // initial_liquidity = expected_initial_liquidity for the created pool, and
+    // create_pool records bonding_curve.creator as the pool's token_creator
 
-    // Collect migration fee to platform wallet
-    **ctx.accounts.sol_vault.to_account_info().try_borrow_mut_lamports()? -= migration_fee;
-    **ctx.accounts.platform_wallet.to_account_info().try_borrow_mut_lamports()? += migration_fee;
+    // All transfers succeeded - commit migration state
 
     // Store AMM information
     bonding_curve.amm_program_id = Some(ctx.accounts.amm_program.key());
@@ -115,6 +246,9 @@ pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
 
     // Mark as migrated (this prevents further trading on bonding curve)
     bonding_curve.is_migrated = true;
+    bonding_curve.migrated_at = clock.unix_timestamp;
+    bonding_curve.amm_pool_confirmed = false;
+    bonding_curve.migration_fee_collected = migration_fee;
 
     // Update global migration counter
     global.successful_migrations = global.successful_migrations
@@ -130,12 +264,21 @@ pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
     emit!(MigrationCompletedEvent {
         token_mint: bonding_curve.token_mint,
         bonding_curve: bonding_curve.key(),
+        creator: bonding_curve.creator,
         amm_program_id: ctx.accounts.amm_program.key(),
         amm_pool_address: ctx.accounts.amm_pool.key(),
         sol_transferred: sol_to_transfer,
         tokens_transferred: lp_tokens_to_transfer,
         lp_tokens_minted: lp_tokens_to_transfer, // LP tokens become AMM LP tokens
         migration_fee,
+        migration_overshoot,
+        platform_wallet: ctx.accounts.platform_wallet.key(),
+        platform_migration_fee,
+        fund_wallet: ctx.accounts.fund_wallet.key(),
+        fund_migration_fee,
+        migration_sqrt_price_x64,
+        initial_sqrt_price_x64,
+        expected_initial_liquidity,
         timestamp: clock.unix_timestamp,
     });
 
@@ -163,67 +306,225 @@ pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
     msg!("SOL Transferred: {} SOL", sol_to_transfer);
     msg!("LP Tokens: {} tokens", lp_tokens_to_transfer);
     msg!("Migration Fee: {} SOL", migration_fee);
+    if migration_fee < uncapped_migration_fee {
+        msg!("Migration Fee Capped From: {} SOL", uncapped_migration_fee);
+    }
+    msg!("Threshold Overshoot: {} SOL", migration_overshoot);
+    msg!("Platform Share: {} SOL", platform_migration_fee);
+    msg!("Fund Share: {} SOL", fund_migration_fee);
+    msg!("Migration Sqrt Price X64: {}", migration_sqrt_price_x64);
+    msg!("Initial Sqrt Price X64: {}", initial_sqrt_price_x64);
+    msg!("Expected Initial Liquidity: {}", expected_initial_liquidity);
 
-    // 🚀 ACTUAL ASSET TRANSFER TO AMM: Transfer SOL and tokens to AMM vaults
-    
-    // Get bonding curve authority for signed transfers
-    let token_mint_key = bonding_curve.token_mint.key();
-    let bonding_curve_seeds = &[
-        BONDING_CURVE_SEED,
-        token_mint_key.as_ref(),
-        &[bonding_curve.bump],
-    ];
-    let bonding_curve_signer = &[&bonding_curve_seeds[..]];
-    
-    // Get SOL vault authority
-    let sol_vault_seeds = &[
-        SOL_VAULT_SEED,
-        token_mint_key.as_ref(),
-        &[bonding_curve.sol_vault_bump],
-    ];
-    let sol_vault_signer = &[&sol_vault_seeds[..]];
+    Ok(())
+}
 
-    // Transfer remaining SOL from bonding curve vault to AMM SOL vault
-    if sol_to_transfer > 0 {
-        let transfer_sol_to_amm = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.sol_vault.to_account_info(),
-            to: ctx.accounts.amm_sol_vault.to_account_info(),
-        };
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_sol_to_amm,
-                sol_vault_signer,
-            ),
-            sol_to_transfer,
-        )?;
-        
-        msg!("✅ Transferred {} SOL to AMM vault", sol_to_transfer);
-    }
+#[derive(Accounts)]
+pub struct ConfirmAmmPool<'info> {
+    pub global: Account<'info, Global>,
 
-    // Transfer LP reserve tokens to AMM token vault
-    if lp_tokens_to_transfer > 0 {
-        let transfer_tokens_to_amm = anchor_spl::token::Transfer {
-            from: ctx.accounts.lp_reserve_token_account.to_account_info(),
-            to: ctx.accounts.amm_token_vault.to_account_info(),
-            authority: bonding_curve.to_account_info(),
-        };
-        anchor_spl::token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                transfer_tokens_to_amm,
-                bonding_curve_signer,
-            ),
-            lp_tokens_to_transfer,
-        )?;
-        
-        msg!("✅ Transferred {} LP tokens to AMM vault", lp_tokens_to_transfer);
-    }
+    #[account(
+        mut,
+        constraint = bonding_curve.is_migrated @ BondingCurveError::NotMigrated,
+        constraint = !bonding_curve.amm_pool_confirmed @ BondingCurveError::AmmPoolAlreadyConfirmed
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
 
-    // NOTE: The AMM pool creation CPI would happen here in production
-    // This requires the specific AMM program interface to be integrated
-    msg!("🏗️  AMM pool creation CPI integration point");
-    msg!("🔗 Ready for AMM program integration at: {}", ctx.accounts.amm_program.key());
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for critical operations)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+/// Mark the migrated curve's AMM pool as live; after this abort_migration is refused
+pub fn confirm_amm_pool(ctx: Context<ConfirmAmmPool>) -> Result<()> {
+    let global = &ctx.accounts.global;
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for critical migration operation
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    // The migration is settled; the curve stays closed through is_migrated
+    bonding_curve.amm_pool_confirmed = true;
+    bonding_curve.migration_in_progress = false;
+    let amm_pool_address = bonding_curve.amm_pool_address.unwrap_or_default();
+
+    emit!(AmmPoolConfirmedEvent {
+        token_mint: bonding_curve.token_mint,
+        bonding_curve: bonding_curve.key(),
+        amm_pool_address,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "AMM_POOL_CONFIRMED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: bonding_curve.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ AMM pool confirmed for: {}", bonding_curve.token_mint);
+    msg!("AMM Pool: {}", amm_pool_address);
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct AbortMigration<'info> {
+    #[account(mut)]
+    pub global: Account<'info, Global>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.is_migrated @ BondingCurveError::NotMigrated,
+        constraint = !bonding_curve.amm_pool_confirmed @ BondingCurveError::AmmPoolAlreadyConfirmed
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// SOL vault; must hold the curve's reserves again before the abort
+    #[account(
+        seeds = [SOL_VAULT_SEED, bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    /// CHECK: This is a PDA owned by the system program
+    pub sol_vault: AccountInfo<'info>,
+
+    /// LP reserve token account; must hold the LP reserve supply again before the abort
+    #[account(
+        seeds = [LP_RESERVE_SEED, bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.lp_reserve_bump
+    )]
+    pub lp_reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Admin authority (required for multi-sig)
+    #[account(
+        constraint = admin_authority.key() == global.admin_authority
+    )]
+    pub admin_authority: Signer<'info>,
+
+    /// Multi-sig authority (required for critical operations)
+    #[account(
+        constraint = multisig_authority.key() == global.multisig_authority
+    )]
+    pub multisig_authority: Signer<'info>,
+}
+
+/// Roll back a migration whose AMM pool was never created. Only possible within
+/// MIGRATION_ABORT_WINDOW_SECONDS and before confirm_amm_pool; the migrated SOL,
+/// migration fee and LP tokens must already be back in the curve's vaults (e.g.
+/// transferred earlier in the same transaction) so trading resumes fully backed.
+/// real_sol_reserves still includes the fee, so the vault check below enforces its
+/// return, and the fee is taken back out of global.total_fees_collected.
+pub fn abort_migration(ctx: Context<AbortMigration>) -> Result<()> {
+    let global = &mut ctx.accounts.global;
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    // Verify multi-sig authorization for critical migration operation
+    global.verify_multisig_auth(&ctx.accounts.admin_authority, &ctx.accounts.multisig_authority)?;
+
+    require!(
+        clock.unix_timestamp.saturating_sub(bonding_curve.migrated_at) <= MIGRATION_ABORT_WINDOW_SECONDS,
+        BondingCurveError::MigrationAbortWindowExpired
+    );
+
+    // Reserves must be backed again before the curve reopens, mirroring the sell-side solvency check
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let backed_lamports = ctx.accounts.sol_vault.lamports()
+        .saturating_sub(rent_exempt_minimum)
+        .saturating_sub(bonding_curve.pending_withdrawal_sol);
+    require!(
+        backed_lamports >= bonding_curve.real_sol_reserves
+            && ctx.accounts.lp_reserve_token_account.amount >= bonding_curve.lp_reserve_supply,
+        BondingCurveError::MigrationAssetsNotRestored
+    );
+
+    let amm_pool_address = bonding_curve.amm_pool_address.unwrap_or_default();
+
+    // Reopen the curve; migration_ready still reflects the reserves, so it can migrate again
+    bonding_curve.is_migrated = false;
+    bonding_curve.migration_in_progress = false;
+    bonding_curve.amm_program_id = None;
+    bonding_curve.amm_pool_address = None;
+    bonding_curve.migrated_at = 0;
+
+    global.successful_migrations = global.successful_migrations
+        .checked_sub(1)
+        .ok_or(BondingCurveError::Underflow)?;
+
+    // The returned migration fee is no longer a collected fee
+    global.total_fees_collected = global.total_fees_collected
+        .checked_sub(bonding_curve.migration_fee_collected)
+        .ok_or(BondingCurveError::Underflow)?;
+    bonding_curve.migration_fee_collected = 0;
+
+    emit!(MigrationAbortedEvent {
+        token_mint: bonding_curve.token_mint,
+        bonding_curve: bonding_curve.key(),
+        amm_pool_address,
+        real_sol_reserves: bonding_curve.real_sol_reserves,
+        lp_reserve_tokens: ctx.accounts.lp_reserve_token_account.amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Multi-sig operation log
+    emit!(MultisigOperationEvent {
+        operation: "MIGRATION_ABORTED".to_string(),
+        admin_signer: ctx.accounts.admin_authority.key(),
+        multisig_signer: ctx.accounts.multisig_authority.key(),
+        target_account: bonding_curve.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    // Security alert for critical operation
+    emit!(SecurityAlertEvent {
+        alert_type: "MIGRATION_ABORTED".to_string(),
+        details: "Migration rolled back and bonding curve trading re-enabled".to_string(),
+        authority: ctx.accounts.admin_authority.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("⏪ Migration aborted for: {}", bonding_curve.token_mint);
+    msg!("Abandoned AMM Pool: {}", amm_pool_address);
+    msg!("SOL Reserves: {} SOL", bonding_curve.real_sol_reserves);
+    msg!("LP Reserve: {} tokens", ctx.accounts.lp_reserve_token_account.amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetMigrationPreview<'info> {
+    pub global: Account<'info, Global>,
+
+    #[account(
+        constraint = !bonding_curve.is_migrated
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// LP reserve token account whose balance seeds the AMM pool
+    #[account(
+        seeds = [LP_RESERVE_SEED, bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.lp_reserve_bump
+    )]
+    pub lp_reserve_token_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Preview what migrate_to_amm would transfer and seed the pool with, returned via
+/// return data. Read-only; uses the same computation as the migration itself.
+pub fn get_migration_preview(ctx: Context<GetMigrationPreview>) -> Result<MigrationPreview> {
+    let preview = ctx.accounts.bonding_curve.migration_preview(
+        &ctx.accounts.global,
+        ctx.accounts.lp_reserve_token_account.amount,
+    )?;
+    Ok(preview)
+}
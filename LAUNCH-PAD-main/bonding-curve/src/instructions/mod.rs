@@ -5,6 +5,7 @@ pub mod sell_tokens;
 pub mod migrate_to_amm;
 pub mod admin_operations;
 pub mod user_operations;
+pub mod creator_vesting;
 
 pub use initialize_global::*;
 pub use initialize_bonding_curve::*;
@@ -12,4 +13,5 @@ pub use buy_tokens::*;
 pub use sell_tokens::*;
 pub use migrate_to_amm::*;
 pub use admin_operations::*;
-pub use user_operations::*;
\ No newline at end of file
+pub use user_operations::*;
+pub use creator_vesting::*;
\ No newline at end of file
@@ -1,27 +1,24 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    token::{self, Mint, Token, TokenAccount, Transfer},
+    token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
 };
-use crate::{constants::*, state::{Global, BondingCurve, UserVolumeAccumulator}, events::*, errors::*};
+use crate::{constants::*, state::{require_not_expired, Global, BondingCurve, PendingWithdrawal, UserVolumeAccumulator}, events::*, errors::*};
+use super::buy_tokens::{creator_fee_transfers, platform_fee_destination};
 
 #[derive(Accounts)]
 pub struct SellTokens<'info> {
-    #[account(
-        constraint = !global.is_paused
-    )]
     pub global: Account<'info, Global>,
 
-    #[account(
-        mut,
-        constraint = !bonding_curve.is_migrated
-    )]
+    #[account(mut)]
     pub bonding_curve: Account<'info, BondingCurve>,
 
-    /// Token mint
+    /// Token mint (mutable for burn-on-sell supply reduction)
     #[account(
-        constraint = token_mint.key() == bonding_curve.token_mint
+        mut,
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
     )]
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 
     /// SOL vault (multi-sig protected)
     #[account(
@@ -37,34 +34,55 @@ pub struct SellTokens<'info> {
         mut,
         token::mint = token_mint,
         token::authority = bonding_curve,
+        token::token_program = token_program,
         seeds = [TOKEN_VAULT_SEED, token_mint.key().as_ref()],
         bump = bonding_curve.token_vault_bump
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
 
     /// User's token account
     #[account(
         mut,
         token::mint = token_mint,
-        token::authority = seller
+        token::authority = seller,
+        token::token_program = token_program
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
 
-    /// User volume accumulator
+    /// User volume accumulator (created on first trade, paid by the seller)
     #[account(
-        mut,
+        init_if_needed,
+        payer = seller,
+        space = UserVolumeAccumulator::LEN,
         seeds = [USER_VOLUME_SEED, seller.key().as_ref()],
-        bump = user_volume_accumulator.bump
+        bump
     )]
     pub user_volume_accumulator: Account<'info, UserVolumeAccumulator>,
 
-    /// Platform fee collection wallet (multi-sig controlled)
-    /// CHECK: Validated against global configuration
+    /// Escrow for sells above the large sell threshold; required only when this sell is escrowed
+    /// (created on first escrowed sell, paid by the seller)
+    #[account(
+        init_if_needed,
+        payer = seller,
+        space = PendingWithdrawal::LEN,
+        seeds = [PENDING_WITHDRAWAL_SEED, token_mint.key().as_ref(), seller.key().as_ref()],
+        bump
+    )]
+    pub pending_withdrawal: Option<Account<'info, PendingWithdrawal>>,
+
+    /// Platform fee vault; fees accumulate here until collect_platform_fees
+    /// CHECK: System-owned PDA funded by initialize_platform_fee_vault
     #[account(
         mut,
-        constraint = platform_wallet.key() == global.platform_wallet
+        seeds = [PLATFORM_FEE_VAULT_SEED],
+        bump
     )]
-    pub platform_wallet: UncheckedAccount<'info>,
+    pub platform_fee_vault: UncheckedAccount<'info>,
+
+    /// Partner wallet set on the curve; required while it is allowlisted
+    /// CHECK: Validated against the bonding curve and global allowlist in the handler
+    #[account(mut)]
+    pub platform_fee_recipient: Option<UncheckedAccount<'info>>,
 
     /// Creator fee collection wallet (multi-sig controlled)
     /// CHECK: Validated against global configuration
@@ -77,19 +95,27 @@ pub struct SellTokens<'info> {
     #[account(mut)]
     pub seller: Signer<'info>,
 
-    pub token_program: Program<'info, Token>,
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn sell_tokens(
-    ctx: Context<SellTokens>,
+/// `remaining_accounts` carries the curve's creator fee recipients, in slot order, when it splits the creator fee
+pub fn sell_tokens<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SellTokens<'info>>,
     token_amount: u64,
     min_sol_received: u64,
+    deadline: Option<i64>,
 ) -> Result<()> {
     let global = &mut ctx.accounts.global;
     let bonding_curve = &mut ctx.accounts.bonding_curve;
     let clock = Clock::get()?;
 
+    // Reject transactions held past the caller's deadline
+    require_not_expired(deadline, clock.unix_timestamp)?;
+    global.require_sells_open()?;
+    bonding_curve.require_trading_open()?;
+
     // Enhanced validation using new security method
     require!(min_sol_received > 0, BondingCurveError::InvalidSolAmount);
     bonding_curve.validate_trade_amounts(token_amount, false)?;
@@ -104,20 +130,23 @@ pub fn sell_tokens(
     let sol_received = calculate_sell_proceeds(
         token_amount,
         bonding_curve.virtual_sol_reserves,
-        bonding_curve.virtual_token_reserves,
         bonding_curve.real_sol_reserves,
-        bonding_curve.real_token_reserves,
+        bonding_curve.circulating_supply()?,
     )?;
 
+
     // Check slippage protection
     require!(
         sol_received >= min_sol_received,
         BondingCurveError::SlippageExceeded
     );
 
-    // Calculate fees
+    // Calculate fees (platform fee discounted by the user's cumulative volume tier)
+    let platform_fee_basis_points = global.platform_fee_for_volume(
+        ctx.accounts.user_volume_accumulator.volume_sol,
+    );
     let platform_fee = sol_received
-        .checked_mul(global.platform_fee_basis_points as u64)
+        .checked_mul(platform_fee_basis_points as u64)
         .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
         .ok_or(BondingCurveError::Overflow)?;
 
@@ -131,79 +160,93 @@ pub fn sell_tokens(
         .and_then(|x| x.checked_sub(creator_fee))
         .ok_or(BondingCurveError::Underflow)?;
 
-    // Check if sol vault has enough SOL
+    // Split returned tokens between permanent burn and curve reserves
+    let tokens_burned = token_amount
+        .checked_mul(global.sell_burn_basis_points as u64)
+        .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+        .ok_or(BondingCurveError::Overflow)?;
+
+    let tokens_to_reserves = token_amount
+        .checked_sub(tokens_burned)
+        .ok_or(BondingCurveError::Underflow)?;
+
+    // Check if sol vault has enough SOL above its rent-exempt minimum and escrowed sells
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(0);
+    let withdrawable_lamports = ctx.accounts.sol_vault.lamports()
+        .saturating_sub(rent_exempt_minimum)
+        .saturating_sub(bonding_curve.pending_withdrawal_sol);
     require!(
-        ctx.accounts.sol_vault.lamports() >= sol_received,
+        sol_received <= withdrawable_lamports,
         BondingCurveError::InsufficientSolReserves
     );
 
     // Transfer tokens from user to vault
     let transfer_tokens_ctx = CpiContext::new(
         ctx.accounts.token_program.to_account_info(),
-        Transfer {
+        TransferChecked {
             from: ctx.accounts.user_token_account.to_account_info(),
+            mint: ctx.accounts.token_mint.to_account_info(),
             to: ctx.accounts.token_vault.to_account_info(),
             authority: ctx.accounts.seller.to_account_info(),
         },
     );
-    token::transfer(transfer_tokens_ctx, token_amount)?;
-
-    // 🔐 SECURE CPI TRANSFERS: Use proper signed transfers instead of dangerous direct manipulation
-    
-    // Get vault authority for signed transfers
-    let token_mint_key = bonding_curve.token_mint.key();
-    let vault_seeds = &[
-        SOL_VAULT_SEED,
-        token_mint_key.as_ref(),
-        &[bonding_curve.sol_vault_bump],
-    ];
-    let vault_signer = &[&vault_seeds[..]];
-
-    // Transfer net SOL to seller from vault using secure CPI
-    require!(net_sol_received > 0, BondingCurveError::ZeroAmountTransfer);
-    let transfer_to_seller = anchor_lang::system_program::Transfer {
-        from: ctx.accounts.sol_vault.to_account_info(),
-        to: ctx.accounts.seller.to_account_info(),
-    };
-    anchor_lang::system_program::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.system_program.to_account_info(),
-            transfer_to_seller,
-            vault_signer,
-        ),
-        net_sol_received,
-    )?;
-
-    // Transfer platform fee from vault to platform wallet using secure CPI
-    if platform_fee > 0 {
-        let transfer_platform_fee = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.sol_vault.to_account_info(),
-            to: ctx.accounts.platform_wallet.to_account_info(),
-        };
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_platform_fee,
-                vault_signer,
-            ),
-            platform_fee,
-        )?;
+    token_interface::transfer_checked(transfer_tokens_ctx, token_amount, ctx.accounts.token_mint.decimals)?;
+
+    // Burn the deflationary share from the vault using bonding curve authority
+    if tokens_burned > 0 {
+        let token_mint_key = bonding_curve.token_mint.key();
+        let curve_seeds = &[
+            BONDING_CURVE_SEED,
+            token_mint_key.as_ref(),
+            &[bonding_curve.bump],
+        ];
+        let curve_signer = &[&curve_seeds[..]];
+
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.token_mint.to_account_info(),
+                from: ctx.accounts.token_vault.to_account_info(),
+                authority: bonding_curve.to_account_info(),
+            },
+            curve_signer,
+        );
+        token_interface::burn(burn_ctx, tokens_burned)?;
     }
 
-    // Transfer creator fee from vault to creator wallet using secure CPI
-    if creator_fee > 0 {
-        let transfer_creator_fee = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.sol_vault.to_account_info(),
-            to: ctx.accounts.creator_wallet.to_account_info(),
-        };
-        anchor_lang::system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                transfer_creator_fee,
-                vault_signer,
-            ),
-            creator_fee,
-        )?;
+    // Large sells are escrowed in the vault and claimed after a delay to smooth outflows
+    require!(net_sol_received > 0, BondingCurveError::ZeroAmountTransfer);
+    let is_escrowed = global.large_sell_threshold_sol > 0
+        && sol_received > global.large_sell_threshold_sol;
+
+    // ✅ EFFECTS FIRST: commit escrow, reserves and counters before any outbound SOL transfer
+    if is_escrowed {
+        let pending_withdrawal = ctx.accounts.pending_withdrawal
+            .as_mut()
+            .ok_or(BondingCurveError::PendingWithdrawalRequired)?;
+        pending_withdrawal.seller = ctx.accounts.seller.key();
+        pending_withdrawal.token_mint = bonding_curve.token_mint;
+        pending_withdrawal.bump = ctx.bumps.pending_withdrawal
+            .ok_or(BondingCurveError::PendingWithdrawalRequired)?;
+        pending_withdrawal.amount = pending_withdrawal.amount
+            .checked_add(net_sol_received)
+            .ok_or(BondingCurveError::Overflow)?;
+        pending_withdrawal.available_at = clock.unix_timestamp
+            .checked_add(global.withdrawal_delay_seconds)
+            .ok_or(BondingCurveError::Overflow)?;
+
+        bonding_curve.pending_withdrawal_sol = bonding_curve.pending_withdrawal_sol
+            .checked_add(net_sol_received)
+            .ok_or(BondingCurveError::Overflow)?;
+
+        emit!(WithdrawalQueuedEvent {
+            token_mint: bonding_curve.token_mint,
+            seller: ctx.accounts.seller.key(),
+            amount: net_sol_received,
+            total_pending: pending_withdrawal.amount,
+            available_at: pending_withdrawal.available_at,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     // Update bonding curve reserves
@@ -212,7 +255,12 @@ pub fn sell_tokens(
         .ok_or(BondingCurveError::Underflow)?;
 
     bonding_curve.real_token_reserves = bonding_curve.real_token_reserves
-        .checked_add(token_amount)
+        .checked_add(tokens_to_reserves)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    // Burned share left circulation without returning to reserves
+    bonding_curve.tokens_burned = bonding_curve.tokens_burned
+        .checked_add(tokens_burned)
         .ok_or(BondingCurveError::Overflow)?;
 
     // Update volume tracking
@@ -249,6 +297,13 @@ pub fn sell_tokens(
 
     // Update user volume accumulator
     let user_volume = &mut ctx.accounts.user_volume_accumulator;
+    if user_volume.initialize_if_needed(ctx.accounts.seller.key(), ctx.bumps.user_volume_accumulator) {
+        emit!(UserVolumeAccumulatorInitializedEvent {
+            user: ctx.accounts.seller.key(),
+            timestamp: clock.unix_timestamp,
+        });
+    }
+
     user_volume.volume_sol = user_volume.volume_sol
         .checked_add(sol_received)
         .ok_or(BondingCurveError::Overflow)?;
@@ -261,22 +316,129 @@ pub fn sell_tokens(
         .checked_add(1)
         .ok_or(BondingCurveError::Overflow)?;
 
+    user_volume.record_decayed_volume(sol_received, clock.unix_timestamp)?;
     user_volume.last_trade_timestamp = clock.unix_timestamp;
 
     // Calculate new price for event
     let new_price = bonding_curve.current_price()?;
 
+    // Reserves dipping back under the threshold revoke migration eligibility
+    if bonding_curve.refresh_migration_ready(clock.unix_timestamp) {
+        emit!(MigrationReadyEvent {
+            token_mint: bonding_curve.token_mint,
+            bonding_curve: bonding_curve.key(),
+            sol_reserves: bonding_curve.real_sol_reserves,
+            token_reserves: bonding_curve.real_token_reserves,
+            migration_threshold: bonding_curve.migration_threshold,
+            migration_ready: bonding_curve.migration_ready,
+            current_price: new_price,
+            timestamp: clock.unix_timestamp,
+        });
+
+        msg!("⚠️ Reserves fell below the migration threshold - migration no longer ready");
+    }
+
+    // Re-validate the vault right before paying out: everything leaving now must come
+    // from lamports above rent and all escrowed sells, including this one if escrowed
+    let immediate_payout = if is_escrowed { 0 } else { net_sol_received };
+    let outbound_lamports = immediate_payout
+        .checked_add(platform_fee)
+        .and_then(|x| x.checked_add(creator_fee))
+        .ok_or(BondingCurveError::Overflow)?;
+    let payable_lamports = ctx.accounts.sol_vault.lamports()
+        .saturating_sub(rent_exempt_minimum)
+        .saturating_sub(bonding_curve.pending_withdrawal_sol);
+    require!(
+        outbound_lamports <= payable_lamports,
+        BondingCurveError::InsufficientSolReserves
+    );
+
+    // 🔐 SECURE CPI TRANSFERS: Use proper signed transfers instead of dangerous direct manipulation
+    
+    // Get vault authority for signed transfers
+    let token_mint_key = bonding_curve.token_mint.key();
+    let vault_seeds = &[
+        SOL_VAULT_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    if !is_escrowed {
+        // Transfer net SOL to seller from vault using secure CPI
+        let transfer_to_seller = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            to: ctx.accounts.seller.to_account_info(),
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_to_seller,
+                vault_signer,
+            ),
+            net_sol_received,
+        )?;
+    }
+
+    // Transfer platform fee from vault to the partner wallet or platform fee vault using secure CPI
+    if platform_fee > 0 {
+        let transfer_platform_fee = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            to: platform_fee_destination(
+                global,
+                bonding_curve,
+                &ctx.accounts.platform_fee_vault,
+                &ctx.accounts.platform_fee_recipient,
+            )?,
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_platform_fee,
+                vault_signer,
+            ),
+            platform_fee,
+        )?;
+    }
+
+    // Transfer creator fee from vault to the creator wallet or the curve's split recipients using secure CPI
+    for (destination, amount) in creator_fee_transfers(
+        bonding_curve,
+        &ctx.accounts.creator_wallet,
+        ctx.remaining_accounts,
+        creator_fee,
+    )? {
+        if amount == 0 {
+            continue;
+        }
+        let transfer_creator_fee = anchor_lang::system_program::Transfer {
+            from: ctx.accounts.sol_vault.to_account_info(),
+            to: destination,
+        };
+        anchor_lang::system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                transfer_creator_fee,
+                vault_signer,
+            ),
+            amount,
+        )?;
+    }
+
     // Emit sell event
     emit!(TokensSoldEvent {
         token_mint: bonding_curve.token_mint,
         seller: ctx.accounts.seller.key(),
         token_amount,
         sol_received: net_sol_received,
+        tokens_burned,
         platform_fee,
         creator_fee,
         new_sol_reserves: bonding_curve.real_sol_reserves,
         new_token_reserves: bonding_curve.real_token_reserves,
         new_price,
+        price_scale: PRICE_PRECISION_SCALE,
+        token_decimals: ctx.accounts.token_mint.decimals,
         timestamp: clock.unix_timestamp,
     });
 
@@ -284,6 +446,10 @@ pub fn sell_tokens(
     msg!("Amount: {} tokens", token_amount);
     msg!("Gross SOL Received: {} SOL", sol_received);
     msg!("Net SOL Received: {} SOL", net_sol_received);
+    if let Some(pending_withdrawal) = ctx.accounts.pending_withdrawal.as_ref().filter(|_| is_escrowed) {
+        msg!("⏳ Net SOL escrowed until {}", pending_withdrawal.available_at);
+    }
+    msg!("Tokens Burned: {} tokens", tokens_burned);
     msg!("Platform Fee: {} SOL", platform_fee);
     msg!("Creator Fee: {} SOL", creator_fee);
     msg!("New Price: {} SOL per token", new_price);
@@ -295,13 +461,12 @@ pub fn sell_tokens(
 fn calculate_sell_proceeds(
     token_amount: u64,
     virtual_sol_reserves: u64,
-    virtual_token_reserves: u64,
     real_sol_reserves: u64,
-    real_token_reserves: u64,
+    circulating_supply: u64,
 ) -> Result<u64> {
     // Anti-manipulation checks
     require!(virtual_sol_reserves > 0, BondingCurveError::InvalidPrice);
-    require!(virtual_token_reserves > 0, BondingCurveError::InvalidPrice);
+    require!(circulating_supply > 0, BondingCurveError::InvalidPrice);
     require!(token_amount > 0, BondingCurveError::InvalidTokenAmount);
     require!(real_sol_reserves > 0, BondingCurveError::InsufficientSolReserves);
     // Use virtual reserves for pricing calculation
@@ -309,28 +474,240 @@ fn calculate_sell_proceeds(
         .checked_add(real_sol_reserves)
         .ok_or(BondingCurveError::Overflow)?;
     
-    let current_virtual_tokens = virtual_token_reserves
-        .checked_sub(real_token_reserves)
-        .ok_or(BondingCurveError::Underflow)?;
+    let current_virtual_tokens = circulating_supply;
 
     let new_virtual_tokens = current_virtual_tokens
         .checked_add(token_amount)
         .ok_or(BondingCurveError::Overflow)?;
 
-    // k = x * y (constant product)
-    let k = current_virtual_sol
-        .checked_mul(current_virtual_tokens)
+    // k = x * y (constant product); lamports times base units exceeds u64 at launch reserves
+    let k = (current_virtual_sol as u128)
+        .checked_mul(current_virtual_tokens as u128)
         .ok_or(BondingCurveError::Overflow)?;
 
     // new_sol = k / new_tokens
-    let new_virtual_sol = k
-        .checked_div(new_virtual_tokens)
-        .ok_or(BondingCurveError::DivisionByZero)?;
+    let new_virtual_sol: u64 = k
+        .checked_div(new_virtual_tokens as u128)
+        .ok_or(BondingCurveError::DivisionByZero)?
+        .try_into()
+        .map_err(|_| BondingCurveError::Overflow)?;
 
     // proceeds = current_sol - new_sol
     let sol_proceeds = current_virtual_sol
         .checked_sub(new_virtual_sol)
         .ok_or(BondingCurveError::Underflow)?;
 
+    // Reject dust sells that round down to zero proceeds
+    require!(sol_proceeds > 0, BondingCurveError::TradeTooSmall);
+
     Ok(sol_proceeds)
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{instructions::{buy_tokens::{calculate_buy_cost, calculate_buy_fees, split_referral_fee, validate_referrer}, initialize_bonding_curve::rescale_to_decimals}, state::CreatorFeeShare};
+
+    const TRADE_TOKENS: u64 = 1_000_000_000; // 1 token
+
+    fn curve() -> BondingCurve {
+        BondingCurve {
+            virtual_sol_reserves: VIRTUAL_SOL_RESERVES,
+            virtual_token_reserves: VIRTUAL_TOKEN_RESERVES,
+            real_sol_reserves: 1_000_000_000,
+            real_token_reserves: 800_000_000_000_000,
+            token_decimals: TOKEN_DECIMALS,
+            ..Default::default()
+        }
+    }
+
+    /// Buy cost and sell proceeds of a small trade, alongside what current_price implies for it
+    fn quote(bonding_curve: &BondingCurve) -> (u64, u64, u64) {
+        let buy_cost = calculate_buy_cost(
+            TRADE_TOKENS,
+            bonding_curve.virtual_sol_reserves,
+            bonding_curve.real_sol_reserves,
+            bonding_curve.real_token_reserves,
+            bonding_curve.circulating_supply().unwrap(),
+        ).unwrap();
+        let sell_proceeds = calculate_sell_proceeds(
+            TRADE_TOKENS,
+            bonding_curve.virtual_sol_reserves,
+            bonding_curve.real_sol_reserves,
+            bonding_curve.circulating_supply().unwrap(),
+        ).unwrap();
+        let price_implied = bonding_curve.current_price().unwrap() * TRADE_TOKENS / PRICE_PRECISION_SCALE;
+        (buy_cost, sell_proceeds, price_implied)
+    }
+
+    #[test]
+    fn trades_execute_at_the_quoted_price_after_burns() {
+        let mut bonding_curve = curve();
+        let (buy_cost, sell_proceeds, price_implied) = quote(&bonding_curve);
+        assert!(sell_proceeds <= price_implied && price_implied <= buy_cost);
+        assert!(buy_cost - sell_proceeds <= price_implied / 1_000);
+
+        // Burns shrink the supply both sides price on, so trades follow the quoted price up
+        bonding_curve.tokens_burned = 50_000_000_000_000;
+        let (burned_buy_cost, burned_sell_proceeds, burned_price_implied) = quote(&bonding_curve);
+        assert!(burned_price_implied > price_implied);
+        assert!(burned_sell_proceeds <= burned_price_implied && burned_price_implied <= burned_buy_cost);
+        assert!(burned_buy_cost - burned_sell_proceeds <= burned_price_implied / 1_000);
+    }
+
+    #[test]
+    fn dust_buys_that_round_to_zero_sol_are_rejected() {
+        let bonding_curve = curve();
+
+        // A single base unit is worth far less than a lamport at launch prices
+        assert_eq!(
+            calculate_buy_cost(
+                1,
+                bonding_curve.virtual_sol_reserves,
+                bonding_curve.real_sol_reserves,
+                bonding_curve.real_token_reserves,
+                bonding_curve.circulating_supply().unwrap(),
+            ).unwrap_err(),
+            BondingCurveError::TradeTooSmall.into()
+        );
+
+        // A whole token still trades
+        let (buy_cost, sell_proceeds, _) = quote(&bonding_curve);
+        assert!(buy_cost > 0 && sell_proceeds > 0);
+    }
+
+    #[test]
+    fn max_sol_cost_bounds_the_buy_including_fees() {
+        let (sol_cost, platform_fee_basis_points, creator_fee_basis_points) = (1_000_000_000, 100, 50);
+
+        // 1 SOL at 1% + 0.5% costs 1.015 SOL in total
+        let (platform_fee, creator_fee, total_cost) =
+            calculate_buy_fees(sol_cost, platform_fee_basis_points, creator_fee_basis_points, 1_015_000_000).unwrap();
+        assert_eq!((platform_fee, creator_fee, total_cost), (10_000_000, 5_000_000, 1_015_000_000));
+
+        // A bound that covers the curve price but not the fees is slippage
+        assert_eq!(
+            calculate_buy_fees(sol_cost, platform_fee_basis_points, creator_fee_basis_points, 1_014_999_999).unwrap_err(),
+            BondingCurveError::SlippageExceeded.into()
+        );
+        assert!(calculate_buy_fees(sol_cost, platform_fee_basis_points, creator_fee_basis_points, sol_cost).is_err());
+    }
+
+    #[test]
+    fn six_and_nine_decimal_mints_quote_the_same_sol_price() {
+        // Launch reserves as initialize_bonding_curve rescales them for each mint
+        let launch = |decimals| {
+            let total_supply = rescale_to_decimals(TOTAL_SUPPLY, decimals).unwrap();
+            BondingCurve {
+                virtual_sol_reserves: VIRTUAL_SOL_RESERVES,
+                virtual_token_reserves: rescale_to_decimals(VIRTUAL_TOKEN_RESERVES, decimals).unwrap(),
+                real_token_reserves: total_supply - total_supply * LP_RESERVE_PERCENTAGE / 100,
+                token_decimals: decimals,
+                ..Default::default()
+            }
+        };
+        let (mut six, mut nine) = (launch(6), launch(TOKEN_DECIMALS));
+        assert_eq!(six.real_token_reserves * 1_000, nine.real_token_reserves);
+        assert_eq!(six.current_price().unwrap(), nine.current_price().unwrap());
+
+        // One whole token costs the same SOL on either mint, before and after a large buy
+        for _ in 0..2 {
+            let buy_whole_token = |bonding_curve: &BondingCurve| calculate_buy_cost(
+                10u64.pow(bonding_curve.token_decimals as u32),
+                bonding_curve.virtual_sol_reserves,
+                bonding_curve.real_sol_reserves,
+                bonding_curve.real_token_reserves,
+                bonding_curve.circulating_supply().unwrap(),
+            ).unwrap();
+            let (six_cost, nine_cost) = (buy_whole_token(&six), buy_whole_token(&nine));
+            assert!(six_cost.abs_diff(nine_cost) <= 1);
+
+            // Both buy 100k whole tokens, then quote again
+            for bonding_curve in [&mut six, &mut nine] {
+                let tokens = 100_000 * 10u64.pow(bonding_curve.token_decimals as u32);
+                let sol_cost = calculate_buy_cost(
+                    tokens,
+                    bonding_curve.virtual_sol_reserves,
+                    bonding_curve.real_sol_reserves,
+                    bonding_curve.real_token_reserves,
+                    bonding_curve.circulating_supply().unwrap(),
+                ).unwrap();
+                bonding_curve.real_sol_reserves += sol_cost;
+                bonding_curve.real_token_reserves -= tokens;
+            }
+            assert!(six.real_sol_reserves.abs_diff(nine.real_sol_reserves) <= 1);
+            assert!(six.current_price().unwrap().abs_diff(nine.current_price().unwrap()) <= 1);
+        }
+    }
+
+    #[test]
+    fn creator_fee_recipients_must_match_the_split_and_be_writable() {
+        let (first, second, creator_wallet) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bonding_curve = curve();
+        bonding_curve.creator_fee_shares[0] = CreatorFeeShare { recipient: first, basis_points: 7_000 };
+        bonding_curve.creator_fee_shares[1] = CreatorFeeShare { recipient: second, basis_points: 3_000 };
+
+        let owner = anchor_lang::system_program::ID;
+        let mut lamports = [0u64; 3];
+        let mut data: [[u8; 0]; 3] = [[]; 3];
+        let [creator_lamports, first_lamports, second_lamports] = &mut lamports;
+        let [creator_data, first_data, second_data] = &mut data;
+        let creator_info = AccountInfo::new(&creator_wallet, false, true, creator_lamports, creator_data, &owner, false, 0);
+        let creator_wallet = UncheckedAccount::try_from(&creator_info);
+        let first_info = AccountInfo::new(&first, false, true, first_lamports, first_data, &owner, false, 0);
+        let mut second_info = AccountInfo::new(&second, false, true, second_lamports, second_data, &owner, false, 0);
+
+        let transfers = creator_fee_transfers(
+            &bonding_curve,
+            &creator_wallet,
+            &[first_info.clone(), second_info.clone()],
+            1_000,
+        ).unwrap();
+        let paid: Vec<(Pubkey, u64)> = transfers.iter().map(|(info, amount)| (info.key(), *amount)).collect();
+        assert_eq!(paid, vec![(first, 700), (second, 300)]);
+
+        // Out of order or missing recipients are rejected
+        for recipients in [vec![second_info.clone(), first_info.clone()], vec![first_info.clone()]] {
+            assert_eq!(
+                creator_fee_transfers(&bonding_curve, &creator_wallet, &recipients, 1_000).unwrap_err(),
+                BondingCurveError::CreatorFeeRecipientMismatch.into()
+            );
+        }
+
+        // A read-only recipient could not be credited, so it is rejected up front
+        second_info.is_writable = false;
+        assert_eq!(
+            creator_fee_transfers(&bonding_curve, &creator_wallet, &[first_info, second_info], 1_000).unwrap_err(),
+            BondingCurveError::CreatorFeeRecipientMismatch.into()
+        );
+    }
+
+    #[test]
+    fn trades_past_their_deadline_are_rejected() {
+        let now = 1_700_000_000;
+        assert!(require_not_expired(None, now).is_ok());
+        assert!(require_not_expired(Some(now), now).is_ok());
+        assert!(require_not_expired(Some(now + 30), now).is_ok());
+        assert_eq!(
+            require_not_expired(Some(now - 1), now).unwrap_err(),
+            BondingCurveError::TransactionExpired.into()
+        );
+    }
+
+    #[test]
+    fn referrals_pay_out_of_the_platform_fee_to_a_distinct_wallet() {
+        let buyer = Pubkey::new_unique();
+        let platform_wallet = Pubkey::new_unique();
+
+        assert!(validate_referrer(Pubkey::new_unique(), buyer, platform_wallet).is_ok());
+        for referrer in [buyer, platform_wallet] {
+            assert_eq!(
+                validate_referrer(referrer, buyer, platform_wallet).unwrap_err(),
+                BondingCurveError::InvalidReferrer.into()
+            );
+        }
+
+        // The referrer's cut comes out of the platform fee, so the buyer pays the same total
+        assert_eq!(split_referral_fee(30_000).unwrap(), (24_000, 6_000));
+        assert_eq!(split_referral_fee(3).unwrap(), (3, 0));
+    }
+}
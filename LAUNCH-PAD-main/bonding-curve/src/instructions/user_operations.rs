@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use crate::{constants::*, state::{Global, UserVolumeAccumulator}, events::*};
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TokenInterface};
+use crate::{constants::*, state::{validate_metadata_uri, Global, BondingCurve, PendingWithdrawal, UserVolumeAccumulator}, events::*, errors::*};
 
 #[derive(Accounts)]
 pub struct InitUserVolumeAccumulator<'info> {
@@ -28,12 +29,7 @@ pub fn init_user_volume_accumulator(ctx: Context<InitUserVolumeAccumulator>) ->
     let clock = Clock::get()?;
 
     // Initialize user volume tracking
-    user_volume.user = ctx.accounts.user.key();
-    user_volume.volume_sol = 0;
-    user_volume.volume_tokens = 0;
-    user_volume.trades_count = 0;
-    user_volume.last_trade_timestamp = 0;
-    user_volume.bump = ctx.bumps.user_volume_accumulator;
+    user_volume.initialize_if_needed(ctx.accounts.user.key(), ctx.bumps.user_volume_accumulator);
 
     // Emit initialization event
     emit!(UserVolumeAccumulatorInitializedEvent {
@@ -44,4 +40,269 @@ pub fn init_user_volume_accumulator(ctx: Context<InitUserVolumeAccumulator>) ->
     msg!("📊 User volume accumulator initialized for: {}", ctx.accounts.user.key());
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct CloseUserVolumeAccumulator<'info> {
+    #[account(
+        mut,
+        close = user,
+        seeds = [USER_VOLUME_SEED, user.key().as_ref()],
+        bump = user_volume_accumulator.bump,
+        constraint = user_volume_accumulator.user == user.key()
+            @ BondingCurveError::UnauthorizedAccess
+    )]
+    pub user_volume_accumulator: Account<'info, UserVolumeAccumulator>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+}
+
+pub fn close_user_volume_accumulator(ctx: Context<CloseUserVolumeAccumulator>) -> Result<()> {
+    let user_volume = &ctx.accounts.user_volume_accumulator;
+    let clock = Clock::get()?;
+
+    // Rent goes back to the user; volume history (and any fee tier) restarts on re-init
+    emit!(UserVolumeAccumulatorClosedEvent {
+        user: ctx.accounts.user.key(),
+        volume_sol: user_volume.volume_sol,
+        trades_count: user_volume.trades_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("📊 User volume accumulator closed for: {}", ctx.accounts.user.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimWithdrawal<'info> {
+    #[account(mut)]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// SOL vault (multi-sig protected)
+    #[account(
+        mut,
+        seeds = [SOL_VAULT_SEED, bonding_curve.token_mint.as_ref()],
+        bump = bonding_curve.sol_vault_bump
+    )]
+    /// CHECK: This is a PDA owned by the system program
+    pub sol_vault: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [PENDING_WITHDRAWAL_SEED, bonding_curve.token_mint.as_ref(), seller.key().as_ref()],
+        bump = pending_withdrawal.bump,
+        constraint = pending_withdrawal.seller == seller.key()
+            @ BondingCurveError::UnauthorizedAccess
+    )]
+    pub pending_withdrawal: Account<'info, PendingWithdrawal>,
+
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let pending_withdrawal = &mut ctx.accounts.pending_withdrawal;
+    let clock = Clock::get()?;
+
+    let amount = pending_withdrawal.amount;
+    require!(amount > 0, BondingCurveError::NoPendingWithdrawal);
+    require!(
+        clock.unix_timestamp >= pending_withdrawal.available_at,
+        BondingCurveError::WithdrawalNotReady
+    );
+
+    let token_mint_key = bonding_curve.token_mint.key();
+    let vault_seeds = &[
+        SOL_VAULT_SEED,
+        token_mint_key.as_ref(),
+        &[bonding_curve.sol_vault_bump],
+    ];
+    let vault_signer = &[&vault_seeds[..]];
+
+    let transfer_to_seller = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.sol_vault.to_account_info(),
+        to: ctx.accounts.seller.to_account_info(),
+    };
+    anchor_lang::system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            transfer_to_seller,
+            vault_signer,
+        ),
+        amount,
+    )?;
+
+    pending_withdrawal.amount = 0;
+    bonding_curve.pending_withdrawal_sol = bonding_curve.pending_withdrawal_sol
+        .checked_sub(amount)
+        .ok_or(BondingCurveError::Underflow)?;
+
+    emit!(WithdrawalClaimedEvent {
+        token_mint: bonding_curve.token_mint,
+        seller: ctx.accounts.seller.key(),
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💸 Escrowed sell proceeds claimed");
+    msg!("Seller: {}", ctx.accounts.seller.key());
+    msg!("Amount: {} SOL", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BurnTokens<'info> {
+    #[account(
+        mut,
+        constraint = !bonding_curve.is_migrated @ BondingCurveError::AlreadyMigrated
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    /// Token mint (mutable for supply reduction)
+    #[account(
+        mut,
+        constraint = token_mint.key() == bonding_curve.token_mint,
+        mint::token_program = token_program
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// User's token account
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = user,
+        token::token_program = token_program
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub user: Signer<'info>,
+
+    /// SPL Token or Token-2022 program matching the mint owner
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    require!(amount > 0, BondingCurveError::InvalidTokenAmount);
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        BondingCurveError::InsufficientTokenReserves
+    );
+    require!(
+        amount <= bonding_curve.circulating_supply()?,
+        BondingCurveError::InvalidTokenAmount
+    );
+
+    let burn_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Burn {
+            mint: ctx.accounts.token_mint.to_account_info(),
+            from: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token_interface::burn(burn_ctx, amount)?;
+
+    // Burned tokens leave circulation, so the curve quotes a higher price
+    bonding_curve.tokens_burned = bonding_curve.tokens_burned
+        .checked_add(amount)
+        .ok_or(BondingCurveError::Overflow)?;
+
+    let new_price = bonding_curve.current_price()?;
+
+    emit!(TokensBurnedEvent {
+        token_mint: bonding_curve.token_mint,
+        user: ctx.accounts.user.key(),
+        amount,
+        total_tokens_burned: bonding_curve.tokens_burned,
+        new_price,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🔥 Tokens burned");
+    msg!("Amount: {} tokens", amount);
+    msg!("Total Burned: {} tokens", bonding_curve.tokens_burned);
+    msg!("New Price: {} SOL per token", new_price);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenUri<'info> {
+    #[account(
+        mut,
+        constraint = bonding_curve.creator == creator.key()
+            @ BondingCurveError::UnauthorizedAccess
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn update_token_uri(ctx: Context<UpdateTokenUri>, new_uri: String) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    // Same bounds as initialize_bonding_curve so the account size always fits
+    validate_metadata_uri(&new_uri)?;
+
+    let previous_uri = std::mem::replace(&mut bonding_curve.uri, new_uri.clone());
+
+    emit!(TokenUriUpdatedEvent {
+        token_mint: bonding_curve.token_mint,
+        bonding_curve: bonding_curve.key(),
+        previous_uri,
+        new_uri: new_uri.clone(),
+        updated_by: ctx.accounts.creator.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("🖼️ Token URI updated for: {}", bonding_curve.token_mint);
+    msg!("New URI: {}", new_uri);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreatorSetPause<'info> {
+    #[account(
+        mut,
+        constraint = bonding_curve.creator == creator.key()
+            @ BondingCurveError::UnauthorizedAccess,
+        constraint = !bonding_curve.is_migrated @ BondingCurveError::AlreadyMigrated
+    )]
+    pub bonding_curve: Account<'info, BondingCurve>,
+
+    pub creator: Signer<'info>,
+}
+
+pub fn creator_set_pause(ctx: Context<CreatorSetPause>, paused: bool) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let clock = Clock::get()?;
+
+    bonding_curve.creator_paused = paused;
+
+    emit!(CreatorPauseUpdatedEvent {
+        token_mint: bonding_curve.token_mint,
+        bonding_curve: bonding_curve.key(),
+        creator: ctx.accounts.creator.key(),
+        paused,
+        timestamp: clock.unix_timestamp,
+    });
+
+    if paused {
+        msg!("⏸️ Trading paused by creator for: {}", bonding_curve.token_mint);
+    } else {
+        msg!("▶️ Trading resumed by creator for: {}", bonding_curve.token_mint);
+    }
+
+    Ok(())
+}
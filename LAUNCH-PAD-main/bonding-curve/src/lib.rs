@@ -1,3 +1,7 @@
+// Anchor 0.31's generated IDL instructions call the deprecated AccountInfo::realloc
+// at crate level, outside anything a narrower allow could cover
+#![allow(deprecated)]
+
 use anchor_lang::prelude::*;
 
 declare_id!("8DV5gyq2Dsy5DW5dMQtLZ5FGw657BUH2h9pZyBDcoSz3");
@@ -20,42 +24,150 @@ pub mod bonding_curve {
     }
 
     /// Initialize bonding curve with multi-sig security
+    #[allow(clippy::too_many_arguments)] // Instruction arguments map 1:1 to the IDL
     pub fn initialize_bonding_curve(
         ctx: Context<InitializeBondingCurve>,
         name: String,
         symbol: String,
         uri: String,
+        lp_reserve_percentage: Option<u8>,
+        decimals: u8,
+        max_migration_fee_lamports: Option<u64>,
+        platform_fee_recipient: Option<Pubkey>,
+        creator_fee_shares: Option<[state::CreatorFeeShare; constants::MAX_CREATOR_FEE_RECIPIENTS]>,
     ) -> Result<()> {
-        instructions::initialize_bonding_curve(ctx, name, symbol, uri)
+        instructions::initialize_bonding_curve(
+            ctx,
+            name,
+            symbol,
+            uri,
+            lp_reserve_percentage,
+            decimals,
+            max_migration_fee_lamports,
+            platform_fee_recipient,
+            creator_fee_shares,
+        )
     }
 
-    /// Buy tokens from bonding curve
-    pub fn buy_tokens(ctx: Context<BuyTokens>, token_amount: u64, max_sol_cost: u64) -> Result<()> {
-        instructions::buy_tokens(ctx, token_amount, max_sol_cost)
+    /// Buy tokens from bonding curve; `max_sol_cost` caps the total spend including fees
+    pub fn buy_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokens<'info>>,
+        token_amount: u64,
+        max_sol_cost: u64,
+        allow_partial: bool,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::buy_tokens(ctx, token_amount, max_sol_cost, allow_partial, deadline)
+    }
+
+    /// Buy tokens and pay a share of the platform fee to a referrer
+    pub fn buy_tokens_with_referral<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyTokensWithReferral<'info>>,
+        token_amount: u64,
+        max_sol_cost: u64,
+        allow_partial: bool,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::buy_tokens_with_referral(ctx, token_amount, max_sol_cost, allow_partial, deadline)
     }
 
     /// Sell tokens to bonding curve
-    pub fn sell_tokens(ctx: Context<SellTokens>, token_amount: u64, min_sol_received: u64) -> Result<()> {
-        instructions::sell_tokens(ctx, token_amount, min_sol_received)
+    pub fn sell_tokens<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SellTokens<'info>>,
+        token_amount: u64,
+        min_sol_received: u64,
+        deadline: Option<i64>,
+    ) -> Result<()> {
+        instructions::sell_tokens(ctx, token_amount, min_sol_received, deadline)
+    }
+
+    /// Pause or resume trading on a token (creator only, before migration)
+    pub fn creator_set_pause(ctx: Context<CreatorSetPause>, paused: bool) -> Result<()> {
+        instructions::creator_set_pause(ctx, paused)
+    }
+
+    /// Update the stored token metadata URI (creator only)
+    pub fn update_token_uri(ctx: Context<UpdateTokenUri>, new_uri: String) -> Result<()> {
+        instructions::update_token_uri(ctx, new_uri)
+    }
+
+    /// Permanently burn tokens from the caller's account, raising the curve price
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        instructions::burn_tokens(ctx, amount)
     }
 
-    /// Initialize user volume accumulator
+    /// Claim SOL escrowed by a large sell once its delay has passed
+    pub fn claim_withdrawal(ctx: Context<ClaimWithdrawal>) -> Result<()> {
+        instructions::claim_withdrawal(ctx)
+    }
+
+    /// Creator's first buy on their curve, escrowed and vested over time
+    pub fn creator_dev_buy<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreatorDevBuy<'info>>,
+        token_amount: u64,
+        max_sol_cost: u64,
+        cliff_seconds: i64,
+        duration_seconds: i64,
+    ) -> Result<()> {
+        instructions::creator_dev_buy(ctx, token_amount, max_sol_cost, cliff_seconds, duration_seconds)
+    }
+
+    /// Release the vested part of the creator's dev-buy allocation
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested(ctx)
+    }
+
+    /// Initialize user volume accumulator (optional - buy/sell create it on first trade)
     pub fn init_user_volume_accumulator(ctx: Context<InitUserVolumeAccumulator>) -> Result<()> {
         instructions::init_user_volume_accumulator(ctx)
     }
 
+    /// Close user volume accumulator and reclaim its rent
+    pub fn close_user_volume_accumulator(ctx: Context<CloseUserVolumeAccumulator>) -> Result<()> {
+        instructions::close_user_volume_accumulator(ctx)
+    }
+
     /// Migrate to AMM (requires multi-sig approval)
-    pub fn migrate_to_amm(ctx: Context<MigrateToAmm>) -> Result<()> {
-        instructions::migrate_to_amm(ctx)
+    pub fn migrate_to_amm(
+        ctx: Context<MigrateToAmm>,
+        min_sqrt_price_x64: u128,
+        max_sqrt_price_x64: u128,
+        min_price_hold_seconds: Option<i64>,
+    ) -> Result<()> {
+        instructions::migrate_to_amm(ctx, min_sqrt_price_x64, max_sqrt_price_x64, min_price_hold_seconds)
+    }
+
+    /// Confirm a migrated curve's AMM pool is live, ending the abort window (multi-sig required)
+    pub fn confirm_amm_pool(ctx: Context<ConfirmAmmPool>) -> Result<()> {
+        instructions::confirm_amm_pool(ctx)
+    }
+
+    /// Roll back an unconfirmed migration and re-enable curve trading (multi-sig required)
+    pub fn abort_migration(ctx: Context<AbortMigration>) -> Result<()> {
+        instructions::abort_migration(ctx)
+    }
+
+    /// Preview migration amounts and the opening AMM pool state without migrating
+    pub fn get_migration_preview(ctx: Context<GetMigrationPreview>) -> Result<state::MigrationPreview> {
+        instructions::get_migration_preview(ctx)
     }
 
     /// Update global settings (multi-sig required)
+    #[allow(clippy::too_many_arguments)] // Instruction arguments map 1:1 to the IDL
     pub fn update_global_settings(
         ctx: Context<UpdateGlobalSettings>,
         platform_fee_basis_points: Option<u16>,
         creator_fee_basis_points: Option<u16>,
         migration_fee_basis_points: Option<u16>,
         migration_enabled: Option<bool>,
+        sell_burn_basis_points: Option<u16>,
+        min_lp_reserve_percentage: Option<u8>,
+        max_lp_reserve_percentage: Option<u8>,
+        fund_wallet: Option<Pubkey>,
+        fund_share_basis_points: Option<u16>,
+        large_sell_threshold_sol: Option<u64>,
+        withdrawal_delay_seconds: Option<i64>,
+        unique_symbols_enabled: Option<bool>,
     ) -> Result<()> {
         instructions::update_global_settings(
             ctx,
@@ -63,9 +175,35 @@ pub mod bonding_curve {
             creator_fee_basis_points,
             migration_fee_basis_points,
             migration_enabled,
+            sell_burn_basis_points,
+            min_lp_reserve_percentage,
+            max_lp_reserve_percentage,
+            fund_wallet,
+            fund_share_basis_points,
+            large_sell_threshold_sol,
+            withdrawal_delay_seconds,
+            unique_symbols_enabled,
         )
     }
 
+    /// Set volume-based platform fee discount tiers (multi-sig required)
+    pub fn set_volume_fee_tiers(
+        ctx: Context<SetVolumeFeeTiers>,
+        volume_fee_tiers: [state::VolumeFeeTier; constants::VOLUME_FEE_TIER_COUNT],
+    ) -> Result<()> {
+        instructions::set_volume_fee_tiers(ctx, volume_fee_tiers)
+    }
+
+    /// Update migration threshold for an active curve (multi-sig required)
+    pub fn set_migration_threshold(ctx: Context<SetMigrationThreshold>, new_threshold: u64) -> Result<()> {
+        instructions::set_migration_threshold(ctx, new_threshold)
+    }
+
+    /// Fund the platform fee vault PDA so trades can deposit fees (multi-sig required)
+    pub fn initialize_platform_fee_vault(ctx: Context<InitializePlatformFeeVault>) -> Result<()> {
+        instructions::initialize_platform_fee_vault(ctx)
+    }
+
     /// Collect platform fees (multi-sig required)
     pub fn collect_platform_fees(ctx: Context<CollectPlatformFees>, amount: u64) -> Result<()> {
         instructions::collect_platform_fees(ctx, amount)
@@ -76,6 +214,29 @@ pub mod bonding_curve {
         instructions::collect_creator_fees(ctx, amount)
     }
 
+    /// Set an allowlisted AMM migration target (multi-sig required)
+    pub fn set_allowed_amm_program(
+        ctx: Context<SetAllowedAmmProgram>,
+        index: u8,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        instructions::set_allowed_amm_program(ctx, index, program_id)
+    }
+
+    /// Set an allowlisted partner platform fee wallet (multi-sig required)
+    pub fn set_partner_fee_wallet(
+        ctx: Context<SetPartnerFeeWallet>,
+        index: u8,
+        wallet: Pubkey,
+    ) -> Result<()> {
+        instructions::set_partner_fee_wallet(ctx, index, wallet)
+    }
+
+    /// Rescue tokens held by a curve above its accounted reserves (multi-sig required)
+    pub fn rescue_tokens(ctx: Context<RescueTokens>) -> Result<()> {
+        instructions::rescue_tokens(ctx)
+    }
+
     /// Emergency pause (multi-sig required)
     pub fn emergency_pause(ctx: Context<EmergencyPause>) -> Result<()> {
         instructions::emergency_pause(ctx)
@@ -85,4 +246,24 @@ pub mod bonding_curve {
     pub fn resume_operations(ctx: Context<ResumeOperations>) -> Result<()> {
         instructions::resume_operations(ctx)
     }
+
+    /// Pause or resume buys only (multi-sig required)
+    pub fn set_buys_paused(ctx: Context<SetBuysPaused>, paused: bool) -> Result<()> {
+        instructions::set_buys_paused(ctx, paused)
+    }
+
+    /// Pause or resume sells only (multi-sig required)
+    pub fn set_sells_paused(ctx: Context<SetSellsPaused>, paused: bool) -> Result<()> {
+        instructions::set_sells_paused(ctx, paused)
+    }
+
+    /// Upgrade a v1 global account to the v2 layout (multi-sig required)
+    pub fn migrate_global_v1_to_v2(ctx: Context<MigrateGlobalV1ToV2>) -> Result<()> {
+        instructions::migrate_global_v1_to_v2(ctx)
+    }
+
+    /// Upgrade a bonding curve created before the current layout (multi-sig required)
+    pub fn migrate_bonding_curve_v1(ctx: Context<MigrateBondingCurveV1>) -> Result<()> {
+        instructions::migrate_bonding_curve_v1(ctx)
+    }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::{constants::{ALLOWED_AMM_PROGRAM_COUNT, BASIS_POINTS_DENOMINATOR, FUND_SHARE_BASIS_POINTS, GLOBAL_VERSION, LARGE_SELL_THRESHOLD_SOL, MAX_CREATOR_FEE_RECIPIENTS, MAX_MIGRATION_THRESHOLD, MAX_LP_RESERVE_PERCENTAGE, MIN_LP_RESERVE_PERCENTAGE, PARTNER_FEE_WALLET_COUNT, PRICE_PRECISION_SCALE, SELL_BURN_BASIS_POINTS, TOKEN_DECIMALS, VOLUME_DECAY_HALF_LIFE_SECONDS, VOLUME_FEE_TIER_COUNT, WITHDRAWAL_DELAY_SECONDS}, errors::BondingCurveError};
 
 #[account]
 pub struct Global {
@@ -32,8 +33,34 @@ pub struct Global {
     pub successful_migrations: u32,
     /// Program version
     pub version: u8,
+    /// Platform fee discounts by cumulative user volume
+    pub volume_fee_tiers: [VolumeFeeTier; VOLUME_FEE_TIER_COUNT],
+    /// Lowest LP reserve percentage a launch may choose
+    pub min_lp_reserve_percentage: u8,
+    /// Highest LP reserve percentage a launch may choose
+    pub max_lp_reserve_percentage: u8,
+    /// Blocks buys only; is_paused still halts everything
+    pub buys_paused: bool,
+    /// Blocks sells only; is_paused still halts everything
+    pub sells_paused: bool,
+    /// Fund wallet receiving its share of migration fees
+    pub fund_wallet: Pubkey,
+    /// Share of the migration fee routed to the fund wallet, in basis points
+    pub fund_share_basis_points: u16,
+    /// AMM programs migration may target; unused slots are the default pubkey
+    pub allowed_amm_programs: [Pubkey; ALLOWED_AMM_PROGRAM_COUNT],
+    /// Sells above this gross SOL amount are escrowed (0 disables)
+    pub large_sell_threshold_sol: u64,
+    /// Seconds an escrowed sell waits before it can be claimed
+    pub withdrawal_delay_seconds: i64,
+    /// Require each launch to claim its symbol in the name registry
+    pub unique_symbols_enabled: bool,
+    /// Partner wallets a curve may route its platform fee to; unused slots are the default pubkey
+    pub partner_fee_wallets: [Pubkey; PARTNER_FEE_WALLET_COUNT],
+    /// Share of sold tokens burned instead of returned to reserves
+    pub sell_burn_basis_points: u16,
     /// Reserved space for future upgrades
-    pub reserved: [u64; 8],
+    pub reserved: [u8; 62],
 }
 
 impl Global {
@@ -53,7 +80,20 @@ impl Global {
         4 + // tokens_created
         4 + // successful_migrations
         1 + // version
-        64; // reserved
+        VolumeFeeTier::LEN * VOLUME_FEE_TIER_COUNT + // volume_fee_tiers
+        1 + // min_lp_reserve_percentage
+        1 + // max_lp_reserve_percentage
+        1 + // buys_paused
+        1 + // sells_paused
+        32 + // fund_wallet
+        2 + // fund_share_basis_points
+        32 * ALLOWED_AMM_PROGRAM_COUNT + // allowed_amm_programs
+        8 + // large_sell_threshold_sol
+        8 + // withdrawal_delay_seconds
+        1 + // unique_symbols_enabled
+        32 * PARTNER_FEE_WALLET_COUNT + // partner_fee_wallets
+        2 + // sell_burn_basis_points
+        62; // reserved
 
     /// Verify multi-sig authorization
     pub fn verify_multisig_auth(&self, admin_signer: &Signer, multisig_signer: &Signer) -> Result<()> {
@@ -73,9 +113,188 @@ impl Global {
         require!(!self.is_paused, BondingCurveError::OperationsPaused);
         Ok(())
     }
+
+    /// Check buys are open; the emergency pause still halts everything
+    pub fn require_buys_open(&self) -> Result<()> {
+        self.require_not_paused()?;
+        require!(!self.buys_paused, BondingCurveError::BuysPaused);
+        Ok(())
+    }
+
+    /// Check sells are open; the emergency pause still halts everything
+    pub fn require_sells_open(&self) -> Result<()> {
+        self.require_not_paused()?;
+        require!(!self.sells_paused, BondingCurveError::SellsPaused);
+        Ok(())
+    }
+
+    /// Check if migration may target the given AMM program
+    pub fn is_allowed_amm_program(&self, program_id: &Pubkey) -> bool {
+        *program_id != Pubkey::default() && self.allowed_amm_programs.contains(program_id)
+    }
+
+    /// Check if a curve may route its platform fee to the given wallet
+    pub fn is_partner_fee_wallet(&self, wallet: &Pubkey) -> bool {
+        *wallet != Pubkey::default() && self.partner_fee_wallets.contains(wallet)
+    }
+
+    /// Platform fee for a user with the given cumulative volume
+    pub fn platform_fee_for_volume(&self, volume_sol: u64) -> u16 {
+        self.volume_fee_tiers
+            .iter()
+            .filter(|tier| tier.is_enabled() && volume_sol >= tier.volume_threshold_sol)
+            .map(|tier| tier.platform_fee_basis_points)
+            .fold(self.platform_fee_basis_points, u16::min)
+    }
+}
+
+/// Global as laid out at version 1; only read by migrate_global_v1_to_v2
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GlobalV1 {
+    pub admin_authority: Pubkey,
+    pub multisig_authority: Pubkey,
+    pub platform_wallet: Pubkey,
+    pub creator_wallet: Pubkey,
+    pub platform_fee_basis_points: u16,
+    pub creator_fee_basis_points: u16,
+    pub migration_fee_basis_points: u16,
+    pub max_slippage_basis_points: u16,
+    pub migration_enabled: bool,
+    pub is_paused: bool,
+    pub total_volume_sol: u64,
+    pub total_fees_collected: u64,
+    pub tokens_created: u32,
+    pub successful_migrations: u32,
+    pub version: u8,
+    pub reserved: [u64; 8],
+}
+
+impl GlobalV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin_authority
+        32 + // multisig_authority
+        32 + // platform_wallet
+        32 + // creator_wallet
+        2 + // platform_fee_basis_points
+        2 + // creator_fee_basis_points
+        2 + // migration_fee_basis_points
+        2 + // max_slippage_basis_points
+        1 + // migration_enabled
+        1 + // is_paused
+        8 + // total_volume_sol
+        8 + // total_fees_collected
+        4 + // tokens_created
+        4 + // successful_migrations
+        1 + // version
+        64; // reserved
+
+    /// Read a Global account still in the v1 layout. An account that was already
+    /// migrated has grown to Global::LEN, so a second migration is rejected here.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(data.len() == Self::LEN, BondingCurveError::InvalidAccountVersion);
+        require!(
+            &data[..8] == Global::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let global = Self::deserialize(&mut &data[8..])?;
+        require!(global.version == 1, BondingCurveError::InvalidAccountVersion);
+        Ok(global)
+    }
+
+    /// Current layout of this account, with every field added since v1 seeded from the
+    /// same defaults initialize_global uses
+    pub fn into_current(self) -> Global {
+        Global {
+            admin_authority: self.admin_authority,
+            multisig_authority: self.multisig_authority,
+            platform_wallet: self.platform_wallet,
+            creator_wallet: self.creator_wallet,
+            platform_fee_basis_points: self.platform_fee_basis_points,
+            creator_fee_basis_points: self.creator_fee_basis_points,
+            migration_fee_basis_points: self.migration_fee_basis_points,
+            max_slippage_basis_points: self.max_slippage_basis_points,
+            migration_enabled: self.migration_enabled,
+            is_paused: self.is_paused,
+            total_volume_sol: self.total_volume_sol,
+            total_fees_collected: self.total_fees_collected,
+            tokens_created: self.tokens_created,
+            successful_migrations: self.successful_migrations,
+            version: GLOBAL_VERSION,
+            volume_fee_tiers: [VolumeFeeTier::default(); VOLUME_FEE_TIER_COUNT],
+            min_lp_reserve_percentage: MIN_LP_RESERVE_PERCENTAGE,
+            max_lp_reserve_percentage: MAX_LP_RESERVE_PERCENTAGE,
+            buys_paused: false,
+            sells_paused: false,
+            fund_wallet: self.platform_wallet,
+            fund_share_basis_points: FUND_SHARE_BASIS_POINTS,
+            allowed_amm_programs: [Pubkey::default(); ALLOWED_AMM_PROGRAM_COUNT],
+            large_sell_threshold_sol: LARGE_SELL_THRESHOLD_SOL,
+            withdrawal_delay_seconds: WITHDRAWAL_DELAY_SECONDS,
+            unique_symbols_enabled: false,
+            partner_fee_wallets: [Pubkey::default(); PARTNER_FEE_WALLET_COUNT],
+            sell_burn_basis_points: SELL_BURN_BASIS_POINTS,
+            reserved: [0; 62],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct VolumeFeeTier {
+    /// Cumulative user volume (in SOL) required for this tier; zero disables the tier
+    pub volume_threshold_sol: u64,
+    /// Platform fee in basis points once the threshold is crossed
+    pub platform_fee_basis_points: u16,
+}
+
+impl VolumeFeeTier {
+    pub const LEN: usize = 8 + // volume_threshold_sol
+        2; // platform_fee_basis_points
+
+    pub fn is_enabled(&self) -> bool {
+        self.volume_threshold_sol > 0
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CreatorFeeShare {
+    /// Wallet receiving this share of the creator fee
+    pub recipient: Pubkey,
+    /// Share of the creator fee in basis points; zero disables the slot
+    pub basis_points: u16,
+}
+
+impl CreatorFeeShare {
+    pub const LEN: usize = 32 + // recipient
+        2; // basis_points
+
+    pub fn is_enabled(&self) -> bool {
+        self.basis_points > 0
+    }
+
+    /// Check a launch's split: enabled shares go to distinct wallets and cover the whole fee.
+    /// All slots disabled is valid and keeps paying the global creator wallet.
+    pub fn validate_split(shares: &[CreatorFeeShare]) -> Result<()> {
+        let mut creator_fee_basis_points = 0u64;
+        for (index, share) in shares.iter().enumerate().filter(|(_, share)| share.is_enabled()) {
+            require!(
+                share.recipient != Pubkey::default()
+                    && !shares[..index]
+                        .iter()
+                        .any(|other| other.is_enabled() && other.recipient == share.recipient),
+                BondingCurveError::InvalidCreatorFeeShares
+            );
+            creator_fee_basis_points += share.basis_points as u64;
+        }
+        require!(
+            creator_fee_basis_points == 0 || creator_fee_basis_points == BASIS_POINTS_DENOMINATOR,
+            BondingCurveError::InvalidCreatorFeeShares
+        );
+        Ok(())
+    }
 }
 
 #[account]
+#[derive(Default)]
 pub struct BondingCurve {
     /// Associated token mint
     pub token_mint: Pubkey,
@@ -126,8 +345,34 @@ pub struct BondingCurve {
     pub sol_vault_bump: u8,
     pub token_vault_bump: u8,
     pub lp_reserve_bump: u8,
+    /// Decimals of the token mint
+    pub token_decimals: u8,
+    /// Set by migrate_to_amm until confirm_amm_pool or abort_migration settles it; trades are rejected
+    pub migration_in_progress: bool,
+    /// Net SOL owed to sellers in pending withdrawals; held in the vault outside real_sol_reserves
+    pub pending_withdrawal_sol: u64,
+    /// Tokens permanently burned, by holders via burn_tokens and by sells
+    pub tokens_burned: u64,
+    /// Token metadata URI (creator-updatable)
+    pub uri: String,
+    /// Trading halted by the token creator; the global pause still overrides
+    pub creator_paused: bool,
+    /// Optional absolute ceiling on the migration fee; the excess stays in the migrated reserves
+    pub max_migration_fee_lamports: Option<u64>,
+    /// When the price last rose to the threshold-implied level; 0 while below it
+    pub price_checkpoint_at: i64,
+    /// Partner wallet receiving this curve's platform fee instead of the platform fee vault
+    pub platform_fee_recipient: Option<Pubkey>,
+    /// When migrate_to_amm completed; 0 while not migrated
+    pub migrated_at: i64,
+    /// AMM pool confirmed live by the multi-sig; a confirmed migration can no longer be aborted
+    pub amm_pool_confirmed: bool,
+    /// Creator fee split set at launch; all slots disabled pays the global creator wallet
+    pub creator_fee_shares: [CreatorFeeShare; MAX_CREATOR_FEE_RECIPIENTS],
+    /// Migration fee booked into global.total_fees_collected by migrate_to_amm; 0 while not migrated
+    pub migration_fee_collected: u64,
     /// Reserved space
-    pub reserved: [u64; 4],
+    pub reserved: [u64; 3],
 }
 
 impl BondingCurve {
@@ -158,44 +403,181 @@ impl BondingCurve {
         1 + // sol_vault_bump
         1 + // token_vault_bump
         1 + // lp_reserve_bump
-        32; // reserved
+        1 + // token_decimals
+        1 + // migration_in_progress
+        8 + // pending_withdrawal_sol
+        8 + // tokens_burned
+        4 + 200 + // uri (String)
+        1 + // creator_paused
+        9 + // max_migration_fee_lamports (Option<u64>)
+        8 + // price_checkpoint_at
+        33 + // platform_fee_recipient (Option<Pubkey>)
+        8 + // migrated_at
+        1 + // amm_pool_confirmed
+        CreatorFeeShare::LEN * MAX_CREATOR_FEE_RECIPIENTS + // creator_fee_shares
+        8 + // migration_fee_collected
+        24; // reserved
+
+    /// Partner wallet owed this curve's platform fee; None once the wallet is delisted,
+    /// in which case fees fall back to the platform fee vault
+    pub fn partner_fee_recipient(&self, global: &Global) -> Option<Pubkey> {
+        self.platform_fee_recipient
+            .filter(|wallet| global.is_partner_fee_wallet(wallet))
+    }
+
+    /// Check if the creator fee is split between the curve's own recipients
+    pub fn has_creator_fee_split(&self) -> bool {
+        self.creator_fee_shares.iter().any(|share| share.is_enabled())
+    }
+
+    /// Creator fee owed to each enabled recipient, in slot order; the last one
+    /// takes the rounding remainder so the parts always sum to `creator_fee`
+    pub fn split_creator_fee(&self, creator_fee: u64) -> Result<Vec<(Pubkey, u64)>> {
+        let shares: Vec<&CreatorFeeShare> = self.creator_fee_shares
+            .iter()
+            .filter(|share| share.is_enabled())
+            .collect();
+
+        let mut remaining = creator_fee;
+        let mut amounts = Vec::with_capacity(shares.len());
+        for (index, share) in shares.iter().enumerate() {
+            let amount = if index + 1 == shares.len() {
+                remaining
+            } else {
+                creator_fee
+                    .checked_mul(share.basis_points as u64)
+                    .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+                    .ok_or(BondingCurveError::Overflow)?
+            };
+            remaining = remaining
+                .checked_sub(amount)
+                .ok_or(BondingCurveError::Underflow)?;
+            amounts.push((share.recipient, amount));
+        }
+        Ok(amounts)
+    }
+
+    /// Check the curve still trades: not migrated, not locked by a migration in flight
+    /// and not halted by its creator
+    pub fn require_trading_open(&self) -> Result<()> {
+        require!(!self.is_migrated, BondingCurveError::AlreadyMigrated);
+        require!(!self.migration_in_progress, BondingCurveError::MigrationInProgress);
+        require!(!self.creator_paused, BondingCurveError::CreatorPaused);
+        Ok(())
+    }
 
     /// Check if migration threshold is met
     pub fn is_migration_threshold_met(&self) -> bool {
         self.real_sol_reserves >= self.migration_threshold
     }
 
+    /// Whether the price has held at the threshold-implied level for at least `min_seconds`
+    pub fn is_price_sustained(&self, timestamp: i64, min_seconds: i64) -> bool {
+        self.price_checkpoint_at != 0
+            && timestamp.saturating_sub(self.price_checkpoint_at) >= min_seconds
+    }
+
+    /// SOL the migration fee is charged on: reserves capped at the threshold, so a
+    /// final buy that overshoots it does not inflate the fee
+    pub fn migration_fee_basis(&self) -> u64 {
+        self.real_sol_reserves.min(self.migration_threshold)
+    }
+
+    /// Apply the per-curve migration fee ceiling, if one was set at launch
+    pub fn cap_migration_fee(&self, migration_fee: u64) -> u64 {
+        match self.max_migration_fee_lamports {
+            Some(max_fee) => migration_fee.min(max_fee),
+            None => migration_fee,
+        }
+    }
+
+    /// Reserves above the migration threshold; seeded into the AMM pool fee-free
+    pub fn migration_overshoot(&self) -> u64 {
+        self.real_sol_reserves.saturating_sub(self.migration_threshold)
+    }
+
+    /// Sync migration_ready with current reserves in both directions; returns true if it changed.
+    /// Price rises with real SOL reserves, so meeting the threshold is the same as the price
+    /// being at or above the threshold-implied level; the checkpoint records since when.
+    pub fn refresh_migration_ready(&mut self, timestamp: i64) -> bool {
+        let threshold_met = self.is_migration_threshold_met();
+        if !threshold_met {
+            self.price_checkpoint_at = 0;
+        } else if self.price_checkpoint_at == 0 {
+            self.price_checkpoint_at = timestamp;
+        }
+
+        if threshold_met == self.migration_ready {
+            return false;
+        }
+        self.migration_ready = threshold_met;
+        true
+    }
+
+    /// Move an active curve's migration threshold; returns the previous one. The new threshold
+    /// must stay above current reserves so it can't instantly trigger migration.
+    pub fn update_migration_threshold(&mut self, new_threshold: u64, timestamp: i64) -> Result<u64> {
+        require!(
+            new_threshold > self.real_sol_reserves,
+            BondingCurveError::InvalidMigrationThreshold
+        );
+        require!(
+            new_threshold <= MAX_MIGRATION_THRESHOLD,
+            BondingCurveError::InvalidMigrationThreshold
+        );
+
+        let previous_threshold = self.migration_threshold;
+        self.migration_threshold = new_threshold;
+        self.refresh_migration_ready(timestamp);
+        Ok(previous_threshold)
+    }
+
     /// Calculate current price in SOL per token
     pub fn current_price(&self) -> Result<u64> {
         let total_sol = self.virtual_sol_reserves
             .checked_add(self.real_sol_reserves)
             .ok_or(BondingCurveError::Overflow)?;
         
-        let total_tokens = self.virtual_token_reserves
-            .checked_sub(self.real_token_reserves)
-            .ok_or(BondingCurveError::Underflow)?;
+        // Normalize to TOKEN_DECIMALS so the price scale is the same for every mint
+        let total_tokens = self.circulating_supply()?
+            .checked_mul(self.decimals_multiplier()?)
+            .ok_or(BondingCurveError::Overflow)?;
 
         if total_tokens == 0 {
             return Err(BondingCurveError::DivisionByZero.into());
         }
 
-        // Enhanced precision scaling with overflow protection
-        const PRECISION_SCALE: u64 = 1_000_000_000;
-        
-        // Check if multiplication would overflow before doing it
-        if total_sol > u64::MAX / PRECISION_SCALE {
-            return Err(BondingCurveError::Overflow.into());
-        }
-        
-        let scaled_sol = total_sol
-            .checked_mul(PRECISION_SCALE)
+        // Enhanced precision scaling in u128; the virtual SOL reserves alone exceed
+        // u64::MAX / PRICE_PRECISION_SCALE
+        let scaled_sol = (total_sol as u128)
+            .checked_mul(PRICE_PRECISION_SCALE as u128)
             .ok_or(BondingCurveError::Overflow)?;
             
         scaled_sol
-            .checked_div(total_tokens)
-            .ok_or(BondingCurveError::DivisionByZero.into())
+            .checked_div(total_tokens as u128)
+            .ok_or(BondingCurveError::DivisionByZero)?
+            .try_into()
+            .map_err(|_| BondingCurveError::Overflow.into())
     }
     
+    /// Tokens held outside the curve, net of burns; the token side of every price
+    pub fn circulating_supply(&self) -> Result<u64> {
+        self.virtual_token_reserves
+            .checked_sub(self.real_token_reserves)
+            .and_then(|x| x.checked_sub(self.tokens_burned))
+            .ok_or(BondingCurveError::Underflow.into())
+    }
+
+    /// Factor converting this mint's base units to TOKEN_DECIMALS base units
+    pub fn decimals_multiplier(&self) -> Result<u64> {
+        let shift = TOKEN_DECIMALS
+            .checked_sub(self.token_decimals)
+            .ok_or(BondingCurveError::InvalidTokenDecimals)?;
+        10u64
+            .checked_pow(shift as u32)
+            .ok_or(BondingCurveError::Overflow.into())
+    }
+
     /// Enhanced validation for trading operations
     pub fn validate_trade_amounts(&self, token_amount: u64, is_buy: bool) -> Result<()> {
         require!(token_amount > 0, BondingCurveError::InvalidTokenAmount);
@@ -208,9 +590,7 @@ impl BondingCurve {
             );
         } else {
             // For sells, ensure user doesn't try to sell more than circulating supply
-            let circulating_supply = self.virtual_token_reserves
-                .checked_sub(self.real_token_reserves)
-                .ok_or(BondingCurveError::Underflow)?;
+            let circulating_supply = self.circulating_supply()?;
             require!(
                 token_amount <= circulating_supply,
                 BondingCurveError::InvalidTokenAmount
@@ -219,6 +599,210 @@ impl BondingCurve {
         
         Ok(())
     }
+
+    /// Implied migration price as sqrt(SOL per token) in Q64.64, from real reserves
+    pub fn migration_sqrt_price_x64(&self) -> Result<u128> {
+        sqrt_price_x64_from_amounts(self.real_sol_reserves, self.real_token_reserves)
+    }
+
+    /// Amounts and opening pool state migrate_to_amm would produce right now
+    pub fn migration_preview(&self, global: &Global, lp_reserve_tokens: u64) -> Result<MigrationPreview> {
+        // Fee is charged on reserves capped at the threshold; any overshoot from
+        // the final buy is not charged and moves to the AMM with the rest of the reserves
+        let migration_overshoot = self.migration_overshoot();
+        let uncapped_migration_fee = self.migration_fee_basis()
+            .checked_mul(global.migration_fee_basis_points as u64)
+            .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+            .ok_or(BondingCurveError::Overflow)?;
+        let migration_fee = self.cap_migration_fee(uncapped_migration_fee);
+
+        // Split the migration fee between the fund and platform wallets
+        let fund_migration_fee = migration_fee
+            .checked_mul(global.fund_share_basis_points as u64)
+            .and_then(|x| x.checked_div(BASIS_POINTS_DENOMINATOR))
+            .ok_or(BondingCurveError::Overflow)?;
+        let platform_migration_fee = migration_fee
+            .checked_sub(fund_migration_fee)
+            .ok_or(BondingCurveError::Underflow)?;
+
+        let sol_to_transfer = self.real_sol_reserves
+            .checked_sub(migration_fee)
+            .ok_or(BondingCurveError::Underflow)?;
+
+        // Opening state the AMM pool should be seeded with
+        let initial_sqrt_price_x64 = sqrt_price_x64_from_amounts(sol_to_transfer, lp_reserve_tokens)?;
+
+        Ok(MigrationPreview {
+            sol_to_transfer,
+            tokens_to_transfer: lp_reserve_tokens,
+            uncapped_migration_fee,
+            migration_fee,
+            platform_migration_fee,
+            fund_migration_fee,
+            migration_overshoot,
+            migration_sqrt_price_x64: self.migration_sqrt_price_x64()?,
+            initial_sqrt_price_x64,
+            expected_initial_liquidity: initial_liquidity_from_amounts(sol_to_transfer, lp_reserve_tokens),
+            migration_ready: self.migration_ready,
+        })
+    }
+}
+
+/// BondingCurve as laid out at launch; only read by migrate_bonding_curve_v1.
+/// The layout has no version field, so the account size identifies it.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct BondingCurveV1 {
+    pub token_mint: Pubkey,
+    pub creator: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub virtual_sol_reserves: u64,
+    pub virtual_token_reserves: u64,
+    pub real_sol_reserves: u64,
+    pub real_token_reserves: u64,
+    pub lp_reserve_supply: u64,
+    pub migration_threshold: u64,
+    pub migration_ready: bool,
+    pub is_migrated: bool,
+    pub amm_program_id: Option<Pubkey>,
+    pub amm_pool_address: Option<Pubkey>,
+    pub total_volume_sol: u64,
+    pub total_volume_tokens: u64,
+    pub platform_fees_collected: u64,
+    pub creator_fees_collected: u64,
+    pub buy_count: u32,
+    pub sell_count: u32,
+    pub created_at: i64,
+    pub last_trade_at: i64,
+    pub bump: u8,
+    pub sol_vault_bump: u8,
+    pub token_vault_bump: u8,
+    pub lp_reserve_bump: u8,
+    pub reserved: [u64; 4],
+}
+
+impl BondingCurveV1 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_mint
+        32 + // creator
+        4 + 32 + // name (String)
+        4 + 10 + // symbol (String)
+        8 + // virtual_sol_reserves
+        8 + // virtual_token_reserves
+        8 + // real_sol_reserves
+        8 + // real_token_reserves
+        8 + // lp_reserve_supply
+        8 + // migration_threshold
+        1 + // migration_ready
+        1 + // is_migrated
+        33 + // amm_program_id (Option<Pubkey>)
+        33 + // amm_pool_address (Option<Pubkey>)
+        8 + // total_volume_sol
+        8 + // total_volume_tokens
+        8 + // platform_fees_collected
+        8 + // creator_fees_collected
+        4 + // buy_count
+        4 + // sell_count
+        8 + // created_at
+        8 + // last_trade_at
+        1 + // bump
+        1 + // sol_vault_bump
+        1 + // token_vault_bump
+        1 + // lp_reserve_bump
+        32; // reserved
+
+    /// Read a bonding curve still in the launch layout. An account that was already
+    /// migrated has grown to BondingCurve::LEN, so a second migration is rejected here.
+    pub fn try_from_account_data(data: &[u8]) -> Result<Self> {
+        require!(data.len() == Self::LEN, BondingCurveError::InvalidAccountVersion);
+        require!(
+            &data[..8] == BondingCurve::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Ok(Self::deserialize(&mut &data[8..])?)
+    }
+
+    /// Current layout of this account, with every field added since launch seeded the way
+    /// initialize_bonding_curve would have. Launch mints always had TOKEN_DECIMALS, and a
+    /// curve that already migrated predates abort_migration, so its pool counts as confirmed.
+    pub fn into_current(self) -> BondingCurve {
+        BondingCurve {
+            token_mint: self.token_mint,
+            creator: self.creator,
+            name: self.name,
+            symbol: self.symbol,
+            virtual_sol_reserves: self.virtual_sol_reserves,
+            virtual_token_reserves: self.virtual_token_reserves,
+            real_sol_reserves: self.real_sol_reserves,
+            real_token_reserves: self.real_token_reserves,
+            lp_reserve_supply: self.lp_reserve_supply,
+            migration_threshold: self.migration_threshold,
+            migration_ready: self.migration_ready,
+            is_migrated: self.is_migrated,
+            amm_program_id: self.amm_program_id,
+            amm_pool_address: self.amm_pool_address,
+            total_volume_sol: self.total_volume_sol,
+            total_volume_tokens: self.total_volume_tokens,
+            platform_fees_collected: self.platform_fees_collected,
+            creator_fees_collected: self.creator_fees_collected,
+            buy_count: self.buy_count,
+            sell_count: self.sell_count,
+            created_at: self.created_at,
+            last_trade_at: self.last_trade_at,
+            bump: self.bump,
+            sol_vault_bump: self.sol_vault_bump,
+            token_vault_bump: self.token_vault_bump,
+            lp_reserve_bump: self.lp_reserve_bump,
+            token_decimals: TOKEN_DECIMALS,
+            migration_in_progress: false,
+            pending_withdrawal_sol: 0,
+            tokens_burned: 0,
+            uri: String::new(),
+            creator_paused: false,
+            max_migration_fee_lamports: None,
+            price_checkpoint_at: 0,
+            platform_fee_recipient: None,
+            migrated_at: 0,
+            amm_pool_confirmed: self.is_migrated,
+            creator_fee_shares: [CreatorFeeShare::default(); MAX_CREATOR_FEE_RECIPIENTS],
+            migration_fee_collected: 0,
+            reserved: [0; 3],
+        }
+    }
+}
+
+/// Return data of get_migration_preview; computed exactly as migrate_to_amm does
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct MigrationPreview {
+    pub sol_to_transfer: u64,
+    pub tokens_to_transfer: u64,
+    pub uncapped_migration_fee: u64,
+    pub migration_fee: u64,
+    pub platform_migration_fee: u64,
+    pub fund_migration_fee: u64,
+    pub migration_overshoot: u64,
+    pub migration_sqrt_price_x64: u128,
+    pub initial_sqrt_price_x64: u128,
+    pub expected_initial_liquidity: u128,
+    pub migration_ready: bool,
+}
+
+/// sqrt(SOL per token) in Q64.64 for a pool seeded with the given amounts
+pub fn sqrt_price_x64_from_amounts(sol_amount: u64, token_amount: u64) -> Result<u128> {
+    require!(token_amount > 0, BondingCurveError::DivisionByZero);
+
+    // sqrt(sol << 64) << 48 / sqrt(token << 32) == sqrt(sol / token) << 64
+    let sol_root = integer_sqrt((sol_amount as u128) << 64);
+    let token_root = integer_sqrt((token_amount as u128) << 32);
+
+    (sol_root << 48)
+        .checked_div(token_root)
+        .ok_or(BondingCurveError::DivisionByZero.into())
+}
+
+/// Full-range liquidity sqrt(SOL * tokens) a pool seeded with the given amounts starts with
+pub fn initial_liquidity_from_amounts(sol_amount: u64, token_amount: u64) -> u128 {
+    integer_sqrt((sol_amount as u128) * (token_amount as u128))
 }
 
 #[account]
@@ -235,8 +819,11 @@ pub struct UserVolumeAccumulator {
     pub last_trade_timestamp: i64,
     /// PDA bump
     pub bump: u8,
+    /// SOL volume decayed by age as of last_trade_timestamp; taken from reserved, so
+    /// accounts created before it read 0 and start decaying from their next trade
+    pub decayed_volume: u64,
     /// Reserved space
-    pub reserved: [u64; 2],
+    pub reserved: [u64; 1],
 }
 
 impl UserVolumeAccumulator {
@@ -247,9 +834,202 @@ impl UserVolumeAccumulator {
         4 + // trades_count
         8 + // last_trade_timestamp
         1 + // bump
+        8 + // decayed_volume
+        8; // reserved
+
+    /// Initialize a freshly created accumulator; returns true if it was new
+    pub fn initialize_if_needed(&mut self, user: Pubkey, bump: u8) -> bool {
+        if self.user != Pubkey::default() {
+            return false;
+        }
+        self.user = user;
+        self.volume_sol = 0;
+        self.volume_tokens = 0;
+        self.trades_count = 0;
+        self.last_trade_timestamp = 0;
+        self.bump = bump;
+        self.decayed_volume = 0;
+        true
+    }
+
+    /// Recent volume as of `now`: halves every VOLUME_DECAY_HALF_LIFE_SECONDS,
+    /// interpolated linearly within each half-life
+    pub fn effective_recent_volume(&self, now: i64) -> u64 {
+        let elapsed = now.saturating_sub(self.last_trade_timestamp).max(0);
+        let half_lives = elapsed / VOLUME_DECAY_HALF_LIFE_SECONDS;
+        if half_lives >= 64 {
+            return 0;
+        }
+        let halved = self.decayed_volume >> half_lives;
+        let remainder = (elapsed % VOLUME_DECAY_HALF_LIFE_SECONDS) as u128;
+        let decay = (halved as u128) * remainder / (2 * VOLUME_DECAY_HALF_LIFE_SECONDS as u128);
+        halved - decay as u64
+    }
+
+    /// Decay prior volume to `now`, then add a new trade; call before updating last_trade_timestamp
+    pub fn record_decayed_volume(&mut self, sol_amount: u64, now: i64) -> Result<()> {
+        self.decayed_volume = self.effective_recent_volume(now)
+            .checked_add(sol_amount)
+            .ok_or(BondingCurveError::Overflow)?;
+        Ok(())
+    }
+}
+
+#[account]
+pub struct PendingWithdrawal {
+    /// Seller owed the escrowed SOL
+    pub seller: Pubkey,
+    /// Token mint of the curve the sell went through
+    pub token_mint: Pubkey,
+    /// Net SOL waiting to be claimed
+    pub amount: u64,
+    /// Earliest claim timestamp
+    pub available_at: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Reserved space
+    pub reserved: [u64; 2],
+}
+
+impl PendingWithdrawal {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // seller
+        32 + // token_mint
+        8 + // amount
+        8 + // available_at
+        1 + // bump
         16; // reserved
 }
 
+#[account]
+pub struct CreatorVesting {
+    /// Creator the allocation vests to
+    pub creator: Pubkey,
+    /// Token mint of the curve the dev-buy went through
+    pub token_mint: Pubkey,
+    /// Tokens bought by the dev-buy and held in escrow
+    pub total: u64,
+    /// Tokens already released to the creator
+    pub claimed: u64,
+    /// Vesting start timestamp
+    pub start: i64,
+    /// Seconds after start before anything can be claimed
+    pub cliff: i64,
+    /// Seconds after start until the full allocation is vested
+    pub duration: i64,
+    /// PDA bump
+    pub bump: u8,
+    /// Escrow token account bump
+    pub escrow_bump: u8,
+    /// Reserved space
+    pub reserved: [u64; 2],
+}
+
+impl CreatorVesting {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // creator
+        32 + // token_mint
+        8 + // total
+        8 + // claimed
+        8 + // start
+        8 + // cliff
+        8 + // duration
+        1 + // bump
+        1 + // escrow_bump
+        16; // reserved
+
+    /// Tokens vested by `now`: nothing before the cliff, then linear from start over duration
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff {
+            return Ok(0);
+        }
+        if elapsed >= self.duration {
+            return Ok(self.total);
+        }
+
+        let vested = (self.total as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|x| x.checked_div(self.duration as u128))
+            .ok_or(BondingCurveError::Overflow)?;
+        Ok(vested as u64)
+    }
+
+    /// Vested tokens not yet claimed
+    pub fn claimable_amount(&self, now: i64) -> Result<u64> {
+        let claimable = self.vested_amount(now)?
+            .checked_sub(self.claimed)
+            .ok_or(BondingCurveError::Underflow)?;
+        Ok(claimable)
+    }
+}
+
+#[account]
+pub struct NameRegistry {
+    /// Token mint that claimed the symbol
+    pub token_mint: Pubkey,
+    /// Creator of that token
+    pub creator: Pubkey,
+    /// Lowercased symbol the registry entry is keyed by
+    pub symbol: String,
+    /// Claim timestamp
+    pub registered_at: i64,
+}
+
+impl NameRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // token_mint
+        32 + // creator
+        4 + 10 + // symbol
+        8; // registered_at
+
+    /// Claim this symbol for `token_mint`; an entry that already names a token stays with it
+    pub fn claim(&mut self, token_mint: Pubkey, creator: Pubkey, symbol: &str, timestamp: i64) -> Result<()> {
+        require!(
+            self.token_mint == Pubkey::default(),
+            BondingCurveError::SymbolAlreadyTaken
+        );
+        self.token_mint = token_mint;
+        self.creator = creator;
+        self.symbol = symbol.to_lowercase();
+        self.registered_at = timestamp;
+        Ok(())
+    }
+}
+
+/// Name registry PDA seed for a symbol; case-insensitive so "PEPE" and "pepe" collide
+pub fn symbol_registry_seed(symbol: &str) -> [u8; 32] {
+    anchor_lang::solana_program::hash::hash(symbol.to_lowercase().as_bytes()).to_bytes()
+}
+
+/// Metadata URI bounds shared by launch and update_token_uri; BondingCurve::LEN reserves 200 bytes
+pub fn validate_metadata_uri(uri: &str) -> Result<()> {
+    require!(!uri.is_empty() && uri.len() <= 200, BondingCurveError::InvalidMetadataUri);
+    Ok(())
+}
+
+/// Reject a trade held past the caller's deadline; `None` never expires
+pub fn require_not_expired(deadline: Option<i64>, now: i64) -> Result<()> {
+    if let Some(deadline) = deadline {
+        require!(now <= deadline, BondingCurveError::TransactionExpired);
+    }
+    Ok(())
+}
+
+// Floor square root via Newton's method
+fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
 // Multi-sig validation helpers
 pub fn verify_admin_authority(authority: &Pubkey) -> Result<()> {
     require!(
@@ -275,3 +1055,400 @@ pub enum AuthorityType {
     Creator,
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global_v1() -> GlobalV1 {
+        GlobalV1 {
+            admin_authority: Pubkey::new_unique(),
+            multisig_authority: Pubkey::new_unique(),
+            platform_wallet: Pubkey::new_unique(),
+            creator_wallet: Pubkey::new_unique(),
+            platform_fee_basis_points: 100,
+            creator_fee_basis_points: 50,
+            migration_fee_basis_points: 250,
+            max_slippage_basis_points: 1_000,
+            migration_enabled: true,
+            is_paused: false,
+            total_volume_sol: 1_000_000,
+            total_fees_collected: 2_500,
+            tokens_created: 7,
+            successful_migrations: 2,
+            version: 1,
+            reserved: [0; 8],
+        }
+    }
+
+    #[test]
+    fn v1_global_is_rewritten_in_the_current_layout() {
+        let v1 = global_v1();
+        let (admin_authority, platform_wallet) = (v1.admin_authority, v1.platform_wallet);
+        let mut data = Global::DISCRIMINATOR.to_vec();
+        data.extend(v1.try_to_vec().unwrap());
+        assert_eq!(data.len(), GlobalV1::LEN);
+
+        let global = GlobalV1::try_from_account_data(&data).unwrap().into_current();
+        let mut migrated = Vec::new();
+        global.try_serialize(&mut migrated).unwrap();
+        assert_eq!(migrated.len(), Global::LEN);
+        let global = Global::try_deserialize(&mut migrated.as_slice()).unwrap();
+
+        assert_eq!(global.admin_authority, admin_authority);
+        assert_eq!(global.platform_fee_basis_points, 100);
+        assert_eq!(global.total_fees_collected, 2_500);
+        assert_eq!((global.tokens_created, global.successful_migrations), (7, 2));
+        assert_eq!(global.version, GLOBAL_VERSION);
+        assert_eq!(global.fund_wallet, platform_wallet);
+        assert_eq!(global.fund_share_basis_points, FUND_SHARE_BASIS_POINTS);
+        assert_eq!(global.min_lp_reserve_percentage, MIN_LP_RESERVE_PERCENTAGE);
+        assert_eq!(global.max_lp_reserve_percentage, MAX_LP_RESERVE_PERCENTAGE);
+        assert_eq!(global.withdrawal_delay_seconds, WITHDRAWAL_DELAY_SECONDS);
+        assert_eq!(global.sell_burn_basis_points, SELL_BURN_BASIS_POINTS);
+        assert!(global.volume_fee_tiers.iter().all(|tier| !tier.is_enabled()));
+
+        // Migrating twice is rejected
+        assert!(GlobalV1::try_from_account_data(&migrated).is_err());
+    }
+
+    #[test]
+    fn global_migration_rejects_unexpected_versions() {
+        let mut v1 = global_v1();
+        v1.version = 2;
+        let mut data = Global::DISCRIMINATOR.to_vec();
+        data.extend(v1.try_to_vec().unwrap());
+        assert!(GlobalV1::try_from_account_data(&data).is_err());
+
+        // A different account type of the same size is rejected too
+        let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+        data.extend(global_v1().try_to_vec().unwrap());
+        assert!(GlobalV1::try_from_account_data(&data).is_err());
+    }
+
+    #[test]
+    fn launch_bonding_curve_is_rewritten_in_the_current_layout() {
+        let (token_mint, amm_pool) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let v1 = BondingCurveV1 {
+            token_mint,
+            creator: Pubkey::new_unique(),
+            name: "Launch".to_string(),
+            symbol: "LNCH".to_string(),
+            virtual_sol_reserves: 30_000_000_000,
+            virtual_token_reserves: 1_000_000_000_000_000,
+            real_sol_reserves: 85_000_000_000,
+            real_token_reserves: 200_000_000_000_000,
+            lp_reserve_supply: 200_000_000_000_000,
+            migration_threshold: 85_000_000_000,
+            migration_ready: true,
+            is_migrated: true,
+            amm_program_id: Some(Pubkey::new_unique()),
+            amm_pool_address: Some(amm_pool),
+            total_volume_sol: 120_000_000_000,
+            total_volume_tokens: 600_000_000_000_000,
+            platform_fees_collected: 1_200_000_000,
+            creator_fees_collected: 600_000_000,
+            buy_count: 40,
+            sell_count: 12,
+            created_at: 1_700_000_000,
+            last_trade_at: 1_700_086_400,
+            bump: 255,
+            sol_vault_bump: 254,
+            token_vault_bump: 253,
+            lp_reserve_bump: 252,
+            reserved: [0; 4],
+        };
+        // Accounts were allocated at the full length; short strings leave zeroed padding
+        let mut data = BondingCurve::DISCRIMINATOR.to_vec();
+        data.extend(v1.try_to_vec().unwrap());
+        data.resize(BondingCurveV1::LEN, 0);
+
+        // The launch layout is too short to load as the current one
+        assert!(BondingCurve::try_deserialize(&mut data.as_slice()).is_err());
+
+        let bonding_curve = BondingCurveV1::try_from_account_data(&data).unwrap().into_current();
+        let mut migrated = Vec::new();
+        bonding_curve.try_serialize(&mut migrated).unwrap();
+        assert!(migrated.len() <= BondingCurve::LEN);
+        migrated.resize(BondingCurve::LEN, 0);
+        let bonding_curve = BondingCurve::try_deserialize(&mut migrated.as_slice()).unwrap();
+
+        assert_eq!(bonding_curve.token_mint, token_mint);
+        assert_eq!((bonding_curve.name.as_str(), bonding_curve.symbol.as_str()), ("Launch", "LNCH"));
+        assert_eq!(bonding_curve.real_sol_reserves, 85_000_000_000);
+        assert_eq!(bonding_curve.amm_pool_address, Some(amm_pool));
+        assert_eq!((bonding_curve.buy_count, bonding_curve.sell_count), (40, 12));
+        assert_eq!(bonding_curve.lp_reserve_bump, 252);
+        assert_eq!(bonding_curve.token_decimals, TOKEN_DECIMALS);
+        assert_eq!(bonding_curve.tokens_burned, 0);
+        assert!(bonding_curve.uri.is_empty());
+        assert!(!bonding_curve.has_creator_fee_split());
+        // A pool migrated before abort_migration existed cannot be aborted now
+        assert!(bonding_curve.amm_pool_confirmed);
+
+        // Migrating twice is rejected
+        assert!(BondingCurveV1::try_from_account_data(&migrated).is_err());
+    }
+
+    #[test]
+    fn migration_threshold_moves_only_above_current_reserves() {
+        let mut bonding_curve = BondingCurve {
+            real_sol_reserves: 10_000_000_000,
+            migration_threshold: 5_000_000_000,
+            ..Default::default()
+        };
+        bonding_curve.refresh_migration_ready(1_700_000_000);
+        assert!(bonding_curve.migration_ready);
+
+        // At or below current reserves would make the curve migratable on the spot
+        for new_threshold in [5_000_000_000, 10_000_000_000] {
+            assert_eq!(
+                bonding_curve.update_migration_threshold(new_threshold, 1_700_000_100).unwrap_err(),
+                BondingCurveError::InvalidMigrationThreshold.into()
+            );
+        }
+        assert!(bonding_curve.update_migration_threshold(MAX_MIGRATION_THRESHOLD + 1, 1_700_000_100).is_err());
+
+        // Raising it past the reserves clears readiness and the price checkpoint
+        let previous = bonding_curve.update_migration_threshold(85_000_000_000, 1_700_000_100).unwrap();
+        assert_eq!(previous, 5_000_000_000);
+        assert_eq!(bonding_curve.migration_threshold, 85_000_000_000);
+        assert!(!bonding_curve.migration_ready);
+        assert_eq!(bonding_curve.price_checkpoint_at, 0);
+    }
+
+    #[test]
+    fn buy_and_sell_pauses_are_independent_under_the_emergency_pause() {
+        let mut global = global_v1().into_current();
+        assert!(global.require_buys_open().is_ok() && global.require_sells_open().is_ok());
+
+        // Pausing buys leaves sells open so holders can still exit
+        global.buys_paused = true;
+        assert_eq!(global.require_buys_open().unwrap_err(), BondingCurveError::BuysPaused.into());
+        assert!(global.require_sells_open().is_ok());
+
+        global.buys_paused = false;
+        global.sells_paused = true;
+        assert!(global.require_buys_open().is_ok());
+        assert_eq!(global.require_sells_open().unwrap_err(), BondingCurveError::SellsPaused.into());
+
+        // The emergency pause halts both sides whatever the per-side flags say
+        global.sells_paused = false;
+        global.is_paused = true;
+        assert_eq!(global.require_buys_open().unwrap_err(), BondingCurveError::OperationsPaused.into());
+        assert_eq!(global.require_sells_open().unwrap_err(), BondingCurveError::OperationsPaused.into());
+    }
+
+    #[test]
+    fn migration_targets_only_allowlisted_programs_and_locks_trading() {
+        let mut global = global_v1().into_current();
+        let amm_program = Pubkey::new_unique();
+        assert!(!global.is_allowed_amm_program(&amm_program));
+        // Empty slots never match
+        assert!(!global.is_allowed_amm_program(&Pubkey::default()));
+
+        global.allowed_amm_programs[1] = amm_program;
+        assert!(global.is_allowed_amm_program(&amm_program));
+        assert!(!global.is_allowed_amm_program(&Pubkey::new_unique()));
+
+        // Trading is refused from migrate_to_amm until the migration is settled
+        let mut bonding_curve = BondingCurve::default();
+        assert!(bonding_curve.require_trading_open().is_ok());
+        bonding_curve.migration_in_progress = true;
+        assert_eq!(
+            bonding_curve.require_trading_open().unwrap_err(),
+            BondingCurveError::MigrationInProgress.into()
+        );
+        bonding_curve.is_migrated = true;
+        assert_eq!(
+            bonding_curve.require_trading_open().unwrap_err(),
+            BondingCurveError::AlreadyMigrated.into()
+        );
+    }
+
+    #[test]
+    fn longest_accepted_metadata_fits_the_bonding_curve_account() {
+        assert!(validate_metadata_uri("").is_err());
+        assert!(validate_metadata_uri(&"u".repeat(201)).is_err());
+        let uri = "u".repeat(200);
+        assert!(validate_metadata_uri(&uri).is_ok());
+
+        let bonding_curve = BondingCurve {
+            name: "n".repeat(32),
+            symbol: "s".repeat(10),
+            uri,
+            max_migration_fee_lamports: Some(1),
+            platform_fee_recipient: Some(Pubkey::new_unique()),
+            amm_program_id: Some(Pubkey::new_unique()),
+            amm_pool_address: Some(Pubkey::new_unique()),
+            ..Default::default()
+        };
+        let mut data = Vec::new();
+        bonding_curve.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), BondingCurve::LEN);
+    }
+
+    #[test]
+    fn creator_pause_halts_only_its_own_curve() {
+        let global = global_v1().into_current();
+        let (mut paused, open) = (BondingCurve::default(), BondingCurve::default());
+        paused.creator_paused = true;
+
+        assert_eq!(paused.require_trading_open().unwrap_err(), BondingCurveError::CreatorPaused.into());
+        assert!(open.require_trading_open().is_ok());
+        // The global switches are untouched, so every other curve keeps trading
+        assert!(global.require_buys_open().is_ok() && global.require_sells_open().is_ok());
+
+        paused.creator_paused = false;
+        assert!(paused.require_trading_open().is_ok());
+    }
+
+    #[test]
+    fn a_symbol_is_claimed_once_regardless_of_case() {
+        assert_eq!(symbol_registry_seed("PEPE"), symbol_registry_seed("pepe"));
+        assert_ne!(symbol_registry_seed("PEPE"), symbol_registry_seed("PEP3"));
+
+        let mut name_registry = NameRegistry {
+            token_mint: Pubkey::default(),
+            creator: Pubkey::default(),
+            symbol: String::new(),
+            registered_at: 0,
+        };
+        let (token_mint, creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        name_registry.claim(token_mint, creator, "PePe", 1_700_000_000).unwrap();
+        assert_eq!(name_registry.token_mint, token_mint);
+        assert_eq!(name_registry.symbol, "pepe");
+
+        // A relaunch under the same symbol cannot take the entry over
+        assert_eq!(
+            name_registry.claim(Pubkey::new_unique(), Pubkey::new_unique(), "PEPE", 1_700_000_100).unwrap_err(),
+            BondingCurveError::SymbolAlreadyTaken.into()
+        );
+        assert_eq!((name_registry.token_mint, name_registry.creator), (token_mint, creator));
+    }
+
+    #[test]
+    fn creator_fee_is_split_70_30_between_recipients() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut bonding_curve = BondingCurve::default();
+        assert!(!bonding_curve.has_creator_fee_split());
+
+        bonding_curve.creator_fee_shares[0] = CreatorFeeShare { recipient: first, basis_points: 7_000 };
+        bonding_curve.creator_fee_shares[1] = CreatorFeeShare { recipient: second, basis_points: 3_000 };
+        assert!(bonding_curve.has_creator_fee_split());
+        assert_eq!(
+            bonding_curve.split_creator_fee(1_000_000).unwrap(),
+            vec![(first, 700_000), (second, 300_000)]
+        );
+
+        // The last recipient takes the rounding remainder, so nothing is lost
+        assert_eq!(
+            bonding_curve.split_creator_fee(1_001).unwrap(),
+            vec![(first, 700), (second, 301)]
+        );
+    }
+
+    #[test]
+    fn creator_fee_shares_must_cover_the_fee_once_per_wallet() {
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let share = |recipient, basis_points| CreatorFeeShare { recipient, basis_points };
+        let mut shares = [CreatorFeeShare::default(); MAX_CREATOR_FEE_RECIPIENTS];
+        assert!(CreatorFeeShare::validate_split(&shares).is_ok());
+
+        shares[0] = share(first, 7_000);
+        shares[2] = share(second, 3_000);
+        assert!(CreatorFeeShare::validate_split(&shares).is_ok());
+
+        // Falling short of the whole fee, a repeated wallet and an unset wallet are all rejected
+        for invalid in [
+            [share(first, 7_000), share(second, 2_000), CreatorFeeShare::default(), CreatorFeeShare::default()],
+            [share(first, 7_000), share(first, 3_000), CreatorFeeShare::default(), CreatorFeeShare::default()],
+            [share(first, 7_000), share(Pubkey::default(), 3_000), CreatorFeeShare::default(), CreatorFeeShare::default()],
+        ] {
+            assert_eq!(
+                CreatorFeeShare::validate_split(&invalid).unwrap_err(),
+                BondingCurveError::InvalidCreatorFeeShares.into()
+            );
+        }
+    }
+
+    #[test]
+    fn platform_fee_drops_to_the_lowest_tier_reached() {
+        let mut global = global_v1().into_current();
+        assert_eq!(global.platform_fee_for_volume(u64::MAX), 100);
+
+        global.volume_fee_tiers[0] = VolumeFeeTier { volume_threshold_sol: 10_000_000_000, platform_fee_basis_points: 80 };
+        global.volume_fee_tiers[1] = VolumeFeeTier { volume_threshold_sol: 100_000_000_000, platform_fee_basis_points: 50 };
+        // A disabled tier is ignored even with a lower fee
+        global.volume_fee_tiers[2] = VolumeFeeTier { volume_threshold_sol: 0, platform_fee_basis_points: 10 };
+
+        assert_eq!(global.platform_fee_for_volume(0), 100);
+        assert_eq!(global.platform_fee_for_volume(9_999_999_999), 100);
+        assert_eq!(global.platform_fee_for_volume(10_000_000_000), 80);
+        assert_eq!(global.platform_fee_for_volume(100_000_000_000), 50);
+
+        // A tier never raises the fee above the base rate
+        global.volume_fee_tiers[1].platform_fee_basis_points = 150;
+        assert_eq!(global.platform_fee_for_volume(100_000_000_000), 80);
+    }
+
+    #[test]
+    fn decayed_volume_fits_in_the_original_accumulator() {
+        let mut user_volume = UserVolumeAccumulator {
+            user: Pubkey::new_unique(),
+            volume_sol: 0,
+            volume_tokens: 0,
+            trades_count: 0,
+            last_trade_timestamp: 0,
+            bump: 255,
+            decayed_volume: 0,
+            reserved: [0; 1],
+        };
+        let mut data = Vec::new();
+        user_volume.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UserVolumeAccumulator::LEN);
+        assert_eq!(UserVolumeAccumulator::LEN, 85);
+
+        // Full weight at the trade, three quarters half a half-life later, then halving
+        let start = 1_700_000_000;
+        user_volume.record_decayed_volume(8_000_000_000, start).unwrap();
+        user_volume.last_trade_timestamp = start;
+        let half_life = VOLUME_DECAY_HALF_LIFE_SECONDS;
+        assert_eq!(user_volume.effective_recent_volume(start), 8_000_000_000);
+        assert_eq!(user_volume.effective_recent_volume(start + half_life / 2), 6_000_000_000);
+        assert_eq!(user_volume.effective_recent_volume(start + half_life), 4_000_000_000);
+        assert_eq!(user_volume.effective_recent_volume(start + 2 * half_life), 2_000_000_000);
+        assert_eq!(user_volume.effective_recent_volume(start + 64 * half_life), 0);
+
+        // A later trade adds to what is left of the earlier volume
+        user_volume.record_decayed_volume(1_000_000_000, start + half_life).unwrap();
+        assert_eq!(user_volume.decayed_volume, 5_000_000_000);
+    }
+
+    #[test]
+    fn creator_vesting_releases_nothing_before_the_cliff_then_linearly() {
+        let vesting = CreatorVesting {
+            creator: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            total: 1_000_000,
+            claimed: 250_000,
+            start: 1_700_000_000,
+            cliff: 100,
+            duration: 1_000,
+            bump: 255,
+            escrow_bump: 254,
+            reserved: [0; 2],
+        };
+
+        assert_eq!(vesting.vested_amount(vesting.start - 10).unwrap(), 0);
+        assert_eq!(vesting.vested_amount(vesting.start + 99).unwrap(), 0);
+        assert_eq!(vesting.vested_amount(vesting.start + 100).unwrap(), 100_000);
+        assert_eq!(vesting.vested_amount(vesting.start + 500).unwrap(), 500_000);
+        assert_eq!(vesting.vested_amount(vesting.start + 1_000).unwrap(), 1_000_000);
+        assert_eq!(vesting.vested_amount(i64::MAX).unwrap(), 1_000_000);
+
+        assert_eq!(vesting.claimable_amount(vesting.start + 500).unwrap(), 250_000);
+        // Claimed beyond what has vested is an accounting error, not a negative claim
+        assert!(vesting.claimable_amount(vesting.start + 200).is_err());
+    }
+}